@@ -0,0 +1,44 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use fixed::types::I32F32;
+use private_context_core::chunking::chunk_at;
+use private_context_core::math::{cosine_similarity, to_fixed};
+use sha2::{Digest, Sha256};
+use sp1_zkvm::io;
+
+pub fn main() {
+    // Full source document; the chunk is cut here rather than trusted from the host
+    let document: Vec<u8> = io::read();
+    let chunk_size: u32 = io::read();
+    let chunk_index: u32 = io::read();
+    let query_vec: Vec<f32> = io::read();
+    let chunk_vec: Vec<f32> = io::read();
+    let threshold_f32: f32 = io::read();
+    // Reclaim claim identifier of the web-attested source (all zeros if unattested)
+    let claim_identifier: [u8; 32] = io::read();
+
+    // The committed hash is computed, not supplied, so it binds the chunk to this document
+    let document_hash: [u8; 32] = Sha256::digest(&document).into();
+    let text = core::str::from_utf8(&document).expect("document is not UTF-8");
+    let chunk = chunk_at(text, chunk_size as usize, chunk_index as usize)
+        .expect("chunk index out of range");
+    let chunk_hash: [u8; 32] = Sha256::digest(chunk.as_bytes()).into();
+
+    let query_fixed = to_fixed(&query_vec);
+    let chunk_fixed = to_fixed(&chunk_vec);
+    let threshold = I32F32::from_num(threshold_f32);
+    let similarity = cosine_similarity(&query_fixed, &chunk_fixed);
+    let is_relevant = similarity >= threshold;
+
+    // Same prefix as the main guest so existing decoders keep working
+    io::commit(&document_hash);
+    io::commit(&is_relevant);
+    let similarity_f32: f32 = similarity.to_num();
+    io::commit(&similarity_f32);
+    io::commit(&claim_identifier);
+
+    io::commit(&chunk_hash);
+    io::commit(&chunk_index);
+    io::commit(&chunk_size);
+}
@@ -0,0 +1,37 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use fixed::types::I32F32;
+use private_context_core::exclusion::{all_below, validate_screen, vector_root};
+use sha2::{Digest, Sha256};
+use sp1_zkvm::io;
+
+pub fn main() {
+    // Full screened document, so the chunk vectors can be tied to it
+    let document: Vec<u8> = io::read();
+    let chunk_size: u32 = io::read();
+    // Every chunk vector of the document, and every entity vector of the watch-list
+    let chunk_vectors: Vec<Vec<f32>> = io::read();
+    let watchlist: Vec<Vec<f32>> = io::read();
+    let threshold_f32: f32 = io::read();
+
+    // A chunk left out, or an empty set, would make the verdict vacuously clear
+    let document_hash: [u8; 32] = Sha256::digest(&document).into();
+    let text = core::str::from_utf8(&document).expect("document is not UTF-8");
+    validate_screen(text, chunk_size as usize, &chunk_vectors, &watchlist)
+        .expect("screen doesn't cover the document");
+
+    let chunk_root = vector_root(&chunk_vectors);
+    let list_hash = vector_root(&watchlist);
+    let threshold = I32F32::from_num(threshold_f32);
+
+    // True only if no chunk/entity pair reaches the threshold
+    let is_clear = all_below(&chunk_vectors, &watchlist, threshold);
+
+    // Only commitments and the verdict are public; no similarity score is revealed
+    io::commit(&document_hash);
+    io::commit(&chunk_root);
+    io::commit(&list_hash);
+    io::commit(&threshold_f32);
+    io::commit(&is_clear);
+}
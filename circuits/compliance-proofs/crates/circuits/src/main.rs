@@ -13,6 +13,8 @@ pub fn main() {
     let threshold_f32: f32 = io::read();
     // Read document hash for binding (proof of specific document)
     let document_hash: [u8; 32] = io::read();
+    // Reclaim claim identifier of the web-attested source (all zeros if unattested)
+    let claim_identifier: [u8; 32] = io::read();
 
     // Convert to fixed-point
     let query_fixed = to_fixed(&query_vec);
@@ -36,4 +38,7 @@ pub fn main() {
     // We convert it back to f32 for output clarity, or keep as bits
     let similarity_f32: f32 = similarity.to_num();
     io::commit(&similarity_f32);
+
+    // Appended last so decoders of the first three values keep working
+    io::commit(&claim_identifier);
 }
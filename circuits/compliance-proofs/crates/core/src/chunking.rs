@@ -0,0 +1,113 @@
+use core::ops::Range;
+use serde::{Deserialize, Serialize};
+
+/// Where a chunk sits in the deterministic chunking of its document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkSpan {
+    pub index: u32,
+    /// Maximum chunk length in characters the document was chunked with
+    pub chunk_size: u32,
+}
+
+/// Separators a chunk may end after, most preferred first.
+const SEPARATORS: [&str; 6] = ["\n\n", "\n", ". ", "? ", "! ", " "];
+
+/// Splits `text` into chunks of at most `max_chars` characters and returns their byte ranges.
+///
+/// Runs identically on the host and in the guest, so a proof can re-derive a chunk from the
+/// document it was cut from. Each chunk ends after the most preferred separator in the last
+/// two thirds of its window, or mid-word when there is none. Chunks are trimmed of surrounding
+/// whitespace, and whitespace-only chunks are dropped.
+pub fn chunk_ranges(text: &str, max_chars: usize) -> Vec<Range<usize>> {
+    assert!(max_chars > 0, "chunk size must be positive");
+    let mut ranges = Vec::new();
+    let mut start = skip_whitespace(text, 0);
+
+    while start < text.len() {
+        let rest = &text[start..];
+        let end = match rest.char_indices().nth(max_chars) {
+            Some((window_end, _)) => split_point(&rest[..window_end]),
+            None => rest.len(),
+        };
+        let len = rest[..end].trim_end().len();
+        if len > 0 {
+            ranges.push(start..start + len);
+        }
+        start = skip_whitespace(text, start + end);
+    }
+    ranges
+}
+
+/// The chunk at `index`, as [`chunk_ranges`] cuts it.
+pub fn chunk_at(text: &str, max_chars: usize, index: usize) -> Option<&str> {
+    chunk_ranges(text, max_chars)
+        .get(index)
+        .map(|range| &text[range.clone()])
+}
+
+fn skip_whitespace(text: &str, from: usize) -> usize {
+    from + (text[from..].len() - text[from..].trim_start().len())
+}
+
+fn split_point(window: &str) -> usize {
+    let min_end = window.len() / 3;
+    SEPARATORS
+        .iter()
+        .find_map(|sep| {
+            window
+                .rfind(sep)
+                .map(|i| i + sep.len())
+                .filter(|&end| end > min_end)
+        })
+        .unwrap_or(window.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Fund Terms\n\nThe fund is open to accredited investors. Minimum \
+        subscription is USD 250,000.\n\nRedemptions are quarterly with 90 days notice. \
+        Investors in the UAE must hold a VARA licence or qualify as professional clients.";
+
+    #[test]
+    fn test_chunks_are_trimmed_substrings_within_size() {
+        let ranges = chunk_ranges(DOC, 60);
+        assert!(ranges.len() > 3);
+        for range in &ranges {
+            let chunk = &DOC[range.clone()];
+            assert!(chunk.chars().count() <= 60, "{:?}", chunk);
+            assert_eq!(chunk, chunk.trim());
+            assert!(!chunk.is_empty());
+        }
+        assert!(ranges.windows(2).all(|w| w[0].end <= w[1].start));
+        assert_eq!(chunk_ranges(DOC, 60), ranges);
+    }
+
+    #[test]
+    fn test_prefers_paragraph_and_sentence_breaks() {
+        let chunks: Vec<&str> = chunk_ranges(DOC, 100)
+            .into_iter()
+            .map(|r| &DOC[r])
+            .collect();
+        // The paragraph break wins over the later sentence breaks in the first window
+        assert_eq!(
+            chunks[0],
+            "# Fund Terms\n\nThe fund is open to accredited investors. Minimum subscription is USD 250,000."
+        );
+        assert_eq!(chunks[1], "Redemptions are quarterly with 90 days notice.");
+        assert_eq!(chunk_at(DOC, 100, 1), Some(chunks[1]));
+        assert_eq!(chunk_at(DOC, 100, chunks.len()), None);
+    }
+
+    #[test]
+    fn test_cuts_unbroken_text_on_char_boundaries() {
+        let text = "ééééééééééé";
+        let chunks: Vec<&str> = chunk_ranges(text, 4)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect();
+        assert_eq!(chunks, vec!["éééé", "éééé", "ééé"]);
+        assert!(chunk_ranges("  \n ", 4).is_empty());
+    }
+}
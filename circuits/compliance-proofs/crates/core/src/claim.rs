@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// The web-attested facts carried by a Reclaim proof, decoded from its JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReclaimClaim {
+    /// `0x`-prefixed 32-byte claim identifier
+    pub identifier: String,
+    pub provider: String,
+    /// Address of the attestor session owner
+    pub owner: String,
+    /// Unix seconds at which the claim was made
+    pub timestamp: u64,
+    /// Values the provider extracted from the attested response
+    pub parameters: BTreeMap<String, String>,
+    #[serde(default)]
+    pub provider_hash: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawProof {
+    identifier: String,
+    claim_data: RawClaimData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawClaimData {
+    provider: String,
+    owner: String,
+    timestamp_s: u64,
+    #[serde(default)]
+    context: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawContext {
+    #[serde(default)]
+    extracted_parameters: BTreeMap<String, String>,
+    provider_hash: Option<String>,
+}
+
+impl ReclaimClaim {
+    /// Decodes the claim from a Reclaim proof JSON. Does not check signatures;
+    /// run `verifier::verify_proof` first.
+    pub fn from_proof_json(json: &str) -> Result<Self> {
+        let raw: RawProof =
+            serde_json::from_str(json).map_err(|e| anyhow!("Failed to parse proof JSON: {}", e))?;
+
+        // `context` is itself a JSON document serialised into a string
+        let context: RawContext = if raw.claim_data.context.is_empty() {
+            RawContext::default()
+        } else {
+            serde_json::from_str(&raw.claim_data.context).context("Malformed claim context")?
+        };
+
+        let claim = Self {
+            identifier: raw.identifier,
+            provider: raw.claim_data.provider,
+            owner: raw.claim_data.owner,
+            timestamp: raw.claim_data.timestamp_s,
+            parameters: context.extracted_parameters,
+            provider_hash: context.provider_hash,
+        };
+        claim.identifier_bytes()?;
+
+        Ok(claim)
+    }
+
+    pub fn identifier_bytes(&self) -> Result<[u8; 32]> {
+        let hex_str = self.identifier.trim_start_matches("0x");
+        let bytes = hex::decode(hex_str).context("Claim identifier is not hex")?;
+        bytes
+            .try_into()
+            .map_err(|_| anyhow!("Claim identifier must be 32 bytes"))
+    }
+
+    /// `sha256(identifier || document_hash)`: ties this claim to one specific document.
+    pub fn bind_to_document(&self, document_hash: &[u8; 32]) -> Result<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.identifier_bytes()?);
+        hasher.update(document_hash);
+        Ok(hasher.finalize().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_PROOF: &str = include_str!("../../../valid_proof.json");
+
+    #[test]
+    fn test_parse_reclaim_claim() {
+        let claim = ReclaimClaim::from_proof_json(VALID_PROOF).unwrap();
+        assert_eq!(claim.provider, "http");
+        assert_eq!(claim.owner, "0xf70bc71a0cbfe10db345b32774212e16088cd6e3");
+        assert_eq!(claim.timestamp, 1728288586);
+        assert_eq!(claim.parameters["userName"], "providerreclaim");
+        assert_eq!(claim.identifier_bytes().unwrap()[0], 0xc6);
+    }
+
+    #[test]
+    fn test_binding_depends_on_document() {
+        let claim = ReclaimClaim::from_proof_json(VALID_PROOF).unwrap();
+        let a = claim.bind_to_document(&[1u8; 32]).unwrap();
+        let b = claim.bind_to_document(&[2u8; 32]).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a, claim.bind_to_document(&[1u8; 32]).unwrap());
+    }
+}
@@ -0,0 +1,243 @@
+//! Commitments that let a chunk be shown with parts blacked out while still
+//! proving it belongs to the original document.
+//!
+//! Each chunk is split into whitespace-delimited segments. A segment's leaf is
+//! `hash_leaf(segment_salt || text)`, the chunk leaf is the Merkle root of its
+//! segment leaves, and the document root is the Merkle root of its chunk leaves.
+//! A disclosure reveals some segments with their salts and only the leaf hash of
+//! the rest, which is enough to recompute the document root.
+
+use crate::merkle::{self, hex_hash, Hash, MerkleProof};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Where a chunk sits in its document's commitment; stored with the chunk, never exported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkCommitment {
+    #[serde(with = "hex_hash")]
+    pub document_root: Hash,
+    /// Secret from which segment salts are derived
+    #[serde(with = "hex_hash")]
+    pub salt: Hash,
+    pub path: MerkleProof,
+}
+
+/// Derives the document salt from its bytes, so re-ingesting a file yields the same root.
+pub fn document_salt(document: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(b"private-context/disclosure-salt");
+    hasher.update(document);
+    hasher.finalize().into()
+}
+
+/// Splits `text` into segments of a word plus its trailing whitespace; concatenating
+/// them gives back `text`.
+pub fn segments(text: &str) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut prev_ws = false;
+    for (i, c) in text.char_indices() {
+        if prev_ws && !c.is_whitespace() {
+            out.push((start, &text[start..i]));
+            start = i;
+        }
+        prev_ws = c.is_whitespace();
+    }
+    if start < text.len() {
+        out.push((start, &text[start..]));
+    }
+    out
+}
+
+pub fn segment_salt(salt: &Hash, chunk_index: usize, segment_index: usize) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update((chunk_index as u64).to_le_bytes());
+    hasher.update((segment_index as u64).to_le_bytes());
+    hasher.finalize().into()
+}
+
+pub fn segment_leaf(segment_salt: &Hash, text: &str) -> Hash {
+    merkle::hash_leaf(&[segment_salt.as_slice(), text.as_bytes()].concat())
+}
+
+pub fn chunk_leaf(salt: &Hash, chunk_index: usize, content: &str) -> Hash {
+    let leaves: Vec<Hash> = segments(content)
+        .iter()
+        .enumerate()
+        .map(|(i, (_, text))| segment_leaf(&segment_salt(salt, chunk_index, i), text))
+        .collect();
+    merkle::merkle_root(&leaves)
+}
+
+/// Commits to every chunk of one document, returning one commitment per chunk in order.
+pub fn commit_document(document: &[u8], chunks: &[&str]) -> Vec<ChunkCommitment> {
+    let salt = document_salt(document);
+    let leaves: Vec<Hash> = chunks
+        .iter()
+        .enumerate()
+        .map(|(i, content)| chunk_leaf(&salt, i, content))
+        .collect();
+    let document_root = merkle::merkle_root(&leaves);
+
+    (0..leaves.len())
+        .map(|i| ChunkCommitment {
+            document_root,
+            salt,
+            path: merkle::merkle_proof(&leaves, i).expect("index within leaves"),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum DisclosedSegment {
+    Revealed {
+        text: String,
+        #[serde(with = "hex_hash")]
+        salt: Hash,
+    },
+    Redacted {
+        category: String,
+        #[serde(with = "hex_hash")]
+        leaf: Hash,
+    },
+}
+
+/// A redacted chunk plus everything needed to check it against the document root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisclosurePackage {
+    pub source: String,
+    #[serde(with = "hex_hash")]
+    pub document_root: Hash,
+    /// Inclusion path of the chunk leaf in the document tree
+    pub path: MerkleProof,
+    pub segments: Vec<DisclosedSegment>,
+    /// Human-readable rendering of `segments`
+    pub redacted_text: String,
+}
+
+impl DisclosurePackage {
+    /// Builds a package from a chunk, hiding the segments for which `redact` returns a category.
+    pub fn new(
+        source: &str,
+        content: &str,
+        commitment: &ChunkCommitment,
+        redact: impl Fn(usize, &str) -> Option<String>,
+    ) -> Self {
+        let chunk_index = commitment.path.index;
+        let segments: Vec<DisclosedSegment> = segments(content)
+            .into_iter()
+            .enumerate()
+            .map(|(i, (offset, text))| {
+                let salt = segment_salt(&commitment.salt, chunk_index, i);
+                match redact(offset, text) {
+                    Some(category) => DisclosedSegment::Redacted {
+                        category,
+                        leaf: segment_leaf(&salt, text),
+                    },
+                    None => DisclosedSegment::Revealed {
+                        text: text.to_string(),
+                        salt,
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            source: source.to_string(),
+            document_root: commitment.document_root,
+            path: commitment.path.clone(),
+            redacted_text: render(&segments),
+            segments,
+        }
+    }
+
+    /// Recomputes the chunk leaf from the disclosed segments and checks it against
+    /// `document_root`.
+    pub fn verify(&self) -> Result<()> {
+        let leaves: Vec<Hash> = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                DisclosedSegment::Revealed { text, salt } => segment_leaf(salt, text),
+                DisclosedSegment::Redacted { leaf, .. } => *leaf,
+            })
+            .collect();
+        let chunk_leaf = merkle::merkle_root(&leaves);
+
+        if !self.path.verify(chunk_leaf, &self.document_root) {
+            bail!("Disclosed chunk does not match the document root");
+        }
+        if self.redacted_text != render(&self.segments) {
+            bail!("Redacted text does not match the disclosed segments");
+        }
+        Ok(())
+    }
+}
+
+/// Concatenates revealed text, collapsing each run of same-category redactions into `[CATEGORY]`.
+fn render(segments: &[DisclosedSegment]) -> String {
+    let mut out = String::new();
+    let mut open: Option<&str> = None;
+    for segment in segments {
+        match segment {
+            DisclosedSegment::Revealed { text, .. } => {
+                open = None;
+                out.push_str(text);
+            }
+            DisclosedSegment::Redacted { category, .. } => {
+                if open != Some(category.as_str()) {
+                    out.push_str(&format!("[{}] ", category.to_uppercase()));
+                    open = Some(category);
+                }
+            }
+        }
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "Holder: Jane Doe\nAccount 12345678 is in good standing.";
+
+    fn disclose(chunk: usize, redact: &[&str]) -> DisclosurePackage {
+        let chunks = ["Holder: Jane Doe", "Account 12345678 is in good standing."];
+        let commitments = commit_document(DOC.as_bytes(), &chunks);
+        DisclosurePackage::new("doc.txt", chunks[chunk], &commitments[chunk], |_, text| {
+            redact.contains(&text.trim()).then(|| "name".to_string())
+        })
+    }
+
+    #[test]
+    fn test_segments_round_trip() {
+        let text = "  a  b\nc ";
+        let joined: String = segments(text).iter().map(|(_, s)| *s).collect();
+        assert_eq!(joined, text);
+        assert_eq!(segments(text)[1], (2, "a  "));
+    }
+
+    #[test]
+    fn test_redacted_package_verifies() {
+        let package = disclose(0, &["Jane", "Doe"]);
+        assert_eq!(package.redacted_text, "Holder: [NAME]");
+        package.verify().unwrap();
+        assert!(disclose(1, &[]).verify().is_ok());
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let mut package = disclose(0, &["Doe"]);
+        if let DisclosedSegment::Revealed { text, .. } = &mut package.segments[1] {
+            *text = "John ".to_string();
+        }
+        assert!(package.verify().is_err());
+
+        let mut package = disclose(0, &["Doe"]);
+        package.redacted_text = "Holder: Jane Roe".to_string();
+        assert!(package.verify().is_err());
+    }
+}
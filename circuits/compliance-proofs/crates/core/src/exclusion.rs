@@ -0,0 +1,95 @@
+use crate::chunking::chunk_ranges;
+use crate::math::{cosine_similarity, to_fixed};
+use crate::merkle::{hash_leaf, merkle_root, Hash};
+use fixed::types::I32F32;
+
+/// Leaf committing to one embedding: its components as little-endian f32 bytes.
+pub fn vector_leaf(vector: &[f32]) -> Hash {
+    let bytes: Vec<u8> = vector.iter().flat_map(|x| x.to_le_bytes()).collect();
+    hash_leaf(&bytes)
+}
+
+/// Merkle root over a set of embeddings, in order. Exclusion proofs commit the document's
+/// chunk vectors and the watch-list's entity vectors this way.
+pub fn vector_root(vectors: &[Vec<f32>]) -> Hash {
+    let leaves: Vec<Hash> = vectors.iter().map(|v| vector_leaf(v)).collect();
+    merkle_root(&leaves)
+}
+
+/// Checks that a screen covers the whole document: one chunk vector per chunk [`chunk_ranges`]
+/// cuts from `document`, and a non-empty watch-list. Either set being empty would make
+/// [`all_below`] vacuously true. Runs in the guest so the prover can't leave chunks out.
+pub fn validate_screen(
+    document: &str,
+    chunk_size: usize,
+    chunks: &[Vec<f32>],
+    watchlist: &[Vec<f32>],
+) -> Result<(), &'static str> {
+    if watchlist.is_empty() {
+        return Err("watch-list is empty");
+    }
+    if chunks.is_empty() {
+        return Err("document has no chunks");
+    }
+    if chunk_ranges(document, chunk_size).len() != chunks.len() {
+        return Err("chunk vectors don't match the document's chunks");
+    }
+    Ok(())
+}
+
+/// Whether every chunk scores below `threshold` against every watch-list entry, in the
+/// guest's fixed-point arithmetic. Vacuously true when either set is empty, which
+/// [`validate_screen`] rejects.
+pub fn all_below(chunks: &[Vec<f32>], watchlist: &[Vec<f32>], threshold: I32F32) -> bool {
+    let watchlist: Vec<Vec<I32F32>> = watchlist.iter().map(|v| to_fixed(v)).collect();
+    chunks.iter().all(|chunk| {
+        let chunk = to_fixed(chunk);
+        watchlist
+            .iter()
+            .all(|entry| cosine_similarity(&chunk, entry) < threshold)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_root_depends_on_every_vector() {
+        let vectors = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.6, 0.8]];
+        let root = vector_root(&vectors);
+        assert_eq!(root, vector_root(&vectors.clone()));
+
+        let mut changed = vectors.clone();
+        changed[2][1] = 0.80001;
+        assert_ne!(root, vector_root(&changed));
+        assert_ne!(root, vector_root(&vectors[..2]));
+    }
+
+    #[test]
+    fn test_all_below_fails_on_any_close_pair() {
+        let chunks = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]];
+        let threshold = I32F32::from_num(0.9);
+
+        assert!(all_below(&chunks, &[vec![0.0, 0.0, 1.0]], threshold));
+        assert!(!all_below(
+            &chunks,
+            &[vec![0.0, 0.0, 1.0], vec![0.1, 1.0, 0.0]],
+            threshold
+        ));
+        assert!(all_below(&chunks, &[], threshold));
+    }
+
+    #[test]
+    fn test_validate_screen_needs_every_chunk() {
+        let document = "First sentence here. Second sentence here.";
+        let chunks = vec![vec![1.0, 0.0]; chunk_ranges(document, 25).len()];
+        let watchlist = vec![vec![0.0, 1.0]];
+        assert_eq!(chunks.len(), 2);
+
+        assert!(validate_screen(document, 25, &chunks, &watchlist).is_ok());
+        assert!(validate_screen(document, 25, &chunks[..1], &watchlist).is_err());
+        assert!(validate_screen(document, 25, &chunks, &[]).is_err());
+        assert!(validate_screen("", 25, &[], &watchlist).is_err());
+    }
+}
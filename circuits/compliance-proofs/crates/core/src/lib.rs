@@ -1,7 +1,15 @@
+pub mod chunking;
+pub mod claim;
+pub mod disclosure;
+pub mod exclusion;
 pub mod math;
+pub mod merkle;
 #[cfg(feature = "reclaim-rust-sdk")]
 pub mod verifier;
 
+use crate::chunking::ChunkSpan;
+use crate::claim::ReclaimClaim;
+use crate::disclosure::ChunkCommitment;
 use serde::{Deserialize, Serialize};
 
 /// Represents a chunk of a document with its associated metadata.
@@ -17,6 +25,21 @@ pub struct ChunkMetadata {
     pub source: String,
     pub proof_id: Option<String>,
     pub provider: Option<String>,
+    /// SHA-256 of the embedding model weights that produced this chunk's vector.
+    #[serde(default)]
+    pub model_hash: Option<String>,
+    /// Decoded Reclaim claim attesting the source document.
+    #[serde(default)]
+    pub claim: Option<ReclaimClaim>,
+    /// Hex `sha256(claim identifier || document hash)` binding the claim to the source file.
+    #[serde(default)]
+    pub claim_binding: Option<String>,
+    /// Position in the source document's Merkle commitment, used for redacted disclosures.
+    #[serde(default)]
+    pub commitment: Option<ChunkCommitment>,
+    /// Position in the source's deterministic chunking, for proofs that re-derive the chunk.
+    #[serde(default)]
+    pub span: Option<ChunkSpan>,
 }
 
 pub trait VectorStore {
@@ -31,6 +31,25 @@ pub fn cosine_similarity(a: &[I32F32], b: &[I32F32]) -> I32F32 {
     dot / (mag_a * mag_b)
 }
 
+/// Cosine similarity in host f32 arithmetic, for comparison with the guest's fixed-point result.
+pub fn cosine_similarity_f32(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let mag_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let mag_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if mag_a == 0.0 || mag_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (mag_a * mag_b)
+}
+
+/// Cosine similarity implied by the L2 distance between two unit vectors, which is what the
+/// HNSW store ranks by.
+pub fn cosine_from_l2(distance: f32) -> f32 {
+    1.0 - distance * distance / 2.0
+}
+
 /// Helper to convert f32 slice to I32F32 slice
 pub fn to_fixed(v: &[f32]) -> Vec<I32F32> {
     v.iter().map(|&x| I32F32::from_num(x)).collect()
@@ -59,4 +78,20 @@ mod tests {
         let c = to_fixed(&[0.0, 1.0, 0.0]);
         assert_eq!(cosine_similarity(&a, &c), I32F32::from_num(0));
     }
+
+    #[test]
+    fn test_host_paths_agree_with_fixed_point() {
+        let a = [0.6, 0.8, 0.0];
+        let b = [0.8, 0.0, 0.6];
+        let fixed: f32 = cosine_similarity(&to_fixed(&a), &to_fixed(&b)).to_num();
+        assert!((cosine_similarity_f32(&a, &b) - fixed).abs() < 1e-6);
+
+        let distance = a
+            .iter()
+            .zip(&b)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f32>()
+            .sqrt();
+        assert!((cosine_from_l2(distance) - fixed).abs() < 1e-6);
+    }
 }
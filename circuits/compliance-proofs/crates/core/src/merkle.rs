@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+/// Domain separation so a leaf can never be passed off as an inner node.
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn hash_leaf(data: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+pub fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Root over already-hashed leaves. An unpaired node is promoted to the next level
+/// unchanged; the empty tree has an all-zero root.
+pub fn merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Inclusion path for one leaf.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub leaf_count: usize,
+    #[serde(with = "hex_hashes")]
+    pub siblings: Vec<Hash>,
+}
+
+/// Builds the inclusion path of `leaves[index]`.
+pub fn merkle_proof(leaves: &[Hash], index: usize) -> Option<MerkleProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut siblings = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut idx = index;
+    while level.len() > 1 {
+        let sibling = idx ^ 1;
+        if sibling < level.len() {
+            siblings.push(level[sibling]);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        idx /= 2;
+    }
+    Some(MerkleProof {
+        index,
+        leaf_count: leaves.len(),
+        siblings,
+    })
+}
+
+impl MerkleProof {
+    /// Recomputes the root from `leaf`, or `None` if the path has the wrong shape.
+    pub fn root_from(&self, leaf: Hash) -> Option<Hash> {
+        if self.index >= self.leaf_count {
+            return None;
+        }
+        let mut siblings = self.siblings.iter();
+        let mut hash = leaf;
+        let mut idx = self.index;
+        let mut width = self.leaf_count;
+        while width > 1 {
+            if idx % 2 == 1 {
+                hash = hash_node(siblings.next()?, &hash);
+            } else if idx + 1 < width {
+                hash = hash_node(&hash, siblings.next()?);
+            }
+            idx /= 2;
+            width = width.div_ceil(2);
+        }
+        if siblings.next().is_some() {
+            return None;
+        }
+        Some(hash)
+    }
+
+    pub fn verify(&self, leaf: Hash, root: &Hash) -> bool {
+        self.root_from(leaf).as_ref() == Some(root)
+    }
+}
+
+/// Serde helpers that write hashes as hex strings.
+pub mod hex_hash {
+    use super::Hash;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(hash: &Hash, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Hash, D::Error> {
+        let s = String::deserialize(d)?;
+        let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("expected 32-byte hex hash"))
+    }
+}
+
+pub mod hex_hashes {
+    use super::Hash;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(hashes: &[Hash], s: S) -> Result<S::Ok, S::Error> {
+        hashes
+            .iter()
+            .map(hex::encode)
+            .collect::<Vec<_>>()
+            .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Hash>, D::Error> {
+        Vec::<String>::deserialize(d)?
+            .into_iter()
+            .map(|s| {
+                let bytes = hex::decode(&s).map_err(serde::de::Error::custom)?;
+                bytes
+                    .try_into()
+                    .map_err(|_| serde::de::Error::custom("expected 32-byte hex hash"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: u8) -> Vec<Hash> {
+        (0..n).map(|i| hash_leaf(&[i])).collect()
+    }
+
+    #[test]
+    fn test_every_leaf_proves_against_root() {
+        for n in 1..=9 {
+            let leaves = leaves(n);
+            let root = merkle_root(&leaves);
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = merkle_proof(&leaves, i).unwrap();
+                assert!(proof.verify(*leaf, &root), "n={} i={}", n, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrong_leaf_or_path_fails() {
+        let leaves = leaves(5);
+        let root = merkle_root(&leaves);
+        let mut proof = merkle_proof(&leaves, 2).unwrap();
+
+        assert!(!proof.verify(leaves[3], &root));
+        proof.siblings.push([0u8; 32]);
+        assert!(proof.root_from(leaves[2]).is_none());
+        assert!(merkle_proof(&leaves, 5).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Differential checks between the similarity the host computes in f32 and the one the
+//! relevance guest computes in I32F32.
+use private_context_core::math::{
+    cosine_from_l2, cosine_similarity, cosine_similarity_f32, to_fixed,
+};
+use serde::Serialize;
+
+/// One pair of vectors scored by every path.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Comparison {
+    /// Guest I32F32 cosine similarity, exact in f64 for values in [-1, 1]
+    pub fixed: f64,
+    /// Host f32 cosine similarity, as `prove` checks it
+    pub float: f32,
+    /// Cosine implied by the f32 L2 distance the HNSW store ranks by
+    pub l2: f32,
+}
+
+impl Comparison {
+    pub fn new(a: &[f32], b: &[f32]) -> Self {
+        let distance = a
+            .iter()
+            .zip(b)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f32>()
+            .sqrt();
+        Self {
+            fixed: cosine_similarity(&to_fixed(a), &to_fixed(b)).to_num(),
+            float: cosine_similarity_f32(a, b),
+            l2: cosine_from_l2(distance),
+        }
+    }
+
+    pub fn float_divergence(&self) -> f64 {
+        (self.float as f64 - self.fixed).abs()
+    }
+
+    /// Only meaningful for unit vectors, which every supported embedder produces.
+    pub fn l2_divergence(&self) -> f64 {
+        (self.l2 as f64 - self.fixed).abs()
+    }
+
+    /// Whether host and guest land on different sides of `threshold`.
+    pub fn flips(&self, threshold: f32) -> bool {
+        (self.float >= threshold) != (self.fixed >= threshold as f64)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub vectors: usize,
+    pub pairs: usize,
+    pub threshold: f32,
+    /// Largest |host f32 - guest fixed-point| over all pairs
+    pub max_float_divergence: f64,
+    pub mean_float_divergence: f64,
+    /// Largest |L2-derived cosine - guest fixed-point| over all pairs
+    pub max_l2_divergence: f64,
+    /// Indices of the pair behind `max_float_divergence`
+    pub worst_pair: Option<(usize, usize)>,
+    /// Pairs where host and guest disagree on `is_relevant` at `threshold`
+    pub flips: usize,
+}
+
+impl Report {
+    /// Compares every unordered pair of `vectors`.
+    pub fn run(vectors: &[Vec<f32>], threshold: f32) -> Self {
+        let mut report = Self {
+            vectors: vectors.len(),
+            threshold,
+            ..Default::default()
+        };
+        let mut total = 0.0;
+
+        for (i, a) in vectors.iter().enumerate() {
+            for (j, b) in vectors.iter().enumerate().skip(i + 1) {
+                let comparison = Comparison::new(a, b);
+                let divergence = comparison.float_divergence();
+                total += divergence;
+                report.pairs += 1;
+                if report.worst_pair.is_none() || divergence > report.max_float_divergence {
+                    report.max_float_divergence = divergence;
+                    report.worst_pair = Some((i, j));
+                }
+                report.max_l2_divergence = report.max_l2_divergence.max(comparison.l2_divergence());
+                if comparison.flips(threshold) {
+                    report.flips += 1;
+                }
+            }
+        }
+
+        if report.pairs > 0 {
+            report.mean_float_divergence = total / report.pairs as f64;
+        }
+        report
+    }
+}
+
+/// Splits prose into sentences of at least `min_len` characters for embedding.
+pub fn sentences(text: &str, min_len: usize) -> Vec<&str> {
+    text.split(['.', '?', '!', '\n'])
+        .map(str::trim)
+        .filter(|s| s.chars().count() >= min_len)
+        .collect()
+}
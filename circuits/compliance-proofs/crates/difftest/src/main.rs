@@ -0,0 +1,90 @@
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use private_context_difftest::{sentences, Report};
+use private_context_ingestion::embedder::{CandleEmbedder, Embedder, ModelFamily};
+use private_context_ingestion::pipeline::collect_inputs;
+use std::path::Path;
+
+/// Compares host f32 similarity with the guest's fixed-point similarity over real embeddings.
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Files, directories or quoted globs whose sentences are embedded
+    #[arg(default_values = ["README.md", "technical_architecture.md", "usage_guide.md"])]
+    corpus: Vec<String>,
+    /// Model directory
+    #[arg(long, default_value = "./models/all-MiniLM-L6-v2")]
+    model_dir: String,
+    /// Embedding model family; detected from the model's config.json when omitted
+    #[arg(long, value_enum)]
+    embedder: Option<ModelFamily>,
+    /// Threshold at which host/guest disagreements are counted
+    #[arg(long, default_value_t = 0.7)]
+    threshold: f32,
+    /// Embed at most this many sentences; all pairs among them are compared
+    #[arg(long, default_value_t = 2000)]
+    limit: usize,
+    /// Exit with an error when the maximum divergence reaches this value
+    #[arg(long)]
+    band: Option<f64>,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let mut texts = Vec::new();
+    for input in &cli.corpus {
+        for path in collect_inputs(input)? {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            texts.extend(sentences(&text, 20).into_iter().map(str::to_string));
+        }
+    }
+    texts.truncate(cli.limit);
+    if texts.len() < 2 {
+        bail!("Corpus has {} sentence(s); need at least two", texts.len());
+    }
+
+    let embedder = CandleEmbedder::new(Path::new(&cli.model_dir), cli.embedder)
+        .context("Failed to initialize embedder")?;
+    eprintln!("🧮 Embedding {} sentences...", texts.len());
+    let mut vectors = Vec::with_capacity(texts.len());
+    for batch in texts.chunks(32) {
+        let batch: Vec<&str> = batch.iter().map(String::as_str).collect();
+        vectors.extend(embedder.embed_passages(&batch)?);
+    }
+
+    let report = Report::run(&vectors, cli.threshold);
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("📊 {} pairs of {} embeddings", report.pairs, report.vectors);
+        println!("   max |f32 - I32F32|: {:e}", report.max_float_divergence);
+        println!("   mean |f32 - I32F32|: {:e}", report.mean_float_divergence);
+        println!(
+            "   max |L2 cosine - I32F32|: {:e}",
+            report.max_l2_divergence
+        );
+        println!(
+            "   is_relevant disagreements at {}: {}",
+            report.threshold, report.flips
+        );
+        if let Some((i, j)) = report.worst_pair {
+            println!("   worst pair:\n     {}\n     {}", texts[i], texts[j]);
+        }
+    }
+
+    if let Some(band) = cli.band {
+        if report.max_float_divergence >= band {
+            bail!(
+                "Maximum divergence {:e} reaches the uncertainty band {:e}",
+                report.max_float_divergence,
+                band
+            );
+        }
+    }
+    Ok(())
+}
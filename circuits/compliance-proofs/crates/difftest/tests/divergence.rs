@@ -0,0 +1,109 @@
+//! Host f32 and guest I32F32 similarity must stay well inside the default uncertainty band
+//! that `prove` refuses to decide in.
+
+use private_context_difftest::{sentences, Comparison, Report};
+
+/// `policy.uncertainty` default
+const BAND: f64 = 1e-4;
+const DIMENSION: usize = 384;
+
+/// xorshift, so runs are reproducible without an RNG dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+    }
+
+    fn vector(&mut self) -> Vec<f32> {
+        (0..DIMENSION).map(|_| self.next()).collect()
+    }
+}
+
+fn normalize(v: Vec<f32>) -> Vec<f32> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    v.into_iter().map(|x| x / norm).collect()
+}
+
+/// Clusters of perturbed copies, so similarities span the range around typical thresholds.
+fn embeddings(clusters: usize, per_cluster: usize) -> Vec<Vec<f32>> {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let mut vectors = Vec::new();
+    for _ in 0..clusters {
+        let center = normalize(rng.vector());
+        for k in 0..per_cluster {
+            let noise = 0.2 * k as f32;
+            let v = center
+                .iter()
+                .zip(rng.vector())
+                .map(|(c, n)| c + noise * n / (DIMENSION as f32).sqrt())
+                .collect();
+            vectors.push(normalize(v));
+        }
+    }
+    vectors
+}
+
+#[test]
+fn unit_vectors_stay_inside_band() {
+    let vectors = embeddings(10, 12);
+    let report = Report::run(&vectors, 0.7);
+
+    assert_eq!(report.pairs, 120 * 119 / 2);
+    assert!(report.max_float_divergence < BAND, "{:?}", report);
+    assert!(report.max_l2_divergence < BAND, "{:?}", report);
+    assert_eq!(report.flips, 0, "{:?}", report);
+}
+
+#[test]
+fn comparison_counts_flips_on_threshold_boundary() {
+    let a = [0.6, 0.8];
+    let b = [1.0, 0.0];
+    let comparison = Comparison::new(&a, &b);
+    assert!(comparison.float_divergence() < 1e-6);
+
+    // Exactly on the host score, the guest decides by its own rounding
+    let at = comparison.float;
+    assert_eq!(
+        comparison.flips(at),
+        comparison.fixed < at as f64,
+        "{:?}",
+        comparison
+    );
+    assert!(!comparison.flips(0.5));
+    assert!(!comparison.flips(0.7));
+}
+
+#[test]
+fn splits_sentences() {
+    let text = "Short. The fund is open to accredited investors only!\nIs it?";
+    assert_eq!(
+        sentences(text, 10),
+        vec!["The fund is open to accredited investors only"]
+    );
+}
+
+/// Run with `cargo test -- --ignored` after `./setup_models.sh`.
+#[test]
+#[ignore = "needs the MiniLM weights from setup_models.sh"]
+fn minilm_embeddings_stay_inside_band() {
+    use private_context_ingestion::embedder::{CandleEmbedder, Embedder};
+    use std::path::Path;
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let embedder = CandleEmbedder::new(&root.join("models/all-MiniLM-L6-v2"), None).unwrap();
+    let mut texts = Vec::new();
+    for doc in ["README.md", "technical_architecture.md", "usage_guide.md"] {
+        let text = std::fs::read_to_string(root.join(doc)).unwrap();
+        texts.extend(sentences(&text, 20).into_iter().map(str::to_string));
+    }
+    let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+    let vectors = embedder.embed_passages(&texts).unwrap();
+
+    let report = Report::run(&vectors, 0.7);
+    assert!(report.pairs > 1000, "{:?}", report);
+    assert!(report.max_float_divergence < BAND, "{:?}", report);
+}
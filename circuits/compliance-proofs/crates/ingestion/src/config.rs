@@ -0,0 +1,374 @@
+//! Layered CLI settings: built-in defaults, then the config file, then the selected
+//! profile, then environment variables. Command-line flags are applied last by the caller.
+
+use crate::db::StoreBackend;
+use crate::embedder::ModelFamily;
+use crate::prover::{ProverMode, CHUNKED_ELF_PATH, DEFAULT_ELF_PATH, EXCLUSION_ELF_PATH};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Read from the working directory when no `--config` is given.
+pub const DEFAULT_CONFIG_FILE: &str = "private-context.toml";
+
+/// Profiles available without a config file; a file may extend or override them.
+pub const BUILTIN_PROFILES: &[&str] = &["dev", "mock", "staging", "prod"];
+
+/// Environment variables and the config keys they override.
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("PRIVATE_CONTEXT_DATA_DIR", "data_dir"),
+    ("PRIVATE_CONTEXT_BACKEND", "backend"),
+    ("PRIVATE_CONTEXT_MODEL_DIR", "model_dir"),
+    ("PRIVATE_CONTEXT_EMBEDDER", "embedder"),
+    ("PRIVATE_CONTEXT_HOLDER", "holder"),
+    // SP1's own variable, kept for compatibility; the prefixed one wins
+    ("SP1_PROVER", "prover"),
+    ("PRIVATE_CONTEXT_PROVER", "prover"),
+    ("PRIVATE_CONTEXT_ELF", "elf"),
+    ("PRIVATE_CONTEXT_CHUNKED_ELF", "chunked_elf"),
+    ("PRIVATE_CONTEXT_EXCLUSION_ELF", "exclusion_elf"),
+    ("PRIVATE_CONTEXT_OUTPUT_DIR", "output_dir"),
+    ("PRIVATE_CONTEXT_THRESHOLD", "policy.threshold"),
+    ("PRIVATE_CONTEXT_UNCERTAINTY", "policy.uncertainty"),
+    ("PRIVATE_CONTEXT_VALIDITY_DAYS", "policy.validity_days"),
+    ("PRIVATE_CONTEXT_JURISDICTION", "policy.jurisdiction"),
+    ("PRIVATE_CONTEXT_ACCREDITATION", "policy.accreditation"),
+    ("PRIVATE_CONTEXT_CHAIN_REST", "chain.rest"),
+    ("PRIVATE_CONTEXT_CHAIN_ID", "chain.chain_id"),
+    ("PRIVATE_CONTEXT_CONTRACT", "chain.contract"),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Directory holding the store, lexical index and checkpoints
+    pub data_dir: String,
+    pub backend: StoreBackend,
+    pub model_dir: String,
+    /// Detected from the model's config.json when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedder: Option<ModelFamily>,
+    /// Wallet holder whose encrypted store and proof history commands use instead of `data_dir`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    pub prover: ProverMode,
+    /// Guest ELF used for relevance proofs
+    pub elf: String,
+    /// Guest ELF used by `prove --chunked`
+    pub chunked_elf: String,
+    /// Guest ELF used by `screen`
+    pub exclusion_elf: String,
+    /// Where proofs, public values and exported packages are written
+    pub output_dir: String,
+    pub policy: PolicyConfig,
+    pub chain: ChainConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyConfig {
+    /// Minimum similarity the circuit accepts
+    pub threshold: f64,
+    /// `prove` refuses scores this close to the threshold, where host f32 and guest
+    /// fixed-point similarity may disagree
+    pub uncertainty: f64,
+    /// Days a proof recorded in a holder's wallet stays valid; 0 for no expiry
+    pub validity_days: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jurisdiction: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accreditation: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainConfig {
+    /// Node REST (LCD) endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    /// Verifier contract address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            data_dir: "data".to_string(),
+            backend: StoreBackend::Hnsw,
+            model_dir: "./models/all-MiniLM-L6-v2".to_string(),
+            embedder: None,
+            holder: None,
+            prover: ProverMode::Local,
+            elf: DEFAULT_ELF_PATH.to_string(),
+            chunked_elf: CHUNKED_ELF_PATH.to_string(),
+            exclusion_elf: EXCLUSION_ELF_PATH.to_string(),
+            output_dir: ".".to_string(),
+            policy: PolicyConfig {
+                threshold: 0.7,
+                uncertainty: 1e-4,
+                validity_days: 90,
+                jurisdiction: None,
+                accreditation: None,
+            },
+            chain: ChainConfig::default(),
+        }
+    }
+}
+
+/// Where a resolved config came from, for `config show`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOrigin {
+    pub file: Option<PathBuf>,
+    pub profile: Option<String>,
+}
+
+fn builtin_profile(name: &str) -> Option<Table> {
+    let toml = match name {
+        "dev" | "mock" => {
+            r#"
+            prover = "mock"
+            [chain]
+            rest = "http://localhost:1317"
+            chain_id = "mantra-local-1"
+            "#
+        }
+        "staging" => {
+            r#"
+            prover = "network"
+            data_dir = "data/staging"
+            output_dir = "out/staging"
+            "#
+        }
+        "prod" => {
+            r#"
+            prover = "network"
+            data_dir = "data/prod"
+            output_dir = "out/prod"
+            "#
+        }
+        _ => return None,
+    };
+    Some(toml.parse().expect("built-in profile is valid TOML"))
+}
+
+/// Recursively overlays `top` onto `base`; tables merge, everything else replaces.
+fn merge(base: &mut Table, top: Table) {
+    for (key, value) in top {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(incoming)) => merge(existing, incoming),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn set_path(table: &mut Table, path: &str, value: Value) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            let entry = table
+                .entry(head)
+                .or_insert_with(|| Value::Table(Table::new()));
+            if let Value::Table(inner) = entry {
+                set_path(inner, rest, value);
+            }
+        }
+        None => {
+            table.insert(path.to_string(), value);
+        }
+    }
+}
+
+impl Config {
+    /// Resolves the config from `file` (or `private-context.toml` if present), `profile`
+    /// and the process environment.
+    pub fn load(file: Option<&Path>, profile: Option<&str>) -> Result<(Self, ConfigOrigin)> {
+        let file = match file {
+            Some(path) => Some(path.to_path_buf()),
+            None => Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|p| p.exists()),
+        };
+        let table = match &file {
+            Some(path) => {
+                let text = fs_err::read_to_string(path)?;
+                Some(
+                    text.parse::<Table>()
+                        .with_context(|| format!("Invalid TOML in {}", path.display()))?,
+                )
+            }
+            None => None,
+        };
+
+        let config =
+            Self::resolve(table, profile, |var| std::env::var(var).ok()).with_context(|| {
+                match &file {
+                    Some(path) => format!("Bad configuration in {}", path.display()),
+                    None => "Bad configuration".to_string(),
+                }
+            })?;
+        Ok((
+            config,
+            ConfigOrigin {
+                file,
+                profile: profile.map(str::to_string),
+            },
+        ))
+    }
+
+    /// Layers defaults, the file's top level, the profile and `env` overrides.
+    pub fn resolve(
+        file: Option<Table>,
+        profile: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let mut table = Table::try_from(Self::default())?;
+        let mut file = file.unwrap_or_default();
+        let file_profiles = match file.remove("profiles") {
+            Some(Value::Table(profiles)) => profiles,
+            Some(_) => bail!("`profiles` must be a table of named profiles"),
+            None => Table::new(),
+        };
+        merge(&mut table, file);
+
+        if let Some(name) = profile {
+            let builtin = builtin_profile(name);
+            let from_file = match file_profiles.get(name) {
+                Some(Value::Table(profile)) => Some(profile.clone()),
+                Some(_) => bail!("Profile `{}` must be a table", name),
+                None => None,
+            };
+            if builtin.is_none() && from_file.is_none() {
+                let mut known: Vec<&str> = BUILTIN_PROFILES.to_vec();
+                known.extend(file_profiles.keys().map(String::as_str));
+                bail!("Unknown profile `{}` (known: {})", name, known.join(", "));
+            }
+            for layer in [builtin, from_file].into_iter().flatten() {
+                merge(&mut table, layer);
+            }
+        }
+
+        for (var, path) in ENV_OVERRIDES {
+            if let Some(raw) = env(var) {
+                set_path(&mut table, path, env_value(var, path, raw)?);
+            }
+        }
+
+        table.try_into().map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Joins `name` onto the output directory, creating the directory if needed.
+    pub fn output_path(&self, name: &str) -> Result<PathBuf> {
+        fs_err::create_dir_all(&self.output_dir)?;
+        Ok(Path::new(&self.output_dir).join(name))
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+}
+
+/// Environment values are strings; convert them to what the key expects.
+fn env_value(var: &str, path: &str, raw: String) -> Result<Value> {
+    Ok(match path {
+        "policy.threshold" | "policy.uncertainty" => Value::Float(
+            raw.parse()
+                .map_err(|_| anyhow!("{} must be a number, got `{}`", var, raw))?,
+        ),
+        "policy.validity_days" => Value::Integer(
+            raw.parse()
+                .map_err(|_| anyhow!("{} must be a whole number, got `{}`", var, raw))?,
+        ),
+        // Enum values are lowercase in TOML; accept `SP1_PROVER=MOCK` and the like
+        "backend" | "embedder" | "prover" => Value::String(raw.to_lowercase()),
+        _ => Value::String(raw),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const FILE: &str = r#"
+        data_dir = "/srv/context"
+
+        [policy]
+        jurisdiction = "VARA_DUBAI"
+
+        [profiles.staging]
+        output_dir = "/srv/out"
+
+        [profiles.ci]
+        prover = "mock"
+    "#;
+
+    fn resolve(profile: Option<&str>, env: &[(&str, &str)]) -> Result<Config> {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::resolve(Some(FILE.parse().unwrap()), profile, |var| {
+            env.get(var).cloned()
+        })
+    }
+
+    #[test]
+    fn layers_apply_in_order() {
+        let base = resolve(None, &[]).unwrap();
+        assert_eq!(base.data_dir, "/srv/context");
+        assert_eq!(base.prover, ProverMode::Local);
+        assert_eq!(base.policy.jurisdiction.as_deref(), Some("VARA_DUBAI"));
+        assert_eq!(base.policy.threshold, 0.7);
+
+        // Built-in staging sets data_dir and prover; the file's staging overrides output_dir
+        let staging = resolve(Some("staging"), &[]).unwrap();
+        assert_eq!(staging.data_dir, "data/staging");
+        assert_eq!(staging.output_dir, "/srv/out");
+        assert_eq!(staging.prover, ProverMode::Network);
+
+        let env = resolve(
+            Some("staging"),
+            &[
+                ("SP1_PROVER", "MOCK"),
+                ("PRIVATE_CONTEXT_THRESHOLD", "0.85"),
+                ("PRIVATE_CONTEXT_CHAIN_REST", "http://node:1317"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(env.prover, ProverMode::Mock);
+        assert_eq!(env.policy.threshold, 0.85);
+        assert_eq!(env.chain.rest.as_deref(), Some("http://node:1317"));
+    }
+
+    #[test]
+    fn profiles_come_from_builtins_or_file() {
+        assert_eq!(resolve(Some("ci"), &[]).unwrap().prover, ProverMode::Mock);
+        let dev = Config::resolve(None, Some("dev"), |_| None).unwrap();
+        assert_eq!(dev.prover, ProverMode::Mock);
+        assert_eq!(dev.chain.chain_id.as_deref(), Some("mantra-local-1"));
+
+        let err = resolve(Some("qa"), &[]).unwrap_err().to_string();
+        assert!(err.contains("Unknown profile `qa`"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_typos_and_bad_values() {
+        let typo: Table = "data_directory = \"x\"".parse().unwrap();
+        assert!(Config::resolve(Some(typo), None, |_| None).is_err());
+        assert!(resolve(None, &[("PRIVATE_CONTEXT_THRESHOLD", "high")]).is_err());
+        assert!(resolve(None, &[("PRIVATE_CONTEXT_PROVER", "quantum")]).is_err());
+    }
+
+    #[test]
+    fn shown_config_round_trips() {
+        let config = resolve(Some("dev"), &[]).unwrap();
+        let shown: Table = config.to_toml().unwrap().parse().unwrap();
+        assert_eq!(
+            Config::resolve(Some(shown), None, |_| None).unwrap(),
+            config
+        );
+    }
+}
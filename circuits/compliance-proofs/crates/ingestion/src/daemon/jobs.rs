@@ -0,0 +1,236 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+pub type JobId = u64;
+
+/// Work run on the blocking pool; it should call [`CancelToken::check`] between stages.
+pub type Job = Box<dyn FnOnce(&CancelToken) -> Result<serde_json::Value> + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobKind {
+    Ingest,
+    Prove,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase", tag = "state")]
+pub enum JobState {
+    Queued,
+    Running,
+    Succeeded { result: serde_json::Value },
+    Failed { error: String },
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobState::Succeeded { .. } | JobState::Failed { .. } | JobState::Cancelled
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub id: JobId,
+    pub kind: JobKind,
+    #[serde(flatten)]
+    pub state: JobState,
+}
+
+/// Cooperative cancellation flag shared between the queue and a running job.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            bail!("Job cancelled");
+        }
+        Ok(())
+    }
+}
+
+struct Entry {
+    status: JobStatus,
+    cancel: CancelToken,
+}
+
+/// FIFO queue drained by a single worker, so only one heavy job holds the CPU at a time.
+#[derive(Clone)]
+pub struct JobQueue {
+    entries: Arc<Mutex<HashMap<JobId, Entry>>>,
+    next_id: Arc<AtomicU64>,
+    sender: mpsc::UnboundedSender<(JobId, Job)>,
+}
+
+impl JobQueue {
+    /// Creates the queue and spawns its worker on the current Tokio runtime.
+    pub fn start() -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(JobId, Job)>();
+        let queue = Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+            sender,
+        };
+
+        let worker = queue.clone();
+        tokio::spawn(async move {
+            while let Some((id, job)) = receiver.recv().await {
+                let Some(cancel) = worker.begin(id) else {
+                    continue;
+                };
+
+                let token = cancel.clone();
+                let outcome = tokio::task::spawn_blocking(move || job(&token)).await;
+
+                let state = match outcome {
+                    _ if cancel.is_cancelled() => JobState::Cancelled,
+                    Ok(Ok(result)) => JobState::Succeeded { result },
+                    Ok(Err(e)) => JobState::Failed {
+                        error: format!("{:#}", e),
+                    },
+                    Err(e) => JobState::Failed {
+                        error: format!("Job panicked: {}", e),
+                    },
+                };
+                worker.set_state(id, state);
+            }
+        });
+
+        queue
+    }
+
+    pub fn submit(&self, kind: JobKind, job: Job) -> JobStatus {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let status = JobStatus {
+            id,
+            kind,
+            state: JobState::Queued,
+        };
+        self.entries.lock().unwrap().insert(
+            id,
+            Entry {
+                status: status.clone(),
+                cancel: CancelToken::default(),
+            },
+        );
+        // The worker holds its own handle and lives as long as the runtime
+        let _ = self.sender.send((id, job));
+        status
+    }
+
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|e| e.status.clone())
+    }
+
+    /// Queued jobs are dropped immediately; running jobs stop at their next checkpoint.
+    pub fn cancel(&self, id: JobId) -> Option<JobStatus> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(&id)?;
+        match entry.status.state {
+            JobState::Queued => {
+                entry.cancel.cancel();
+                entry.status.state = JobState::Cancelled;
+            }
+            JobState::Running => entry.cancel.cancel(),
+            _ => {}
+        }
+        Some(entry.status.clone())
+    }
+
+    /// Marks a dequeued job running, or returns `None` if it was cancelled while queued.
+    fn begin(&self, id: JobId) -> Option<CancelToken> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(&id)?;
+        if entry.cancel.is_cancelled() {
+            return None;
+        }
+        entry.status.state = JobState::Running;
+        Some(entry.cancel.clone())
+    }
+
+    fn set_state(&self, id: JobId, state: JobState) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(&id) {
+            entry.status.state = state;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn wait_until_finished(queue: &JobQueue, id: JobId) -> JobState {
+        loop {
+            let state = queue.status(id).unwrap().state;
+            if state.is_finished() {
+                return state;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn queued_and_running_jobs_can_be_cancelled() {
+        let queue = JobQueue::start();
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+
+        // Spins until cancelled, holding the single worker
+        let running = queue.submit(
+            JobKind::Prove,
+            Box::new(move |cancel| {
+                started_tx.send(()).unwrap();
+                loop {
+                    cancel.check()?;
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+            }),
+        );
+        let queued = queue.submit(JobKind::Ingest, Box::new(|_| Ok(serde_json::json!("ran"))));
+
+        tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(queue.status(running.id).unwrap().state, JobState::Running);
+
+        assert_eq!(queue.cancel(queued.id).unwrap().state, JobState::Cancelled);
+        queue.cancel(running.id).unwrap();
+
+        assert_eq!(wait_until_finished(&queue, running.id).await, JobState::Cancelled);
+        assert_eq!(wait_until_finished(&queue, queued.id).await, JobState::Cancelled);
+        assert!(queue.cancel(999).is_none());
+    }
+
+    #[tokio::test]
+    async fn failures_are_reported() {
+        let queue = JobQueue::start();
+        let job = queue.submit(JobKind::Ingest, Box::new(|_| bail!("no such file")));
+
+        assert_eq!(
+            wait_until_finished(&queue, job.id).await,
+            JobState::Failed {
+                error: "no such file".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! Long-running local service that keeps the embedder, store and proving key warm
+//! and exposes them over HTTP on localhost.
+//!
+//! Every request must carry the bearer token printed at startup and a `Host` of the
+//! loopback interface, so web pages can't drive the API through DNS rebinding.
+
+mod jobs;
+
+pub use jobs::{CancelToken, JobId, JobKind, JobQueue, JobState, JobStatus};
+
+use crate::db::DynStore;
+use crate::embedder::Embedder;
+use crate::lexical::LexicalIndex;
+use crate::parser::RecursiveCharacterParser;
+use crate::pipeline::{
+    annotate_chunk, collect_inputs, load_claim, Checkpoints, IngestOptions, IngestReport, Ingestor,
+};
+use crate::prover::{RelevanceInputs, RelevanceProver};
+use crate::retrieval::{chunk_id, retrieve, MetadataFilter, SearchMode};
+use crate::wallet::rand_bytes;
+use anyhow::Result;
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use private_context_core::DocumentChunk;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+/// Everything a request needs, loaded once at startup.
+pub struct DaemonState {
+    pub embedder: Arc<dyn Embedder + Send + Sync>,
+    pub store: Arc<Mutex<DynStore>>,
+    pub lexical: Arc<LexicalIndex>,
+    pub checkpoints: Arc<Checkpoints>,
+    /// `None` disables `/prove`, e.g. when the guest ELF is missing
+    pub prover: Option<Arc<RelevanceProver>>,
+    pub ingest_options: IngestOptions,
+    pub jobs: JobQueue,
+    /// Bearer token every request must present, see [`new_token`]
+    pub token: String,
+}
+
+/// Random token for one launch of the daemon.
+pub fn new_token() -> String {
+    hex::encode(rand_bytes::<32>())
+}
+
+pub fn router(state: Arc<DaemonState>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/search", post(search))
+        .route("/ingest", post(ingest))
+        .route("/prove", post(prove))
+        .route("/jobs/:id", get(job_status).delete(cancel_job))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state)
+}
+
+/// Serves until the process exits. Callers bind `listener` to a loopback address.
+pub async fn serve(listener: TcpListener, state: Arc<DaemonState>) -> Result<()> {
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        ApiError(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e))
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Rejects requests addressed to another host name, i.e. a rebound DNS name, or without
+/// the launch's bearer token.
+async fn authorize(State(state): State<Arc<DaemonState>>, req: Request, next: Next) -> Response {
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or_default();
+    if !is_loopback_host(host) {
+        return ApiError(
+            StatusCode::FORBIDDEN,
+            format!("Host `{}` not allowed", host),
+        )
+        .into_response();
+    }
+
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !tokens_match(token, &state.token) {
+        return ApiError(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid bearer token".to_string(),
+        )
+        .into_response();
+    }
+
+    next.run(req).await
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "localhost" | "127.0.0.1" | "[::1]"
+    )
+}
+
+/// Compares in time independent of where the tokens differ.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    !expected.is_empty()
+        && given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn default_k() -> usize {
+    5
+}
+
+fn default_mode() -> SearchMode {
+    SearchMode::Hybrid
+}
+
+fn default_threshold() -> f32 {
+    0.7
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    pub text: String,
+    #[serde(default = "default_k")]
+    pub k: usize,
+    #[serde(default = "default_mode")]
+    pub mode: SearchMode,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub id: String,
+    #[serde(flatten)]
+    pub chunk: DocumentChunk,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IngestRequest {
+    /// File, directory or glob on the daemon's filesystem
+    pub path: String,
+    /// Reclaim proof JSON file to verify and attach
+    #[serde(default)]
+    pub proof: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProveRequest {
+    pub text: String,
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    #[serde(default = "default_mode")]
+    pub mode: SearchMode,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+async fn health(State(state): State<Arc<DaemonState>>) -> Json<serde_json::Value> {
+    Json(json!({
+        "status": "ok",
+        "dimension": state.embedder.dimension(),
+        "model_hash": state.embedder.model_hash(),
+        "prover": state.prover.as_ref().map(|p| p.mode()),
+        "vkey_hash": state.prover.as_ref().map(|p| p.vkey_hash()),
+    }))
+}
+
+fn top_chunks(
+    state: &DaemonState,
+    text: &str,
+    k: usize,
+    mode: SearchMode,
+    filter: &MetadataFilter,
+) -> Result<(Vec<f32>, Vec<DocumentChunk>)> {
+    let query_vector = state.embedder.embed_query(text)?;
+    let store = state.store.lock().unwrap();
+    let chunks = retrieve(&store, &state.lexical, text, &query_vector, k, mode, filter)?;
+    Ok((query_vector, chunks))
+}
+
+async fn search(
+    State(state): State<Arc<DaemonState>>,
+    Json(req): Json<SearchRequest>,
+) -> ApiResult<Json<Vec<SearchHit>>> {
+    let hits = tokio::task::spawn_blocking(move || {
+        let filter = MetadataFilter {
+            source: req.source,
+            provider: req.provider,
+        };
+        let (_, chunks) = top_chunks(&state, &req.text, req.k, req.mode, &filter)?;
+        Ok::<_, anyhow::Error>(
+            chunks
+                .into_iter()
+                .map(|chunk| SearchHit {
+                    id: chunk_id(&chunk),
+                    chunk,
+                })
+                .collect(),
+        )
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))??;
+
+    Ok(Json(hits))
+}
+
+async fn ingest(
+    State(state): State<Arc<DaemonState>>,
+    Json(req): Json<IngestRequest>,
+) -> ApiResult<(StatusCode, Json<JobStatus>)> {
+    let bad_request = |e: anyhow::Error| ApiError(StatusCode::BAD_REQUEST, format!("{:#}", e));
+
+    let files = collect_inputs(&req.path).map_err(bad_request)?;
+    let claim = match &req.proof {
+        Some(proof) => Some(load_claim(proof).await.map_err(bad_request)?),
+        None => None,
+    };
+
+    let job_state = state.clone();
+    let status = state.jobs.submit(
+        JobKind::Ingest,
+        Box::new(move |cancel| {
+            let state = job_state;
+            let parser = RecursiveCharacterParser::new(500);
+            let model_hash = state.embedder.model_hash().to_string();
+            let mut store = state.store.lock().unwrap();
+            let mut ingestor = Ingestor {
+                parser: &parser,
+                embedder: state.embedder.as_ref(),
+                store: &mut *store,
+                lexical: &state.lexical,
+                checkpoints: &state.checkpoints,
+                options: state.ingest_options,
+            };
+
+            // One file at a time so cancellation lands between files; checkpoints make
+            // a cancelled ingest resumable
+            let mut total = IngestReport::default();
+            for file in &files {
+                cancel.check()?;
+                let report = ingestor.run(std::slice::from_ref(file), |chunk, hash| {
+                    annotate_chunk(chunk, hash, &model_hash, claim.as_ref())
+                })?;
+                total.files_ingested += report.files_ingested;
+                total.files_skipped += report.files_skipped;
+                total.chunks += report.chunks;
+                total.failures.extend(report.failures);
+            }
+            Ok(serde_json::to_value(total)?)
+        }),
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+async fn prove(
+    State(state): State<Arc<DaemonState>>,
+    Json(req): Json<ProveRequest>,
+) -> ApiResult<(StatusCode, Json<JobStatus>)> {
+    let Some(prover) = state.prover.clone() else {
+        return Err(ApiError(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Proving is disabled: no guest ELF loaded".to_string(),
+        ));
+    };
+
+    let job_state = state.clone();
+    let status = state.jobs.submit(
+        JobKind::Prove,
+        Box::new(move |cancel| {
+            let state = job_state;
+            let filter = MetadataFilter {
+                source: req.source,
+                provider: req.provider,
+            };
+            let (query_vector, chunks) = top_chunks(&state, &req.text, 1, req.mode, &filter)?;
+            let Some(chunk) = chunks.into_iter().next() else {
+                anyhow::bail!("No documents found to prove against");
+            };
+            cancel.check()?;
+
+            let chunk_vector = state
+                .embedder
+                .embed_passages(&[chunk.content.as_str()])?
+                .remove(0);
+            let document_hash: [u8; 32] = Sha256::digest(chunk.content.as_bytes()).into();
+            let claim_identifier = match &chunk.metadata.claim {
+                Some(claim) => claim.identifier_bytes()?,
+                None => [0u8; 32],
+            };
+            cancel.check()?;
+
+            let proof = prover.prove(&RelevanceInputs {
+                query_vector,
+                chunk_vector,
+                threshold: req.threshold,
+                document_hash,
+                claim_identifier,
+            })?;
+
+            Ok(json!({
+                "chunk_id": chunk_id(&chunk),
+                "source": chunk.metadata.source,
+                "document_hash": hex::encode(proof.outputs.document_hash),
+                "is_relevant": proof.outputs.is_relevant,
+                "similarity": proof.outputs.similarity,
+                "claim_identifier": proof.outputs.claim_identifier.map(hex::encode),
+                "public_values": hex::encode(&proof.public_values),
+                "proof": proof.proof.as_ref().map(hex::encode),
+                "vkey_hash": prover.vkey_hash(),
+            }))
+        }),
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+async fn job_status(
+    State(state): State<Arc<DaemonState>>,
+    Path(id): Path<JobId>,
+) -> ApiResult<Json<JobStatus>> {
+    state
+        .jobs
+        .status(id)
+        .map(Json)
+        .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("No job {}", id)))
+}
+
+async fn cancel_job(
+    State(state): State<Arc<DaemonState>>,
+    Path(id): Path<JobId>,
+) -> ApiResult<Json<JobStatus>> {
+    state
+        .jobs
+        .cancel(id)
+        .map(Json)
+        .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("No job {}", id)))
+}
@@ -0,0 +1,119 @@
+//! Behaviour every `VectorStore` backend must share. Each backend runs
+//! [`run_all`] from its own test module with a constructor for a fresh store.
+
+use private_context_core::{ChunkMetadata, DocumentChunk, VectorStore};
+use std::path::Path;
+
+pub const DIMENSION: usize = 4;
+
+fn chunk(content: &str) -> DocumentChunk {
+    DocumentChunk {
+        content: content.to_string(),
+        metadata: ChunkMetadata {
+            source: format!("{}.txt", content),
+            proof_id: None,
+            provider: Some("conformance".to_string()),
+            model_hash: Some("conformance-model".to_string()),
+            claim: None,
+            claim_binding: None,
+            commitment: None,
+            span: None,
+        },
+    }
+}
+
+fn axis(i: usize) -> Vec<f32> {
+    let mut v = vec![0.0; DIMENSION];
+    v[i] = 1.0;
+    v
+}
+
+pub fn run_all<S, F>(open: F)
+where
+    S: VectorStore<Vector = Vec<f32>, Error = anyhow::Error>,
+    F: Fn(&Path) -> S,
+{
+    let dir = tempfile::tempdir().unwrap();
+    empty_store_returns_nothing(open(&dir.path().join("empty")));
+    nearest_chunk_ranks_first(open(&dir.path().join("nearest")));
+    search_respects_k(open(&dir.path().join("k")));
+    metadata_round_trips(open(&dir.path().join("metadata")));
+    data_survives_reopen(&open, &dir.path().join("reopen"));
+}
+
+fn empty_store_returns_nothing<S>(store: S)
+where
+    S: VectorStore<Vector = Vec<f32>, Error = anyhow::Error>,
+{
+    assert!(store.search(&axis(0), 3).unwrap().is_empty());
+}
+
+fn nearest_chunk_ranks_first<S>(mut store: S)
+where
+    S: VectorStore<Vector = Vec<f32>, Error = anyhow::Error>,
+{
+    store
+        .add_chunks(
+            vec![chunk("alpha"), chunk("beta"), chunk("gamma")],
+            vec![axis(0), axis(1), axis(2)],
+        )
+        .unwrap();
+
+    let results = store.search(&axis(1), 1).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].content, "beta");
+}
+
+fn search_respects_k<S>(mut store: S)
+where
+    S: VectorStore<Vector = Vec<f32>, Error = anyhow::Error>,
+{
+    let chunks = (0..DIMENSION).map(|i| chunk(&format!("c{}", i))).collect();
+    let vectors = (0..DIMENSION).map(axis).collect();
+    store.add_chunks(chunks, vectors).unwrap();
+
+    assert_eq!(store.search(&axis(0), 2).unwrap().len(), 2);
+    assert_eq!(store.search(&axis(0), DIMENSION * 2).unwrap().len(), DIMENSION);
+}
+
+fn metadata_round_trips<S>(mut store: S)
+where
+    S: VectorStore<Vector = Vec<f32>, Error = anyhow::Error>,
+{
+    store
+        .add_chunks(vec![chunk("delta")], vec![axis(3)])
+        .unwrap();
+
+    let result = &store.search(&axis(3), 1).unwrap()[0];
+    assert_eq!(result.metadata.source, "delta.txt");
+    assert_eq!(result.metadata.provider.as_deref(), Some("conformance"));
+    assert!(result.metadata.proof_id.is_none());
+    assert_eq!(
+        result.metadata.model_hash.as_deref(),
+        Some("conformance-model")
+    );
+}
+
+fn data_survives_reopen<S, F>(open: &F, path: &Path)
+where
+    S: VectorStore<Vector = Vec<f32>, Error = anyhow::Error>,
+    F: Fn(&Path) -> S,
+{
+    {
+        let mut store = open(path);
+        store
+            .add_chunks(vec![chunk("first")], vec![axis(0)])
+            .unwrap();
+    }
+    {
+        // Ids must keep growing across sessions instead of overwriting earlier rows
+        let mut store = open(path);
+        store
+            .add_chunks(vec![chunk("second")], vec![axis(1)])
+            .unwrap();
+    }
+
+    let store = open(path);
+    assert_eq!(store.search(&axis(0), 1).unwrap()[0].content, "first");
+    assert_eq!(store.search(&axis(1), 1).unwrap()[0].content, "second");
+}
@@ -1,10 +1,7 @@
 use anyhow::{Context, Result};
 use hnsw_rs::prelude::*;
-use private_context_core::{ChunkMetadata, DocumentChunk};
-use serde::{Deserialize, Serialize};
+use private_context_core::{DocumentChunk, VectorStore};
 use sled::Db;
-use std::fs::File;
-use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
@@ -12,14 +9,12 @@ use std::sync::{Arc, Mutex};
 pub struct LocalStore {
     index: Arc<Mutex<Hnsw<'static, f32, DistL2>>>,
     db: Db,
-    index_path: String,
 }
 
 impl LocalStore {
     pub fn new(data_dir: &str) -> Result<Self> {
         let path = Path::new(data_dir);
         let db_path = path.join("doc_store");
-        let index_path = path.join("vector_index.bin");
 
         // 1. Initialize Sled (KV Store)
         let db = sled::open(db_path).context("Failed to open Sled DB")?;
@@ -52,15 +47,16 @@ impl LocalStore {
 
         let index = Arc::new(Mutex::new(idx));
 
-        Ok(Self {
-            index,
-            db,
-            index_path: index_path.to_string_lossy().to_string(),
-        })
+        Ok(Self { index, db })
     }
+}
+
+impl VectorStore for LocalStore {
+    type Vector = Vec<f32>;
+    type Error = anyhow::Error;
 
-    pub fn add_chunks(&self, chunks: Vec<DocumentChunk>, embeddings: Vec<Vec<f32>>) -> Result<()> {
-        let mut index = self.index.lock().unwrap();
+    fn add_chunks(&mut self, chunks: Vec<DocumentChunk>, embeddings: Vec<Vec<f32>>) -> Result<()> {
+        let index = self.index.lock().unwrap();
         let vectors_tree = self.db.open_tree("vectors")?;
 
         let start_id = index.get_nb_point();
@@ -91,7 +87,7 @@ impl LocalStore {
         Ok(())
     }
 
-    pub fn search(&self, query_vector: &[f32], k: usize) -> Result<Vec<DocumentChunk>> {
+    fn search(&self, query_vector: &Vec<f32>, k: usize) -> Result<Vec<DocumentChunk>> {
         let index = self.index.lock().unwrap();
 
         // Search returns Vec<Neighbour { d_id: usize, distance: f32, p_id: PointId }>
@@ -111,3 +107,14 @@ impl LocalStore {
         Ok(documents)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::conformance;
+
+    #[test]
+    fn hnsw_conformance() {
+        conformance::run_all(|dir| LocalStore::new(&dir.to_string_lossy()).unwrap());
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{anyhow, Context, Result};
+use arrow_array::{
+    Array, FixedSizeListArray, Float32Array, RecordBatch, RecordBatchIterator, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use futures::TryStreamExt;
+use lancedb::connection::Connection;
+use lancedb::connect;
+use lancedb::query::{ExecutableQuery, QueryBase};
+use private_context_core::{DocumentChunk, VectorStore};
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::runtime::{Handle, Runtime};
+
+const TABLE_NAME: &str = "vectors";
+
+/// Columnar Vector Store backed by LanceDB (Apache Arrow storage)
+pub struct LanceStore {
+    conn: Connection,
+    dimension: usize,
+    runtime: Runtime,
+}
+
+impl LanceStore {
+    pub fn new(data_dir: &str, dimension: usize) -> Result<Self> {
+        let uri = Path::new(data_dir).join("lancedb");
+        let runtime = Runtime::new().context("Failed to start LanceDB runtime")?;
+
+        let conn = runtime
+            .block_on(connect(&uri.to_string_lossy()).execute())
+            .context("Failed to connect to LanceDB")?;
+
+        Ok(Self {
+            conn,
+            dimension,
+            runtime,
+        })
+    }
+
+    /// LanceDB is async-only while `VectorStore` is sync, so drive the future on the
+    /// caller's runtime when there is one and on our own otherwise.
+    fn block_on<F: Future>(&self, fut: F) -> F::Output {
+        match Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(fut)),
+            Err(_) => self.runtime.block_on(fut),
+        }
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("text", DataType::Utf8, false),
+            Field::new("metadata", DataType::Utf8, false),
+            Field::new(
+                "vector",
+                DataType::FixedSizeList(
+                    Arc::new(Field::new("item", DataType::Float32, true)),
+                    self.dimension as i32,
+                ),
+                false,
+            ),
+        ]))
+    }
+
+    async fn count_rows(&self) -> Result<usize> {
+        match self.conn.open_table(TABLE_NAME).execute().await {
+            Ok(table) => Ok(table.count_rows(None).await?),
+            Err(_) => Ok(0),
+        }
+    }
+}
+
+impl VectorStore for LanceStore {
+    type Vector = Vec<f32>;
+    type Error = anyhow::Error;
+
+    fn add_chunks(&mut self, chunks: Vec<DocumentChunk>, embeddings: Vec<Vec<f32>>) -> Result<()> {
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        if let Some(bad) = embeddings.iter().find(|v| v.len() != self.dimension) {
+            return Err(anyhow!(
+                "Embedding dimension {} does not match store dimension {}",
+                bad.len(),
+                self.dimension
+            ));
+        }
+
+        let start_id = self.block_on(self.count_rows())?;
+        let schema = self.schema();
+
+        let mut ids = Vec::with_capacity(chunks.len());
+        let mut texts = Vec::with_capacity(chunks.len());
+        let mut metadata = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            ids.push((start_id + i).to_string());
+            texts.push(chunk.content.clone());
+            metadata.push(serde_json::to_string(&chunk.metadata)?);
+        }
+
+        let flattened: Vec<f32> = embeddings.into_iter().flatten().collect();
+        let vector_list = FixedSizeListArray::try_new(
+            Arc::new(Field::new("item", DataType::Float32, true)),
+            self.dimension as i32,
+            Arc::new(Float32Array::from(flattened)),
+            None,
+        )?;
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(ids)),
+                Arc::new(StringArray::from(texts)),
+                Arc::new(StringArray::from(metadata)),
+                Arc::new(vector_list),
+            ],
+        )?;
+
+        self.block_on(async {
+            let reader = RecordBatchIterator::new(vec![Ok(batch)], schema.clone());
+            // Try to open table, if fails, create it
+            match self.conn.open_table(TABLE_NAME).execute().await {
+                Ok(table) => table.add(reader).execute().await?,
+                Err(_) => {
+                    self.conn.create_table(TABLE_NAME, reader).execute().await?;
+                }
+            }
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    fn search(&self, query_vector: &Vec<f32>, k: usize) -> Result<Vec<DocumentChunk>> {
+        self.block_on(async {
+            let table = match self.conn.open_table(TABLE_NAME).execute().await {
+                Ok(table) => table,
+                // Nothing ingested yet
+                Err(_) => return Ok(Vec::new()),
+            };
+
+            let batches = table
+                .query()
+                .nearest_to(query_vector.as_slice())?
+                .limit(k)
+                .execute()
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            let mut documents = Vec::new();
+            for batch in batches {
+                let text_col = string_column(&batch, "text")?;
+                let metadata_col = string_column(&batch, "metadata")?;
+
+                for i in 0..batch.num_rows() {
+                    documents.push(DocumentChunk {
+                        content: text_col.value(i).to_string(),
+                        metadata: serde_json::from_str(metadata_col.value(i))?,
+                    });
+                }
+            }
+
+            Ok(documents)
+        })
+    }
+}
+
+fn string_column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a StringArray> {
+    batch
+        .column_by_name(name)
+        .and_then(|col| col.as_any().downcast_ref::<StringArray>())
+        .ok_or_else(|| anyhow!("LanceDB result is missing the `{}` column", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::conformance;
+
+    #[test]
+    fn lance_conformance() {
+        conformance::run_all(|dir| {
+            LanceStore::new(&dir.to_string_lossy(), conformance::DIMENSION).unwrap()
+        });
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod conformance;
+#[cfg(feature = "hnsw")]
+mod hnsw;
+#[cfg(feature = "lancedb")]
+mod lance;
+
+#[cfg(feature = "hnsw")]
+pub use hnsw::LocalStore;
+#[cfg(feature = "lancedb")]
+pub use lance::LanceStore;
+
+use anyhow::{anyhow, Result};
+use private_context_core::VectorStore;
+use serde::{Deserialize, Serialize};
+
+/// Any storage backend the CLI can drive. `Send` so the daemon can share it across tasks.
+pub type DynStore = Box<dyn VectorStore<Vector = Vec<f32>, Error = anyhow::Error> + Send>;
+
+/// Storage backends, each gated behind the cargo feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreBackend {
+    /// HNSW index + Sled document store
+    Hnsw,
+    /// LanceDB columnar store
+    Lancedb,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreConfig {
+    pub backend: StoreBackend,
+    pub data_dir: String,
+    /// Embedding dimension; required by backends with a fixed-width schema.
+    pub dimension: usize,
+}
+
+/// Opens the backend selected by `config`, failing if it was compiled out.
+pub fn open_store(config: &StoreConfig) -> Result<DynStore> {
+    match config.backend {
+        #[cfg(feature = "hnsw")]
+        StoreBackend::Hnsw => Ok(Box::new(LocalStore::new(&config.data_dir)?)),
+        #[cfg(feature = "lancedb")]
+        StoreBackend::Lancedb => Ok(Box::new(LanceStore::new(
+            &config.data_dir,
+            config.dimension,
+        )?)),
+        #[allow(unreachable_patterns)]
+        backend => Err(anyhow!(
+            "Storage backend `{:?}` is not compiled in. Rebuild with `--features {}`.",
+            backend,
+            backend.feature()
+        )),
+    }
+}
+
+impl StoreBackend {
+    pub fn feature(&self) -> &'static str {
+        match self {
+            StoreBackend::Hnsw => "hnsw",
+            StoreBackend::Lancedb => "lancedb",
+        }
+    }
+}
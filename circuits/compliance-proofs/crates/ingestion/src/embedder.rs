@@ -1,35 +1,155 @@
-use anyhow::{Context, Result};
-use candle_core::{Device, Tensor};
+use anyhow::{anyhow, Context, Result};
+use candle_core::{Device, IndexOp, Tensor};
 use candle_nn::VarBuilder;
 use candle_transformers::models::bert::{BertModel, Config, DTYPE};
-use std::fs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::path::Path;
-use tokenizers::Tokenizer;
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer, TruncationParams};
 
 pub trait Embedder {
-    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+    /// Embeds a search query, applying the model's query prefix if it has one.
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>>;
+    /// Embeds document chunks, applying the model's passage prefix if it has one.
+    fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>>;
     fn dimension(&self) -> usize;
+    /// Hex SHA-256 of the weights, stored with every vector so mixed-model indexes are detectable.
+    fn model_hash(&self) -> &str;
+}
+
+/// Embedding model families the CLI knows how to pool and prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelFamily {
+    /// sentence-transformers/all-MiniLM-*
+    Minilm,
+    /// BAAI/bge-*
+    Bge,
+    /// intfloat/e5-*
+    E5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pooling {
+    /// Average of non-padding token states
+    Mean,
+    /// Hidden state of the leading [CLS] token
+    Cls,
+}
+
+pub struct ModelSpec {
+    pub family: ModelFamily,
+    /// Lowercase substrings of `_name_or_path` or the model directory that identify the family
+    markers: &'static [&'static str],
+    pub pooling: Pooling,
+    pub query_prefix: &'static str,
+    pub passage_prefix: &'static str,
+}
+
+const REGISTRY: [ModelSpec; 3] = [
+    ModelSpec {
+        family: ModelFamily::Minilm,
+        markers: &["minilm"],
+        pooling: Pooling::Mean,
+        query_prefix: "",
+        passage_prefix: "",
+    },
+    ModelSpec {
+        family: ModelFamily::Bge,
+        markers: &["bge"],
+        pooling: Pooling::Cls,
+        query_prefix: "Represent this sentence for searching relevant passages: ",
+        passage_prefix: "",
+    },
+    ModelSpec {
+        family: ModelFamily::E5,
+        markers: &["e5-", "e5_"],
+        pooling: Pooling::Mean,
+        query_prefix: "query: ",
+        passage_prefix: "passage: ",
+    },
+];
+
+impl ModelFamily {
+    pub fn spec(&self) -> &'static ModelSpec {
+        REGISTRY
+            .iter()
+            .find(|spec| spec.family == *self)
+            .expect("every family has a registry entry")
+    }
+
+    /// Picks the family from `config.json`'s `_name_or_path`, falling back to the directory name.
+    pub fn detect(model_dir: &Path, info: &ModelInfo) -> Option<Self> {
+        let dir_name = model_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let candidates = [info.name_or_path.to_lowercase(), dir_name];
+
+        candidates.iter().find_map(|name| {
+            REGISTRY
+                .iter()
+                .find(|spec| spec.markers.iter().any(|m| name.contains(m)))
+                .map(|spec| spec.family)
+        })
+    }
+}
+
+/// Fields of a Hugging Face `config.json` the registry needs beyond the BERT config.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelInfo {
+    #[serde(rename = "_name_or_path", default)]
+    pub name_or_path: String,
+    pub hidden_size: usize,
+    pub max_position_embeddings: usize,
 }
 
 pub struct CandleEmbedder {
     model: BertModel,
     tokenizer: Tokenizer,
     device: Device,
+    spec: &'static ModelSpec,
+    dimension: usize,
+    model_hash: String,
 }
 
 impl CandleEmbedder {
-    pub fn new(model_dir: &Path) -> Result<Self> {
+    /// Loads the model in `model_dir`. `family` overrides detection from `config.json`.
+    pub fn new(model_dir: &Path, family: Option<ModelFamily>) -> Result<Self> {
         let device = Device::Cpu; // Default to CPU for privacy/portability
 
         let config_path = model_dir.join("config.json");
         let tokenizer_path = model_dir.join("tokenizer.json");
         let weights_path = model_dir.join("model.safetensors");
 
-        let config = fs_err::read_to_string(config_path)?;
-        let config: Config = serde_json::from_str(&config)?;
+        let config_json = fs_err::read_to_string(config_path)?;
+        let config: Config = serde_json::from_str(&config_json)?;
+        let info: ModelInfo = serde_json::from_str(&config_json)?;
+
+        let family = family
+            .or_else(|| ModelFamily::detect(model_dir, &info))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown embedding model in {:?}. Pass `--embedder minilm|bge|e5`.",
+                    model_dir
+                )
+            })?;
 
-        let tokenizer = Tokenizer::from_file(tokenizer_path)
-            .map_err(|e| anyhow::anyhow!("Tokenizer error: {}", e))?;
+        let mut tokenizer = Tokenizer::from_file(tokenizer_path)
+            .map_err(|e| anyhow!("Tokenizer error: {}", e))?;
+        // Pad to the longest input in each batch and never exceed the position table
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongest,
+            ..Default::default()
+        }));
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: info.max_position_embeddings,
+                ..Default::default()
+            }))
+            .map_err(|e| anyhow!("Tokenizer error: {}", e))?;
+
+        let model_hash = hash_file(&weights_path)?;
 
         let vb = unsafe { VarBuilder::from_mmaped_safetensors(&[weights_path], DTYPE, &device)? };
 
@@ -39,42 +159,146 @@ impl CandleEmbedder {
             model,
             tokenizer,
             device,
+            spec: family.spec(),
+            dimension: info.hidden_size,
+            model_hash,
         })
     }
 
-    pub fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+    pub fn family(&self) -> ModelFamily {
+        self.spec.family
+    }
+
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let tokens = self
             .tokenizer
             .encode_batch(texts.to_vec(), true)
-            .map_err(|e| anyhow::anyhow!("Tokenization error: {}", e))?;
+            .map_err(|e| anyhow!("Tokenization error: {}", e))?;
 
         let token_ids = tokens
             .iter()
             .map(|t| Tensor::new(t.get_ids(), &self.device))
             .collect::<Result<Vec<_>, _>>()?;
+        let attention_mask = tokens
+            .iter()
+            .map(|t| Tensor::new(t.get_attention_mask(), &self.device))
+            .collect::<Result<Vec<_>, _>>()?;
 
         let token_ids = Tensor::stack(&token_ids, 0)?;
+        let attention_mask = Tensor::stack(&attention_mask, 0)?;
         let token_type_ids = token_ids.zeros_like()?; // BERT expects token type ids
 
-        let embeddings = self.model.forward(&token_ids, &token_type_ids, None)?;
+        let hidden = self
+            .model
+            .forward(&token_ids, &token_type_ids, Some(&attention_mask))?;
 
-        // Mean pooling
-        let (_n_batch, n_tokens, _hidden_size) = embeddings.dims3()?;
-        let embeddings = (embeddings.sum(1)? / (n_tokens as f64))?;
+        let pooled = pool(&hidden, &attention_mask, self.spec.pooling)?;
+        let normalized = l2_normalize(&pooled)?;
 
-        let results = embeddings.to_vec2::<f32>()?;
-        Ok(results)
+        Ok(normalized.to_vec2::<f32>()?)
     }
 }
 
 impl Embedder for CandleEmbedder {
-    fn embed(&self, text: &str) -> Result<Vec<f32>> {
-        let batch = self.embed_batch(&[text])?;
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        let batch = self.embed_batch(&[format!("{}{}", self.spec.query_prefix, text)])?;
         Ok(batch.into_iter().next().unwrap())
     }
 
+    fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let prefixed: Vec<String> = texts
+            .iter()
+            .map(|t| format!("{}{}", self.spec.passage_prefix, t))
+            .collect();
+        self.embed_batch(&prefixed)
+    }
+
     fn dimension(&self) -> usize {
-        // BERT base usually has 768 or 384 for MiniLM
-        768 // This should be dynamic based on config, but for MVP...
+        self.dimension
+    }
+
+    fn model_hash(&self) -> &str {
+        &self.model_hash
+    }
+}
+
+/// Reduces `(batch, tokens, hidden)` states to `(batch, hidden)`.
+fn pool(hidden: &Tensor, attention_mask: &Tensor, pooling: Pooling) -> Result<Tensor> {
+    Ok(match pooling {
+        Pooling::Cls => hidden.i((.., 0))?,
+        Pooling::Mean => {
+            // Padding positions have mask 0 and must not dilute the average
+            let mask = attention_mask.to_dtype(hidden.dtype())?.unsqueeze(2)?;
+            let summed = hidden.broadcast_mul(&mask)?.sum(1)?;
+            let counts = mask.sum(1)?.clamp(1e-9f32, f32::MAX)?;
+            summed.broadcast_div(&counts)?
+        }
+    })
+}
+
+/// Scales each row to unit length so dot product equals cosine similarity.
+fn l2_normalize(embeddings: &Tensor) -> Result<Tensor> {
+    let norms = embeddings
+        .sqr()?
+        .sum_keepdim(1)?
+        .sqrt()?
+        .clamp(1e-12f32, f32::MAX)?;
+    Ok(embeddings.broadcast_div(&norms)?)
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let bytes = fs_err::read(path).context("Failed to read model weights")?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mean_pooling_ignores_padding() {
+        // One real token followed by a padding token with a large hidden state
+        let hidden = Tensor::new(&[[[1f32, 2.0], [100.0, 100.0]]], &Device::Cpu).unwrap();
+        let mask = Tensor::new(&[[1u32, 0]], &Device::Cpu).unwrap();
+
+        let pooled = pool(&hidden, &mask, Pooling::Mean).unwrap();
+        assert_eq!(pooled.to_vec2::<f32>().unwrap(), vec![vec![1.0, 2.0]]);
+
+        let cls = pool(&hidden, &mask, Pooling::Cls).unwrap();
+        assert_eq!(cls.to_vec2::<f32>().unwrap(), vec![vec![1.0, 2.0]]);
+    }
+
+    #[test]
+    fn normalized_rows_have_unit_length() {
+        let v = Tensor::new(&[[3f32, 4.0], [0.0, 2.0]], &Device::Cpu).unwrap();
+        let rows = l2_normalize(&v).unwrap().to_vec2::<f32>().unwrap();
+        assert_eq!(rows, vec![vec![0.6, 0.8], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn family_detected_from_config_or_directory() {
+        let info = |name: &str| ModelInfo {
+            name_or_path: name.to_string(),
+            hidden_size: 384,
+            max_position_embeddings: 512,
+        };
+
+        assert_eq!(
+            ModelFamily::detect(Path::new("models/x"), &info("BAAI/bge-small-en-v1.5")),
+            Some(ModelFamily::Bge)
+        );
+        assert_eq!(
+            ModelFamily::detect(Path::new("models/e5-small-v2"), &info("")),
+            Some(ModelFamily::E5)
+        );
+        assert_eq!(
+            ModelFamily::detect(Path::new("models/all-MiniLM-L6-v2"), &info("")),
+            Some(ModelFamily::Minilm)
+        );
+        assert_eq!(ModelFamily::detect(Path::new("models/x"), &info("gpt2")), None);
     }
 }
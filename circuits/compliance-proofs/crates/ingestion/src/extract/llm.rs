@@ -0,0 +1,227 @@
+use super::rules::{country_code, parse_date};
+use super::{Balance, ExtractedFields, Extractor, Field, RuleExtractor};
+use crate::retrieval::chunk_id;
+use anyhow::{anyhow, Context, Result};
+use candle_core::quantized::gguf_file;
+use candle_core::{Device, Tensor};
+use candle_transformers::generation::LogitsProcessor;
+use candle_transformers::models::quantized_llama::ModelWeights;
+use private_context_core::DocumentChunk;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Mutex;
+use tokenizers::Tokenizer;
+
+const MAX_NEW_TOKENS: usize = 256;
+/// Model answers are less reliable than labelled regex hits but beat inferred ones.
+const LLM_CONFIDENCE: f32 = 0.75;
+/// Tokens that end generation across common chat templates.
+const EOS_TOKENS: [&str; 4] = ["</s>", "<|endoftext|>", "<|im_end|>", "<|eot_id|>"];
+
+/// Fields as the model is asked to emit them; normalised before use.
+#[derive(Debug, Default, Deserialize)]
+struct ModelAnswer {
+    name: Option<String>,
+    date_of_birth: Option<String>,
+    country: Option<String>,
+    balance_amount: Option<f64>,
+    balance_currency: Option<String>,
+    as_of_date: Option<String>,
+    /// Index of the excerpt the values were read from
+    source: Option<usize>,
+}
+
+/// Small quantized GGUF model (Llama family) run locally on CPU via candle.
+/// Fields the model misses or garbles fall back to [`RuleExtractor`].
+pub struct LlmExtractor {
+    // `ModelWeights::forward` needs `&mut self` to update its KV cache
+    model: Mutex<ModelWeights>,
+    tokenizer: Tokenizer,
+    device: Device,
+    eos_token: Option<u32>,
+}
+
+impl LlmExtractor {
+    /// `model_path` is a `.gguf` file; `tokenizer.json` is read from the same directory.
+    pub fn new(model_path: &Path) -> Result<Self> {
+        let device = Device::Cpu;
+
+        let mut file = fs_err::File::open(model_path)?;
+        let content = gguf_file::Content::read(&mut file)
+            .map_err(|e| anyhow!("Failed to read GGUF {:?}: {}", model_path, e))?;
+        let model = ModelWeights::from_gguf(content, &mut file, &device)?;
+
+        let tokenizer_path = model_path.with_file_name("tokenizer.json");
+        let tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| anyhow!("Tokenizer error: {}", e))?;
+        let eos_token = EOS_TOKENS.iter().find_map(|t| tokenizer.token_to_id(t));
+
+        Ok(Self {
+            model: Mutex::new(model),
+            tokenizer,
+            device,
+            eos_token,
+        })
+    }
+
+    fn generate(&self, prompt: &str) -> Result<String> {
+        let mut model = self.model.lock().unwrap();
+        let prompt_tokens = self
+            .tokenizer
+            .encode(prompt, true)
+            .map_err(|e| anyhow!("Tokenization error: {}", e))?
+            .get_ids()
+            .to_vec();
+
+        // Greedy decoding keeps extraction deterministic
+        let mut sampler = LogitsProcessor::new(0, None, None);
+        let mut generated = Vec::new();
+
+        let input = Tensor::new(prompt_tokens.as_slice(), &self.device)?.unsqueeze(0)?;
+        let mut next = sampler.sample(&model.forward(&input, 0)?.squeeze(0)?)?;
+
+        for index in 0..MAX_NEW_TOKENS {
+            if Some(next) == self.eos_token {
+                break;
+            }
+            generated.push(next);
+            let input = Tensor::new(&[next], &self.device)?.unsqueeze(0)?;
+            let logits = model.forward(&input, prompt_tokens.len() + index)?;
+            next = sampler.sample(&logits.squeeze(0)?)?;
+        }
+
+        self.tokenizer
+            .decode(&generated, true)
+            .map_err(|e| anyhow!("Detokenization error: {}", e))
+    }
+}
+
+fn build_prompt(chunks: &[DocumentChunk]) -> String {
+    let mut prompt = String::from(
+        "Extract the following fields from the excerpts and answer with a single JSON object \
+         with keys name, date_of_birth (YYYY-MM-DD), country, balance_amount (number), \
+         balance_currency (ISO 4217), as_of_date (YYYY-MM-DD) and source (excerpt number). \
+         Use null for anything not stated.\n\n",
+    );
+    for (i, chunk) in chunks.iter().enumerate() {
+        prompt.push_str(&format!("Excerpt {}:\n{}\n\n", i, chunk.content));
+    }
+    prompt.push_str("JSON:\n");
+    prompt
+}
+
+/// Pulls the first `{ ... }` block out of free-form model output.
+fn parse_answer(output: &str) -> Result<ModelAnswer> {
+    let start = output.find('{').context("Model output has no JSON object")?;
+    let end = output.rfind('}').context("Model output has no JSON object")?;
+    serde_json::from_str(&output[start..=end]).context("Model output is not valid JSON")
+}
+
+impl Extractor for LlmExtractor {
+    fn extract(&self, chunks: &[DocumentChunk]) -> Result<ExtractedFields> {
+        let rules = RuleExtractor.extract(chunks)?;
+        if chunks.is_empty() {
+            return Ok(rules);
+        }
+
+        let answer = match self.generate(&build_prompt(chunks)).and_then(|o| parse_answer(&o)) {
+            Ok(answer) => answer,
+            Err(e) => {
+                println!("⚠️  LLM extraction failed ({:#}); using rule-based fields", e);
+                return Ok(rules);
+            }
+        };
+
+        let source = chunks
+            .get(answer.source.unwrap_or(0))
+            .unwrap_or(&chunks[0]);
+        let id = chunk_id(source);
+        let field = |value| Field {
+            value,
+            chunk_id: id.clone(),
+            confidence: LLM_CONFIDENCE,
+        };
+
+        // Only accept values that survive the same normalisation the rules apply
+        let balance = match (answer.balance_amount, answer.balance_currency) {
+            (Some(amount), Some(currency)) if amount.is_finite() => Some(Field {
+                value: Balance {
+                    amount_minor: (amount * 100.0).round() as i64,
+                    currency: currency.to_uppercase(),
+                },
+                chunk_id: id.clone(),
+                confidence: LLM_CONFIDENCE,
+            }),
+            _ => None,
+        };
+        let from_model = ExtractedFields {
+            name: answer.name.filter(|n| !n.trim().is_empty()).map(field),
+            date_of_birth: answer.date_of_birth.as_deref().and_then(parse_date).map(field),
+            country: answer
+                .country
+                .as_deref()
+                .and_then(country_code)
+                .map(|c| field(c.to_string())),
+            balance,
+            as_of_date: answer.as_of_date.as_deref().and_then(parse_date).map(field),
+        };
+
+        Ok(prefer_confident(from_model, rules))
+    }
+}
+
+/// Field-by-field, keeps whichever side is more confident.
+fn prefer_confident(model: ExtractedFields, rules: ExtractedFields) -> ExtractedFields {
+    fn pick<T>(a: Option<Field<T>>, b: Option<Field<T>>) -> Option<Field<T>> {
+        match (a, b) {
+            (Some(a), Some(b)) if b.confidence > a.confidence => Some(b),
+            (a, b) => a.or(b),
+        }
+    }
+
+    ExtractedFields {
+        name: pick(model.name, rules.name),
+        date_of_birth: pick(model.date_of_birth, rules.date_of_birth),
+        country: pick(model.country, rules.country),
+        balance: pick(model.balance, rules.balance),
+        as_of_date: pick(model.as_of_date, rules.as_of_date),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_parsed_from_chatty_output() {
+        let output = r#"Sure! Here it is: {"name": "Jane Doe", "date_of_birth": "1985-03-12", "country": "UAE", "balance_amount": 10.5, "balance_currency": "usd", "as_of_date": null, "source": 1} Hope that helps."#;
+        let answer = parse_answer(output).unwrap();
+        assert_eq!(answer.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(answer.source, Some(1));
+        assert!(parse_answer("no json here").is_err());
+    }
+
+    #[test]
+    fn more_confident_rule_value_wins() {
+        let field = |value: &str, confidence| Field {
+            value: value.to_string(),
+            chunk_id: "c".to_string(),
+            confidence,
+        };
+        let model = ExtractedFields {
+            name: Some(field("J. Doe", LLM_CONFIDENCE)),
+            country: Some(field("AE", LLM_CONFIDENCE)),
+            ..Default::default()
+        };
+        let rules = ExtractedFields {
+            name: Some(field("Jane Doe", 0.9)),
+            as_of_date: Some(field("2024-06-30", 0.9)),
+            ..Default::default()
+        };
+
+        let merged = prefer_confident(model, rules);
+        assert_eq!(merged.name.unwrap().value, "Jane Doe");
+        assert_eq!(merged.country.unwrap().value, "AE");
+        assert_eq!(merged.as_of_date.unwrap().value, "2024-06-30");
+    }
+}
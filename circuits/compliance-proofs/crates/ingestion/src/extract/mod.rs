@@ -0,0 +1,51 @@
+mod llm;
+mod rules;
+
+pub use llm::LlmExtractor;
+pub use rules::RuleExtractor;
+
+use anyhow::Result;
+use private_context_core::DocumentChunk;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A value pulled from retrieved text, with the chunk it came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Field<T> {
+    pub value: T,
+    /// Hex chunk id, see `retrieval::chunk_id`
+    pub chunk_id: String,
+    /// 0.0 – 1.0
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Balance {
+    /// Amount in minor units (cents) so circuits can compare integers
+    pub amount_minor: i64,
+    /// ISO 4217 code
+    pub currency: String,
+}
+
+/// Typed compliance fields; serialises to the private input of a predicate circuit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractedFields {
+    pub name: Option<Field<String>>,
+    /// `YYYY-MM-DD`
+    pub date_of_birth: Option<Field<String>>,
+    /// ISO 3166-1 alpha-2
+    pub country: Option<Field<String>>,
+    pub balance: Option<Field<Balance>>,
+    /// `YYYY-MM-DD`
+    pub as_of_date: Option<Field<String>>,
+}
+
+pub trait Extractor {
+    /// `chunks` are in retrieval order, best first.
+    fn extract(&self, chunks: &[DocumentChunk]) -> Result<ExtractedFields>;
+}
+
+/// JSON Schema of the extraction output.
+pub fn schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(ExtractedFields)
+}
@@ -0,0 +1,238 @@
+use super::{Balance, ExtractedFields, Extractor, Field};
+use crate::retrieval::chunk_id;
+use anyhow::Result;
+use chrono::NaiveDate;
+use private_context_core::DocumentChunk;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Confidence when a value follows an explicit label such as "Date of Birth:".
+const LABELLED: f32 = 0.9;
+/// Confidence when the label is looser ("born", "balance of").
+const INFERRED: f32 = 0.6;
+
+const DATE: &str = r"(\d{4}-\d{2}-\d{2}|\d{1,2}/\d{1,2}/\d{4}|\d{1,2} [A-Za-z]+ \d{4}|[A-Za-z]+ \d{1,2}, \d{4})";
+
+struct Patterns {
+    name: Regex,
+    dob_labelled: Regex,
+    dob_inferred: Regex,
+    country: Regex,
+    balance: Regex,
+    as_of: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        name: Regex::new(
+            r"(?i:full name|account holder|holder name|investor name|name)\s*[:\-]\s*([A-Z][A-Za-z'\-]+(?: [A-Z][A-Za-z'\-]+){0,3})",
+        )
+        .unwrap(),
+        dob_labelled: Regex::new(&format!(r"(?i)(?:date of birth|dob)\s*[:\-]?\s*{}", DATE)).unwrap(),
+        dob_inferred: Regex::new(&format!(r"(?i)born(?: on)?\s+{}", DATE)).unwrap(),
+        country: Regex::new(
+            r"(?i)(?:country of residence|country|nationality|citizenship|jurisdiction)\s*[:\-]\s*([A-Za-z][A-Za-z .]+?)\s*(?:[,;\n]|$)",
+        )
+        .unwrap(),
+        balance: Regex::new(
+            r"(?i)(balance|total assets|net worth)\s*(?:of|is|:)?\s*(USD|EUR|GBP|AED|CHF|\$|€|£)?\s*([0-9][0-9,]*(?:\.[0-9]{1,2})?)\s*(USD|EUR|GBP|AED|CHF)?",
+        )
+        .unwrap(),
+        as_of: Regex::new(&format!(
+            r"(?i)(?:as of|as at|statement date|valid as of)\s*[:\-]?\s*{}",
+            DATE
+        ))
+        .unwrap(),
+    })
+}
+
+/// Regex extractor that needs no model weights; also fills gaps left by [`super::LlmExtractor`].
+pub struct RuleExtractor;
+
+impl Extractor for RuleExtractor {
+    fn extract(&self, chunks: &[DocumentChunk]) -> Result<ExtractedFields> {
+        let p = patterns();
+        let mut fields = ExtractedFields::default();
+
+        for chunk in chunks {
+            let id = chunk_id(chunk);
+            let text = &chunk.content;
+            let field = |value, confidence| Field {
+                value,
+                chunk_id: id.clone(),
+                confidence,
+            };
+            let balance_field = |value, confidence| Field {
+                value,
+                chunk_id: id.clone(),
+                confidence,
+            };
+
+            if let Some(c) = p.name.captures(text) {
+                keep_best(&mut fields.name, field(c[1].trim().to_string(), LABELLED));
+            }
+
+            let dob = p
+                .dob_labelled
+                .captures(text)
+                .map(|c| (c[1].to_string(), LABELLED))
+                .or_else(|| p.dob_inferred.captures(text).map(|c| (c[1].to_string(), INFERRED)));
+            if let Some((raw, confidence)) = dob {
+                if let Some(date) = parse_date(&raw) {
+                    keep_best(&mut fields.date_of_birth, field(date, confidence));
+                }
+            }
+
+            if let Some(c) = p.country.captures(text) {
+                if let Some(code) = country_code(&c[1]) {
+                    keep_best(&mut fields.country, field(code.to_string(), LABELLED));
+                }
+            }
+
+            if let Some(c) = p.balance.captures(text) {
+                let currency = c
+                    .get(2)
+                    .or_else(|| c.get(4))
+                    .and_then(|m| currency_code(m.as_str()));
+                if let (Some(currency), Some(amount_minor)) = (currency, parse_amount(&c[3])) {
+                    let confidence = if c[1].eq_ignore_ascii_case("balance") {
+                        LABELLED
+                    } else {
+                        INFERRED
+                    };
+                    keep_best(
+                        &mut fields.balance,
+                        balance_field(
+                            Balance {
+                                amount_minor,
+                                currency: currency.to_string(),
+                            },
+                            confidence,
+                        ),
+                    );
+                }
+            }
+
+            if let Some(date) = p.as_of.captures(text).and_then(|c| parse_date(&c[1])) {
+                keep_best(&mut fields.as_of_date, field(date, LABELLED));
+            }
+        }
+
+        Ok(fields)
+    }
+}
+
+/// Earlier chunks rank higher, so only a strictly more confident match replaces a value.
+fn keep_best<T>(slot: &mut Option<Field<T>>, candidate: Field<T>) {
+    if slot
+        .as_ref()
+        .map_or(true, |current| candidate.confidence > current.confidence)
+    {
+        *slot = Some(candidate);
+    }
+}
+
+/// Normalises common date layouts to `YYYY-MM-DD`.
+pub fn parse_date(raw: &str) -> Option<String> {
+    const FORMATS: [&str; 4] = ["%Y-%m-%d", "%d/%m/%Y", "%d %B %Y", "%B %d, %Y"];
+    FORMATS
+        .iter()
+        .find_map(|f| NaiveDate::parse_from_str(raw.trim(), f).ok())
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+fn parse_amount(raw: &str) -> Option<i64> {
+    let cleaned = raw.replace(',', "");
+    let (whole, frac) = cleaned.split_once('.').unwrap_or((&cleaned, ""));
+    let cents = format!("{:0<2}", frac);
+    Some(whole.parse::<i64>().ok()?.checked_mul(100)? + cents.parse::<i64>().ok()?)
+}
+
+fn currency_code(raw: &str) -> Option<&'static str> {
+    Some(match raw.to_uppercase().as_str() {
+        "$" | "USD" => "USD",
+        "€" | "EUR" => "EUR",
+        "£" | "GBP" => "GBP",
+        "AED" => "AED",
+        "CHF" => "CHF",
+        _ => return None,
+    })
+}
+
+/// Maps names and codes seen in KYC documents to ISO 3166-1 alpha-2.
+pub fn country_code(raw: &str) -> Option<&'static str> {
+    const COUNTRIES: [(&str, &[&str]); 12] = [
+        ("US", &["us", "usa", "united states", "united states of america"]),
+        ("GB", &["gb", "uk", "united kingdom", "great britain"]),
+        ("AE", &["ae", "uae", "united arab emirates"]),
+        ("DE", &["de", "germany"]),
+        ("FR", &["fr", "france"]),
+        ("CH", &["ch", "switzerland"]),
+        ("SG", &["sg", "singapore"]),
+        ("IN", &["in", "india"]),
+        ("JP", &["jp", "japan"]),
+        ("CA", &["ca", "canada"]),
+        ("AU", &["au", "australia"]),
+        ("HK", &["hk", "hong kong"]),
+    ];
+    let needle = raw.trim().trim_end_matches('.').to_lowercase();
+    COUNTRIES
+        .iter()
+        .find(|(_, names)| names.contains(&needle.as_str()))
+        .map(|(code, _)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use private_context_core::ChunkMetadata;
+
+    fn chunk(content: &str) -> DocumentChunk {
+        DocumentChunk {
+            content: content.to_string(),
+            metadata: ChunkMetadata {
+                source: "kyc.txt".to_string(),
+                proof_id: None,
+                provider: None,
+                model_hash: None,
+                claim: None,
+                claim_binding: None,
+                commitment: None,
+                span: None,
+            },
+        }
+    }
+
+    #[test]
+    fn extracts_labelled_fields_with_sources() {
+        let chunks = vec![
+            chunk("Full Name: Jane Doe\nDate of Birth: 12 March 1985\nCountry: United Arab Emirates"),
+            chunk("Statement as of 2024-06-30. Closing balance: USD 1,250,000.50"),
+        ];
+
+        let fields = RuleExtractor.extract(&chunks).unwrap();
+        assert_eq!(fields.name.unwrap().value, "Jane Doe");
+        assert_eq!(fields.date_of_birth.unwrap().value, "1985-03-12");
+        assert_eq!(fields.country.unwrap().value, "AE");
+        assert_eq!(fields.as_of_date.as_ref().unwrap().value, "2024-06-30");
+        assert_eq!(fields.as_of_date.unwrap().chunk_id, chunk_id(&chunks[1]));
+
+        let balance = fields.balance.unwrap();
+        assert_eq!(balance.value.amount_minor, 125_000_050);
+        assert_eq!(balance.value.currency, "USD");
+    }
+
+    #[test]
+    fn labelled_value_beats_inferred_one() {
+        let chunks = vec![
+            chunk("The client was born on 01/02/1990."),
+            chunk("DOB: 1990-02-01"),
+        ];
+
+        let dob = RuleExtractor.extract(&chunks).unwrap().date_of_birth.unwrap();
+        assert_eq!(dob.value, "1990-02-01");
+        assert_eq!(dob.confidence, LABELLED);
+        assert_eq!(dob.chunk_id, chunk_id(&chunks[1]));
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use private_context_core::DocumentChunk;
+use serde::{Deserialize, Serialize};
+use sled::{Db, Tree};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// BM25 term-frequency saturation
+const K1: f32 = 1.2;
+/// BM25 document-length normalisation
+const B: f32 = 0.75;
+
+const STATS_KEY: &[u8] = b"stats";
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct IndexStats {
+    doc_count: u64,
+    total_tokens: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedChunk {
+    chunk: DocumentChunk,
+    length: u32,
+}
+
+/// (doc id, term frequency)
+type Posting = (u64, u32);
+
+/// Local BM25 inverted index over chunk text, kept in its own Sled DB next to the vector index.
+pub struct LexicalIndex {
+    db: Db,
+    docs: Tree,
+    postings: Tree,
+}
+
+impl LexicalIndex {
+    pub fn open(data_dir: &str) -> Result<Self> {
+        let db = sled::open(Path::new(data_dir).join("lexical_index"))
+            .context("Failed to open lexical index")?;
+        let docs = db.open_tree("docs")?;
+        let postings = db.open_tree("postings")?;
+
+        Ok(Self { db, docs, postings })
+    }
+
+    fn stats(&self) -> Result<IndexStats> {
+        Ok(match self.db.get(STATS_KEY)? {
+            Some(bytes) => bincode::deserialize(&bytes)?,
+            None => IndexStats::default(),
+        })
+    }
+
+    pub fn add_chunks(&self, chunks: &[DocumentChunk]) -> Result<()> {
+        let mut stats = self.stats()?;
+        let mut new_postings: HashMap<String, Vec<Posting>> = HashMap::new();
+
+        for chunk in chunks {
+            let id = stats.doc_count;
+            let tokens = tokenize(&chunk.content);
+
+            let mut tf: HashMap<String, u32> = HashMap::new();
+            for token in &tokens {
+                *tf.entry(token.clone()).or_default() += 1;
+            }
+            for (term, freq) in tf {
+                new_postings.entry(term).or_default().push((id, freq));
+            }
+
+            let indexed = IndexedChunk {
+                chunk: chunk.clone(),
+                length: tokens.len() as u32,
+            };
+            self.docs
+                .insert(id.to_be_bytes(), serde_json::to_vec(&indexed)?)?;
+
+            stats.doc_count += 1;
+            stats.total_tokens += tokens.len() as u64;
+        }
+
+        for (term, postings) in new_postings {
+            let mut merged = self.load_postings(&term)?;
+            merged.extend(postings);
+            self.postings
+                .insert(term.as_bytes(), bincode::serialize(&merged)?)?;
+        }
+
+        self.db.insert(STATS_KEY, bincode::serialize(&stats)?)?;
+        self.db.flush()?;
+
+        Ok(())
+    }
+
+    fn load_postings(&self, term: &str) -> Result<Vec<Posting>> {
+        Ok(match self.postings.get(term.as_bytes())? {
+            Some(bytes) => bincode::deserialize(&bytes)?,
+            None => Vec::new(),
+        })
+    }
+
+    fn load_chunk(&self, id: u64) -> Result<Option<IndexedChunk>> {
+        Ok(match self.docs.get(id.to_be_bytes())? {
+            Some(bytes) => Some(serde_json::from_slice(&bytes)?),
+            None => None,
+        })
+    }
+
+    /// Returns up to `k` chunks matching `predicate`, best BM25 score first.
+    pub fn search<F>(&self, query: &str, k: usize, predicate: F) -> Result<Vec<(DocumentChunk, f32)>>
+    where
+        F: Fn(&DocumentChunk) -> bool,
+    {
+        let stats = self.stats()?;
+        if stats.doc_count == 0 {
+            return Ok(Vec::new());
+        }
+        let n = stats.doc_count as f32;
+        let avg_len = stats.total_tokens as f32 / n;
+
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+
+        let mut scores: HashMap<u64, f32> = HashMap::new();
+        let mut lengths: HashMap<u64, u32> = HashMap::new();
+        for term in terms {
+            let postings = self.load_postings(&term)?;
+            if postings.is_empty() {
+                continue;
+            }
+            let df = postings.len() as f32;
+            let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+
+            for (id, tf) in postings {
+                let len = match lengths.get(&id) {
+                    Some(len) => *len,
+                    None => {
+                        let len = self.load_chunk(id)?.map(|c| c.length).unwrap_or(0);
+                        lengths.insert(id, len);
+                        len
+                    }
+                };
+                let tf = tf as f32;
+                let norm = K1 * (1.0 - B + B * len as f32 / avg_len);
+                *scores.entry(id).or_default() += idf * tf * (K1 + 1.0) / (tf + norm);
+            }
+        }
+
+        let mut ranked: Vec<(u64, f32)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let mut results = Vec::new();
+        for (id, score) in ranked {
+            if results.len() == k {
+                break;
+            }
+            if let Some(indexed) = self.load_chunk(id)? {
+                if predicate(&indexed.chunk) {
+                    results.push((indexed.chunk, score));
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// Lowercased alphanumeric tokens. Identifiers such as IBANs and passport numbers
+/// survive intact because only non-alphanumeric characters split tokens.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use private_context_core::ChunkMetadata;
+
+    fn chunk(content: &str, source: &str) -> DocumentChunk {
+        DocumentChunk {
+            content: content.to_string(),
+            metadata: ChunkMetadata {
+                source: source.to_string(),
+                proof_id: None,
+                provider: None,
+                model_hash: None,
+                claim: None,
+                claim_binding: None,
+                commitment: None,
+                span: None,
+            },
+        }
+    }
+
+    #[test]
+    fn exact_identifier_ranks_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = LexicalIndex::open(&dir.path().to_string_lossy()).unwrap();
+        index
+            .add_chunks(&[
+                chunk("Account summary for the quarter", "a.txt"),
+                chunk("IBAN DE89370400440532013000 belongs to the holder", "b.txt"),
+                chunk("The holder is an Accredited Investor", "c.txt"),
+            ])
+            .unwrap();
+
+        let results = index
+            .search("DE89370400440532013000", 3, |_| true)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.metadata.source, "b.txt");
+
+        let results = index.search("accredited investor", 3, |_| true).unwrap();
+        assert_eq!(results[0].0.metadata.source, "c.txt");
+    }
+
+    #[test]
+    fn predicate_filters_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = LexicalIndex::open(&dir.path().to_string_lossy()).unwrap();
+        index
+            .add_chunks(&[chunk("passport X1234567", "a.txt"), chunk("passport X1234567", "b.txt")])
+            .unwrap();
+
+        let results = index
+            .search("passport", 5, |c| c.metadata.source == "b.txt")
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.metadata.source, "b.txt");
+    }
+}
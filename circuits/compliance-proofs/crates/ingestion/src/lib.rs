@@ -0,0 +1,14 @@
+pub mod config;
+pub mod daemon;
+pub mod db;
+pub mod embedder;
+pub mod extract;
+pub mod lexical;
+pub mod manifest;
+pub mod parser;
+pub mod pipeline;
+pub mod prover;
+pub mod redact;
+pub mod retrieval;
+pub mod wallet;
+pub mod watchlist;
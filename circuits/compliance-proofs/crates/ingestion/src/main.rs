@@ -1,33 +1,106 @@
-mod db;
-mod embedder;
-mod parser;
-
-use crate::db::LocalStore;
-use crate::embedder::{CandleEmbedder, Embedder};
-use crate::parser::{DocumentParser, RecursiveCharacterParser};
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use std::path::Path;
+use chrono::Utc;
+use clap::{Args, Parser, Subcommand};
+use private_context_core::disclosure::DisclosurePackage;
+use private_context_ingestion::config::Config;
+use private_context_ingestion::daemon::{self, DaemonState, JobQueue};
+use private_context_ingestion::db::{open_store, StoreBackend, StoreConfig};
+use private_context_ingestion::embedder::{CandleEmbedder, Embedder, ModelFamily};
+use private_context_ingestion::extract::{self, Extractor, LlmExtractor, RuleExtractor};
+use private_context_ingestion::lexical::LexicalIndex;
+use private_context_ingestion::manifest::{
+    policy_hash, prover_key, sha256_file, ChunkerSettings, ProofBundle, ProofKind, ProofManifest,
+    SignedManifest, PROOF_FILE,
+};
+use private_context_ingestion::parser::{Chunker, DocumentParser, CHUNK_SIZE};
+use private_context_ingestion::pipeline::{
+    annotate_chunk, collect_inputs, load_claim, Checkpoints, IngestOptions, Ingestor,
+};
+use private_context_ingestion::prover::{
+    sp1_version, ChunkedRelevanceInputs, ExclusionInputs, ProverMode, RelevanceInputs,
+    RelevanceProver,
+};
+use private_context_ingestion::redact::Redactor;
+use private_context_ingestion::retrieval::{chunk_id, retrieve, MetadataFilter, SearchMode};
+use private_context_ingestion::wallet::{
+    expiry, passphrase_from_env, proof_id, Consumption, Holder, HolderKind, ProofRecord, Wallet,
+};
+use private_context_ingestion::watchlist::WatchList;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// Config file; `private-context.toml` in the working directory is used when present
+    #[arg(long, global = true, env = "PRIVATE_CONTEXT_CONFIG")]
+    config: Option<PathBuf>,
+    /// Named profile from the config file or a built-in one (dev, mock, staging, prod)
+    #[arg(long, global = true, env = "PRIVATE_CONTEXT_PROFILE")]
+    profile: Option<String>,
+    /// Storage backend for chunks and vectors
+    #[arg(long, global = true, value_enum)]
+    backend: Option<StoreBackend>,
+    /// Directory holding the local store
+    #[arg(long, global = true)]
+    data_dir: Option<String>,
+    /// Model directory (contains config.json, tokenizer.json, model.safetensors)
+    #[arg(long, global = true)]
+    model_dir: Option<String>,
+    /// Embedding model family; detected from the model's config.json when omitted
+    #[arg(long, global = true, value_enum)]
+    embedder: Option<ModelFamily>,
+    /// Wallet holder whose encrypted store and proof history to use
+    #[arg(long, global = true)]
+    holder: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Args)]
+struct RetrievalArgs {
+    /// Retrieval strategy
+    #[arg(long, value_enum, default_value_t = SearchMode::Hybrid)]
+    mode: SearchMode,
+    /// Only consider chunks from this source path
+    #[arg(long)]
+    source: Option<String>,
+    /// Only consider chunks attested by this provider
+    #[arg(long)]
+    provider: Option<String>,
+}
+
+impl RetrievalArgs {
+    fn filter(&self) -> MetadataFilter {
+        MetadataFilter {
+            source: self.source.clone(),
+            provider: self.provider.clone(),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
-    /// Ingest a document into the private database
+    /// Ingest documents into the private database
     Ingest {
-        /// Path to the document
+        /// File, directory (recursive) or quoted glob such as "filings/**/*.md"
         path: String,
-        /// Model directory (contains config.json, tokenizer.json, model.safetensors)
-        #[arg(long, default_value = "./models/all-MiniLM-L6-v2")]
-        model_dir: String,
         /// Path to Reclaim Protocol proof JSON file
         #[arg(long)]
         proof: Option<String>,
+        /// Chunks embedded per batch
+        #[arg(long, default_value_t = 32)]
+        batch_size: usize,
+        /// Worker threads for parsing and embedding (0 = one per core)
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+        /// Ignore checkpoints from earlier runs and re-ingest every file
+        #[arg(long)]
+        restart: bool,
+        /// Chunk boundaries; `verifiable` chunks can be proven with `prove --chunked`
+        #[arg(long, value_enum, default_value_t = Chunker::Recursive)]
+        chunker: Chunker,
     },
     /// Search for relevant documents
     Search {
@@ -36,122 +109,415 @@ enum Commands {
         /// Number of results to return
         #[arg(short, default_value_t = 5)]
         k: usize,
-        /// Model directory
-        #[arg(long, default_value = "./models/all-MiniLM-L6-v2")]
-        model_dir: String,
+        #[command(flatten)]
+        retrieval: RetrievalArgs,
+    },
+    /// Extract typed compliance fields from the top-k retrieved chunks
+    Extract {
+        /// Query describing what to look for
+        #[arg(required_unless_present = "schema")]
+        text: Option<String>,
+        /// Number of chunks to read
+        #[arg(short, default_value_t = 5)]
+        k: usize,
+        /// Quantized GGUF model for extraction; rule-based extraction when omitted
+        #[arg(long)]
+        llm: Option<String>,
+        /// Write the fields JSON here instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+        /// Print the JSON Schema of the output and exit
+        #[arg(long)]
+        schema: bool,
+        #[command(flatten)]
+        retrieval: RetrievalArgs,
     },
     /// Prove relevance (experimental)
     Prove {
         /// Query text
         text: String,
-        /// Model directory
-        #[arg(long, default_value = "./models/all-MiniLM-L6-v2")]
-        model_dir: String,
-        /// Threshold for similarity (default 0.7)
-        #[arg(long, default_value_t = 0.7)]
-        threshold: f32,
+        /// Threshold for similarity; defaults to the configured policy threshold
+        #[arg(long)]
+        threshold: Option<f32>,
+        /// Refuse to prove when the host score is this close to the threshold; defaults to the
+        /// configured policy uncertainty
+        #[arg(long)]
+        uncertainty: Option<f32>,
+        /// Prove with the chunked guest, which cuts the chunk from the full source document so
+        /// the committed hash is the document's; needs chunks ingested with `--chunker verifiable`
+        #[arg(long)]
+        chunked: bool,
+        #[command(flatten)]
+        retrieval: RetrievalArgs,
+    },
+    /// Prove that no chunk of a document matches any watch-list entity
+    Screen {
+        /// Document to screen
+        path: PathBuf,
+        /// Entities, one per line, or a `.json` list saved by an earlier run
+        #[arg(long)]
+        watchlist: PathBuf,
+        /// Similarity at which a chunk counts as a match; defaults to the configured policy
+        /// threshold
+        #[arg(long)]
+        threshold: Option<f32>,
+        /// Refuse to prove when the closest match is this close to the threshold; defaults to
+        /// the configured policy uncertainty
+        #[arg(long)]
+        uncertainty: Option<f32>,
+    },
+    /// Export a redacted excerpt of the best-matching chunk as a disclosure package
+    Disclose {
+        /// Query text
+        text: String,
+        /// Number of candidate chunks
+        #[arg(short, default_value_t = 5)]
+        k: usize,
+        /// Disclose this chunk id from the candidates instead of the top hit
+        #[arg(long)]
+        chunk_id: Option<String>,
+        /// Extra terms to redact, one per line, optionally prefixed `name:`, `account:`, `address:` or `id:`
+        #[arg(long)]
+        dictionary: Option<String>,
+        /// Where to write the package; `disclosure.json` in the output directory by default
+        #[arg(long)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        retrieval: RetrievalArgs,
+    },
+    /// Check a proof bundle against its signed provenance manifest
+    VerifyManifest {
+        /// Bundle directory under `<output_dir>/proofs`, or its manifest.json
+        bundle: PathBuf,
+        /// Expected signer public key (hex)
+        #[arg(long)]
+        signer: Option<String>,
+        /// Guest ELF to check against the recorded ELF hash and verifying key
+        #[arg(long)]
+        elf: Option<PathBuf>,
+    },
+    /// Check a disclosure package against its document root
+    VerifyDisclosure {
+        /// Disclosure package JSON
+        package: String,
+        /// Expected document root (hex); only internal consistency is checked when omitted
+        #[arg(long)]
+        root: Option<String>,
+    },
+    /// Run the local proving daemon (HTTP on 127.0.0.1)
+    Serve {
+        /// Port on the loopback interface
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+        /// Prover backend; defaults to the configured prover
+        #[arg(long, value_enum)]
+        prover: Option<ProverMode>,
+        /// Chunks embedded per batch during ingest jobs
+        #[arg(long, default_value_t = 32)]
+        batch_size: usize,
+        /// Worker threads for ingest jobs (0 = one per core)
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+    },
+    /// Inspect the resolved configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Manage holder profiles and their proof history
+    Wallet {
+        #[command(subcommand)]
+        action: WalletAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the effective settings after the file, profile, environment and flags are applied
+    Show,
+}
+
+#[derive(Subcommand)]
+enum WalletAction {
+    /// Create a holder with its own encrypted store, signing key and proof history
+    Create {
+        /// Lowercase letters, digits, `-` or `_`
+        name: String,
+        #[arg(long, value_enum, default_value_t = HolderKind::Entity)]
+        kind: HolderKind,
+    },
+    /// List holder profiles
+    List,
+    /// List the selected holder's proofs, which contract consumed each and when it expires
+    Proofs,
+    /// Record that a contract consumed one of the selected holder's proofs
+    Consume {
+        /// Proof id from `wallet proofs`
+        proof_id: String,
+        /// Contract that verified the proof
+        #[arg(long)]
+        contract: String,
+        /// Transaction hash
+        #[arg(long)]
+        tx: String,
+        /// Defaults to the configured chain id
+        #[arg(long)]
+        chain_id: Option<String>,
     },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
-    let data_dir = "data";
+    let (mut config, origin) = Config::load(cli.config.as_deref(), cli.profile.as_deref())?;
+    // Flags win over the file, the profile and the environment
+    if let Some(backend) = cli.backend {
+        config.backend = backend;
+    }
+    if let Some(data_dir) = cli.data_dir {
+        config.data_dir = data_dir;
+    }
+    if let Some(model_dir) = cli.model_dir {
+        config.model_dir = model_dir;
+    }
+    if cli.embedder.is_some() {
+        config.embedder = cli.embedder;
+    }
+    if cli.holder.is_some() {
+        config.holder = cli.holder;
+    }
+
+    if let Commands::Config {
+        action: ConfigAction::Show,
+    } = cli.command
+    {
+        match &origin.file {
+            Some(path) => println!("# file: {}", path.display()),
+            None => println!("# file: none (built-in defaults)"),
+        }
+        if let Some(profile) = &origin.profile {
+            println!("# profile: {}", profile);
+        }
+        print!("{}", config.to_toml()?);
+        return Ok(());
+    }
+    if let Commands::Wallet { action } = cli.command {
+        return wallet_command(action, &config);
+    }
+
+    let holder = match &config.holder {
+        Some(name) => {
+            let holder = Wallet::new(&config.data_dir).unlock(name, &passphrase_from_env()?)?;
+            println!("👤 Holder: {}", name);
+            Some(holder)
+        }
+        None => None,
+    };
+    let result = run(cli.command, &config, holder.as_ref()).await;
+    // Re-encrypt the holder's store even when the command failed
+    if let Some(holder) = holder {
+        holder.close()?;
+    }
+    result
+}
+
+async fn run(command: Commands, config: &Config, holder: Option<&Holder>) -> Result<()> {
+    let data_dir = match holder {
+        Some(holder) => holder.open_store()?.to_string_lossy().into_owned(),
+        None => config.data_dir.clone(),
+    };
+    // Vector width always comes from the loaded model so the store schema cannot drift from it
+    let store_config = |embedder: &CandleEmbedder| StoreConfig {
+        backend: config.backend,
+        data_dir: data_dir.clone(),
+        dimension: embedder.dimension(),
+    };
 
     // Ensure data directory exists
-    std::fs::create_dir_all(data_dir)?;
+    std::fs::create_dir_all(&data_dir)?;
 
-    match cli.command {
+    match command {
         Commands::Ingest {
             path,
-            model_dir,
             proof,
+            batch_size,
+            threads,
+            restart,
+            chunker,
         } => {
             // ... (existing ingestion code)
             println!("🚀 Ingesting document from: {}", path);
 
             // 1. Verify Proof if provided
-            let mut proof_id = None;
-            let mut provider = None;
-
-            if let Some(proof_path) = proof {
-                println!("🔒 Verifying Reclaim proof: {}", proof_path);
-                let proof_json =
-                    std::fs::read_to_string(&proof_path).context("Failed to read proof file")?;
-
-                let proof_obj = private_context_core::verifier::Proof::new(&proof_json);
-
-                if private_context_core::verifier::verify_proof(&proof_obj).await? {
+            let claim = match proof {
+                Some(proof_path) => {
+                    println!("🔒 Verifying Reclaim proof: {}", proof_path);
+                    let claim = load_claim(&proof_path).await?;
                     println!("✅ Proof verified successfully!");
-                    // In a real app, we'd extract these from the decoded proof
-                    proof_id = Some("verified-proof-id".to_string());
-                    provider = Some("reclaim-provider".to_string());
-                } else {
-                    return Err(anyhow::anyhow!("❌ Proof verification failed!"));
+                    println!(
+                        "   Claim {} from provider `{}` (owner {})",
+                        claim.identifier, claim.provider, claim.owner
+                    );
+                    Some(claim)
                 }
-            }
+                None => None,
+            };
 
-            let embedder = CandleEmbedder::new(Path::new(&model_dir)).context(
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
+                .context(
                 "Failed to initialize embedder. Ensure model files are in the specified directory.",
             )?;
+            let store_config = store_config(&embedder);
 
-            let parser = RecursiveCharacterParser::new(500); // 500 chars chunk size
-            let mut chunks = parser.parse(Path::new(&path))?;
-            println!("📄 Split into {} chunks", chunks.len());
+            let files = collect_inputs(&path)?;
+            println!("📄 Found {} file(s)", files.len());
 
-            // Attach Proof Metadata
-            if proof_id.is_some() {
-                for chunk in &mut chunks {
-                    chunk.metadata.proof_id = proof_id.clone();
-                    chunk.metadata.provider = provider.clone();
-                }
+            let parser = chunker.parser(CHUNK_SIZE);
+            let mut store = open_store(&store_config)?;
+            // Lexical index lives next to the vector index so hybrid search sees the same chunks
+            let lexical = LexicalIndex::open(&store_config.data_dir)?;
+            let checkpoints = Checkpoints::open(&store_config.data_dir)?;
+            if restart {
+                checkpoints.clear()?;
             }
 
-            let texts: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
-            let embeddings = embedder.embed_batch(&texts)?;
+            let model_hash = embedder.model_hash().to_string();
+            let mut ingestor = Ingestor {
+                parser: &parser,
+                embedder: &embedder,
+                store: &mut store,
+                lexical: &lexical,
+                checkpoints: &checkpoints,
+                options: IngestOptions {
+                    batch_size,
+                    threads,
+                },
+            };
+
+            // Attach Proof and Model Metadata
+            let report = ingestor.run(&files, |chunk, document_hash| {
+                annotate_chunk(chunk, document_hash, &model_hash, claim.as_ref())
+            })?;
 
-            let store = LocalStore::new(data_dir)?;
-            store.add_chunks(chunks, embeddings)?;
+            println!(
+                "📊 {} file(s) ingested ({} chunks), {} unchanged, {} failed",
+                report.files_ingested,
+                report.chunks,
+                report.files_skipped,
+                report.failures.len()
+            );
             println!("✅ Ingestion complete!");
         }
-        Commands::Search { text, k, model_dir } => {
-            println!("🔍 Searching for: '{}'", text);
+        Commands::Search { text, k, retrieval } => {
+            println!("🔍 Searching for: '{}' ({:?})", text, retrieval.mode);
 
-            let embedder = CandleEmbedder::new(Path::new(&model_dir))
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
                 .context("Failed to initialize embedder")?;
+            let store_config = store_config(&embedder);
 
-            let query_vector = embedder.embed(&text)?;
+            let query_vector = embedder.embed_query(&text)?;
 
-            let store = LocalStore::new(data_dir)?;
-            let results = store.search(&query_vector, k)?;
+            let store = open_store(&store_config)?;
+            let lexical = LexicalIndex::open(&store_config.data_dir)?;
+            let results = retrieve(
+                &store,
+                &lexical,
+                &text,
+                &query_vector,
+                k,
+                retrieval.mode,
+                &retrieval.filter(),
+            )?;
 
             println!("🎯 Found {} results:", results.len());
             for (i, res) in results.iter().enumerate() {
                 println!("\n[{}] Relevance rank", i + 1);
                 println!("Source: {}", res.metadata.source);
+                warn_on_model_mismatch(res, &embedder);
                 println!(
                     "Content: {}...",
                     res.content.chars().take(200).collect::<String>()
                 );
             }
         }
+        Commands::Extract {
+            text,
+            k,
+            llm,
+            output,
+            schema,
+            retrieval,
+        } => {
+            if schema {
+                println!("{}", serde_json::to_string_pretty(&extract::schema())?);
+                return Ok(());
+            }
+            let text = text.unwrap_or_default();
+            println!("🧾 Extracting fields for: '{}'", text);
+
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
+                .context("Failed to initialize embedder")?;
+            let store_config = store_config(&embedder);
+            let query_vector = embedder.embed_query(&text)?;
+
+            let store = open_store(&store_config)?;
+            let lexical = LexicalIndex::open(&store_config.data_dir)?;
+            let chunks = retrieve(
+                &store,
+                &lexical,
+                &text,
+                &query_vector,
+                k,
+                retrieval.mode,
+                &retrieval.filter(),
+            )?;
+            println!("📄 Reading {} chunk(s)", chunks.len());
+
+            let extractor: Box<dyn Extractor> = match llm {
+                Some(path) => Box::new(
+                    LlmExtractor::new(Path::new(&path)).context("Failed to load extraction model")?,
+                ),
+                None => Box::new(RuleExtractor),
+            };
+            let fields = extractor.extract(&chunks)?;
+            let json = serde_json::to_string_pretty(&fields)?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, &json).context("Failed to write extracted fields")?;
+                    println!("💾 Fields saved to {}", path);
+                }
+                None => println!("{}", json),
+            }
+        }
         Commands::Prove {
             text,
-            model_dir,
             threshold,
+            uncertainty,
+            chunked,
+            retrieval,
         } => {
-            use sp1_sdk::{HashableKey, ProverClient, SP1Stdin};
-
+            let started_at = Utc::now();
             println!("🧪 Generating Proof for: '{}'", text);
 
             // 1. Get embedding for query
-            let embedder = CandleEmbedder::new(Path::new(&model_dir))
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
                 .context("Failed to initialize embedder")?;
-            let query_vector = embedder.embed(&text)?;
+            let store_config = store_config(&embedder);
+            let query_vector = embedder.embed_query(&text)?;
 
             // 2. Fetch top result (mockup: using search to get a chunk)
-            let store = LocalStore::new(data_dir)?;
-            let results = store.search(&query_vector, 1)?;
+            let store = open_store(&store_config)?;
+            let lexical = LexicalIndex::open(&store_config.data_dir)?;
+            let results = retrieve(
+                &store,
+                &lexical,
+                &text,
+                &query_vector,
+                1,
+                retrieval.mode,
+                &retrieval.filter(),
+            )?;
 
             if results.is_empty() {
                 println!("❌ No documents found to prove against.");
@@ -167,23 +533,69 @@ async fn main() -> Result<()> {
             // For this MVP, we re-embed or modify search to return vectors?
             // Or simpler: We simulate the vector since we verify the *logic* of the circuit.
             // Let's re-embed the chunk content for now (inefficient but works for MVP).
-            let chunk_vector = embedder.embed(&chunk.content)?;
-
-            // 3. Setup SP1 Prover inputs
-            let mut stdin = SP1Stdin::new();
-            stdin.write(&query_vector);
-            stdin.write(&chunk_vector);
-            stdin.write(&threshold);
+            warn_on_model_mismatch(chunk, &embedder);
+            let chunk_vector = embedder
+                .embed_passages(&[chunk.content.as_str()])?
+                .remove(0);
 
             // Calculate document hash
             use sha2::{Digest, Sha256};
             let mut hasher = Sha256::new();
             hasher.update(chunk.content.as_bytes());
             let document_hash: [u8; 32] = hasher.finalize().into();
-            stdin.write(&document_hash);
 
-            // 4. Generate Proof
-            let elf_path = "crates/circuits/elf/riscv32im-succinct-zkvm-elf";
+            // Zero identifier when the chunk has no web attestation
+            let claim_identifier = match &chunk.metadata.claim {
+                Some(claim) => {
+                    println!("🔗 Binding proof to Reclaim claim {}", claim.identifier);
+                    claim.identifier_bytes()?
+                }
+                None => [0u8; 32],
+            };
+
+            let inputs = RelevanceInputs {
+                query_vector,
+                chunk_vector,
+                threshold: threshold.unwrap_or(config.policy.threshold as f32),
+                document_hash,
+                claim_identifier,
+            };
+            let band = uncertainty.unwrap_or(config.policy.uncertainty as f32);
+            let host_similarity = inputs.ensure_decidable(band)?;
+            println!("📐 Host Similarity: {}", host_similarity);
+
+            // The chunked guest hashes the whole source file and cuts the chunk itself
+            let chunked_inputs = if chunked {
+                let Some(span) = chunk.metadata.span else {
+                    anyhow::bail!(
+                        "Chunk from {} has no span; re-ingest it with `--chunker verifiable`",
+                        chunk.metadata.source
+                    );
+                };
+                let document = std::fs::read(&chunk.metadata.source).with_context(|| {
+                    format!("Failed to read source document {}", chunk.metadata.source)
+                })?;
+                let chunked_inputs = ChunkedRelevanceInputs::new(document, span, inputs.clone());
+                if chunked_inputs.chunk()? != chunk.content {
+                    anyhow::bail!(
+                        "{} changed since it was ingested; re-ingest it before proving",
+                        chunk.metadata.source
+                    );
+                }
+                println!(
+                    "✂️  Guest will cut chunk {} (size {}) from the full document",
+                    span.index, span.chunk_size
+                );
+                Some(chunked_inputs)
+            } else {
+                None
+            };
+
+            // 3. Generate Proof
+            let elf_path = match chunked_inputs {
+                Some(_) => config.chunked_elf.as_str(),
+                None => config.elf.as_str(),
+            };
 
             if !std::path::Path::new(elf_path).exists() {
                 println!("⚠️  Guest ELF not found at {}.", elf_path);
@@ -192,75 +604,515 @@ async fn main() -> Result<()> {
 
             println!("🔨 Generating proof...");
 
-            // Check SP1_PROVER env var
-            let prover_mode = std::env::var("SP1_PROVER").unwrap_or("local".to_string());
-            println!("🚀 Active Mode: {}", prover_mode.to_uppercase());
+            let prover_mode = config.prover;
+            println!("🚀 Active Mode: {:?}", prover_mode);
 
-            let client = ProverClient::new();
-            let (pk, vk) = client.setup(&std::fs::read(elf_path)?);
+            let prover = RelevanceProver::new(Path::new(elf_path), prover_mode)?;
 
             // Export VKEY Hash for Solana (Do this before Mock check)
-            let vkey_hash = vk.bytes32();
+            let vkey_hash = prover.vkey_hash();
             println!("🔑 VKey Hash: {}", vkey_hash);
-            std::fs::write("vkey_hash.txt", &vkey_hash).context("Failed to write vkey hash")?;
+            std::fs::write(config.output_path("vkey_hash.txt")?, &vkey_hash)
+                .context("Failed to write vkey hash")?;
 
-            if prover_mode.to_lowercase() == "mock" {
+            if prover_mode == ProverMode::Mock {
                 println!("⚠️  Running in MOCK mode. Skipping Groth16 generation.");
-                let (public_values, _report) = client.execute(&pk.elf, stdin).run().unwrap();
-                println!("✅ Mock execution successful!");
-
-                // Save Public Values
-                let public_values_bytes = public_values.to_vec();
-                let pub_path = "public_values.bin";
-                std::fs::write(pub_path, &public_values_bytes)
-                    .context("Failed to write public values")?;
-                println!("💾 Public Values saved to {}", pub_path);
-
-                // Verify public output
-                let mut pv = public_values.clone();
-                let _committed_hash: [u8; 32] = pv.read();
-                let is_relevant: bool = pv.read();
-                let similarity: f32 = pv.read();
-
-                println!("🎉 Verification Result: Relevant? {}", is_relevant);
-                println!("📊 Computed Similarity: {}", similarity);
-                return Ok(());
+            } else {
+                println!("🔒 Generating full Groth16 proof...");
             }
-
-            // Generate Groth16 proof
-            println!("🔒 Generating full Groth16 proof...");
-            let mut proof = client.prove(&pk, stdin).groth16().run().unwrap();
-
-            println!("✅ Proof generated successfully!");
+            let proof = match &chunked_inputs {
+                Some(chunked_inputs) => prover.prove_chunked(chunked_inputs)?,
+                None => prover.prove(&inputs)?,
+            };
 
             // Save proof for Solana
-            let proof_bytes = proof.bytes();
-            let proof_path = "proof_groth16.bin";
-            std::fs::write(proof_path, &proof_bytes).context("Failed to write proof file")?;
-            println!("💾 Proof saved to {}", proof_path);
+            match &proof.proof {
+                Some(proof_bytes) => {
+                    println!("✅ Proof generated successfully!");
+                    let proof_path = config.output_path("proof_groth16.bin")?;
+                    std::fs::write(&proof_path, proof_bytes)
+                        .context("Failed to write proof file")?;
+                    println!("💾 Proof saved to {}", proof_path.display());
+                }
+                None => println!("✅ Mock execution successful!"),
+            }
 
             // Save Public Values
-            let public_values_bytes = proof.public_values.to_vec();
-            let pub_path = "public_values.bin";
-            std::fs::write(pub_path, &public_values_bytes)
+            let pub_path = config.output_path("public_values.bin")?;
+            std::fs::write(&pub_path, &proof.public_values)
                 .context("Failed to write public values")?;
-            println!("💾 Public Values saved to {}", pub_path);
+            println!("💾 Public Values saved to {}", pub_path.display());
+
+            // Verify public output
+            println!("🎉 Verification Result: Relevant? {}", proof.outputs.is_relevant);
+            println!("📊 Computed Similarity: {}", proof.outputs.similarity);
+            if let Some(claim) = proof.outputs.claim_identifier {
+                println!("🔗 Committed Claim: 0x{}", hex::encode(claim));
+            }
+            if let Some(committed) = &proof.outputs.chunk {
+                println!(
+                    "🧩 Committed Chunk {}: 0x{}",
+                    committed.span.index,
+                    hex::encode(committed.hash)
+                );
+            }
 
-            // Export VKEY Hash for Solana
-            let vkey_hash = vk.bytes32();
+            let manifest = ProofManifest {
+                kind: match chunked_inputs {
+                    Some(_) => ProofKind::Chunked,
+                    None => ProofKind::Relevance,
+                },
+                proof_id: proof_id(&proof.public_values),
+                elf_sha256: sha256_file(Path::new(elf_path))?,
+                vkey_hash: vkey_hash.clone(),
+                sp1_version: sp1_version().to_string(),
+                prover: prover_mode,
+                model_hash: embedder.model_hash().to_string(),
+                policy: config.policy.clone(),
+                policy_hash: policy_hash(&config.policy)?,
+                threshold: inputs.threshold,
+                uncertainty: band,
+                chunker: chunk.metadata.span.map(|span| ChunkerSettings {
+                    chunker: Chunker::Verifiable,
+                    chunk_size: span.chunk_size,
+                }),
+                files: Vec::new(),
+                started_at,
+                finished_at: Utc::now(),
+            };
+            let bundle_dir = seal_bundle(
+                config,
+                holder,
+                &proof.public_values,
+                proof.proof.as_deref(),
+                manifest,
+            )?;
+
+            // Mock runs have nothing a contract could consume, so only real proofs are recorded
+            if let (Some(holder), Some(_)) = (holder, &proof.proof) {
+                let issued_at = Utc::now();
+                let record = ProofRecord {
+                    id: proof_id(&proof.public_values),
+                    issued_at,
+                    expires_at: expiry(issued_at, config.policy.validity_days),
+                    document_hash: hex::encode(proof.outputs.document_hash),
+                    source: Some(chunk.metadata.source.clone()),
+                    query: Some(text.clone()),
+                    similarity: proof.outputs.similarity,
+                    vkey_hash: Some(vkey_hash),
+                    proof_path: Some(bundle_dir.join(PROOF_FILE)),
+                    signature: holder.sign(&proof.public_values)?,
+                    consumed_by: None,
+                };
+                println!(
+                    "🗂️  Recorded proof {} for holder {}",
+                    record.id, holder.profile.name
+                );
+                holder.record_proof(record)?;
+            }
+        }
+        Commands::Screen {
+            path,
+            watchlist,
+            threshold,
+            uncertainty,
+        } => {
+            let started_at = Utc::now();
+            println!(
+                "🛡️  Screening {} against {}",
+                path.display(),
+                watchlist.display()
+            );
+
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
+                .context("Failed to initialize embedder")?;
+            let list = WatchList::load(&watchlist, &embedder)?;
+            println!("📋 {} watch-list entities", list.entries.len());
+
+            // The guest re-derives the chunk count from the document, so only the verifiable
+            // chunker can be screened
+            let document = std::fs::read(&path).context("Failed to read document")?;
+            let chunks = Chunker::Verifiable.parser(CHUNK_SIZE).parse(&path)?;
+            let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+            let chunk_vectors = embedder.embed_passages(&contents)?;
+            println!("📄 {} chunk(s)", chunks.len());
+
+            let inputs = ExclusionInputs {
+                document,
+                chunk_size: CHUNK_SIZE as u32,
+                chunk_vectors,
+                watchlist: list.vectors.clone(),
+                threshold: threshold.unwrap_or(config.policy.threshold as f32),
+            };
+            inputs.validate()?;
+            let band = uncertainty.unwrap_or(config.policy.uncertainty as f32);
+            let closest = inputs.ensure_decidable(band)?;
+            if let Some(closest) = closest {
+                println!("📐 Closest host similarity: {}", closest);
+            }
+
+            let elf_path = Path::new(&config.exclusion_elf);
+            if !elf_path.exists() {
+                println!("⚠️  Guest ELF not found at {}.", elf_path.display());
+                return Ok(());
+            }
+
+            let prover = RelevanceProver::new(elf_path, config.prover)?;
+            println!("🚀 Active Mode: {:?}", config.prover);
+            let vkey_hash = prover.vkey_hash();
             println!("🔑 VKey Hash: {}", vkey_hash);
-            std::fs::write("vkey_hash.txt", vkey_hash).context("Failed to write vkey hash")?;
+            std::fs::write(config.output_path("exclusion_vkey_hash.txt")?, &vkey_hash)
+                .context("Failed to write vkey hash")?;
 
-            // Verify public output
-            // Read in order: document_hash, is_relevant, similarity
-            let _committed_hash: [u8; 32] = proof.public_values.read();
-            let is_relevant: bool = proof.public_values.read();
-            let similarity: f32 = proof.public_values.read();
+            let proof = prover.prove_exclusion(&inputs)?;
+            if let Some(proof_bytes) = &proof.proof {
+                let proof_path = config.output_path("exclusion_proof_groth16.bin")?;
+                std::fs::write(&proof_path, proof_bytes).context("Failed to write proof file")?;
+                println!("💾 Proof saved to {}", proof_path.display());
+            }
+            let pub_path = config.output_path("exclusion_public_values.bin")?;
+            std::fs::write(&pub_path, &proof.public_values)
+                .context("Failed to write public values")?;
+            println!("💾 Public Values saved to {}", pub_path.display());
+
+            // Published alongside the proof so verifiers can recompute the list hash
+            let list_path = config.output_path("watchlist.json")?;
+            list.save(&list_path)?;
+            println!("💾 Committed watch-list saved to {}", list_path.display());
 
-            println!("🎉 Verification Result: Relevant? {}", is_relevant);
-            println!("📊 Computed Similarity: {}", similarity);
+            let manifest = ProofManifest {
+                kind: ProofKind::Exclusion,
+                proof_id: proof_id(&proof.public_values),
+                elf_sha256: sha256_file(elf_path)?,
+                vkey_hash,
+                sp1_version: sp1_version().to_string(),
+                prover: config.prover,
+                model_hash: embedder.model_hash().to_string(),
+                policy: config.policy.clone(),
+                policy_hash: policy_hash(&config.policy)?,
+                threshold: inputs.threshold,
+                uncertainty: band,
+                chunker: Some(ChunkerSettings {
+                    chunker: Chunker::Verifiable,
+                    chunk_size: CHUNK_SIZE as u32,
+                }),
+                files: Vec::new(),
+                started_at,
+                finished_at: Utc::now(),
+            };
+            seal_bundle(
+                config,
+                holder,
+                &proof.public_values,
+                proof.proof.as_deref(),
+                manifest,
+            )?;
+
+            println!(
+                "📄 Document hash: 0x{}",
+                hex::encode(proof.outputs.document_hash)
+            );
+            println!("🌳 Chunk root: 0x{}", hex::encode(proof.outputs.chunk_root));
+            println!("📋 List hash: 0x{}", hex::encode(proof.outputs.list_hash));
+            if proof.outputs.is_clear {
+                println!(
+                    "✅ No chunk matches any entity at {}",
+                    proof.outputs.threshold
+                );
+            } else {
+                println!(
+                    "❌ At least one chunk matches an entity at {}",
+                    proof.outputs.threshold
+                );
+            }
+        }
+        Commands::Disclose {
+            text,
+            k,
+            chunk_id: wanted,
+            dictionary,
+            output,
+            retrieval,
+        } => {
+            println!("🕶️  Preparing disclosure for: '{}'", text);
+
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
+                .context("Failed to initialize embedder")?;
+            let store_config = store_config(&embedder);
+            let query_vector = embedder.embed_query(&text)?;
+
+            let store = open_store(&store_config)?;
+            let lexical = LexicalIndex::open(&store_config.data_dir)?;
+            let results = retrieve(
+                &store,
+                &lexical,
+                &text,
+                &query_vector,
+                k,
+                retrieval.mode,
+                &retrieval.filter(),
+            )?;
+
+            let chunk = match &wanted {
+                Some(id) => results.iter().find(|c| &chunk_id(c) == id),
+                None => results.first(),
+            };
+            let Some(chunk) = chunk else {
+                println!("❌ No matching chunk to disclose.");
+                return Ok(());
+            };
+
+            let mut redactor = Redactor::new();
+            if let Some(path) = dictionary {
+                redactor = redactor.load_dictionary(Path::new(&path))?;
+            }
+            let package = redactor.disclose(chunk)?;
+            package.verify()?;
+
+            let output = match output {
+                Some(path) => path,
+                None => config.output_path("disclosure.json")?,
+            };
+            std::fs::write(&output, serde_json::to_string_pretty(&package)?)
+                .context("Failed to write disclosure package")?;
+            println!("📄 Source: {}", package.source);
+            println!("🌳 Document root: {}", hex::encode(package.document_root));
+            println!("{}", package.redacted_text);
+            println!("💾 Disclosure package saved to {}", output.display());
+        }
+        Commands::VerifyManifest {
+            bundle,
+            signer,
+            elf,
+        } => {
+            let signed = SignedManifest::load(&bundle)?;
+            let bundle_dir = if bundle.is_dir() {
+                bundle.as_path()
+            } else {
+                bundle.parent().unwrap_or(Path::new("."))
+            };
+            signed.verify(bundle_dir)?;
+            let manifest = &signed.manifest;
+            println!(
+                "✅ Manifest signature and files check out ({:?} proof {})",
+                manifest.kind, manifest.proof_id
+            );
+
+            if let Some(signer) = signer {
+                if signer.trim_start_matches("0x") != signed.signer {
+                    anyhow::bail!("❌ Manifest was signed by {}", signed.signer);
+                }
+                println!("🔏 Signed by {}", signed.signer);
+            }
+            if let Some(elf) = elf {
+                if sha256_file(&elf)? != manifest.elf_sha256 {
+                    anyhow::bail!(
+                        "❌ {} is not the ELF the proof was made with",
+                        elf.display()
+                    );
+                }
+                // Mock setup derives the same verifying key without proving anything
+                let vkey_hash = RelevanceProver::new(&elf, ProverMode::Mock)?.vkey_hash();
+                if vkey_hash != manifest.vkey_hash {
+                    anyhow::bail!("❌ ELF yields verifying key {}", vkey_hash);
+                }
+                println!("🧬 ELF and verifying key match");
+            }
+            if manifest.sp1_version != sp1_version() {
+                println!(
+                    "⚠️  Proved with SP1 {}, this build uses {}",
+                    manifest.sp1_version,
+                    sp1_version()
+                );
+            }
+            println!("🧠 Model: {}", manifest.model_hash);
+            println!(
+                "📏 Threshold {} (uncertainty {}), policy {}",
+                manifest.threshold, manifest.uncertainty, manifest.policy_hash
+            );
+            println!(
+                "⏱️  {} → {}",
+                manifest.started_at.to_rfc3339(),
+                manifest.finished_at.to_rfc3339()
+            );
+        }
+        Commands::VerifyDisclosure { package, root } => {
+            let json = std::fs::read_to_string(&package).context("Failed to read package")?;
+            let package: DisclosurePackage =
+                serde_json::from_str(&json).context("Malformed disclosure package")?;
+            package.verify()?;
+
+            if let Some(root) = root {
+                if root.trim_start_matches("0x") != hex::encode(package.document_root) {
+                    anyhow::bail!("❌ Package commits to a different document root");
+                }
+            }
+            println!(
+                "✅ Disclosure matches document root {}",
+                hex::encode(package.document_root)
+            );
+            println!("{}", package.redacted_text);
+        }
+        Commands::Serve {
+            port,
+            prover,
+            batch_size,
+            threads,
+        } => {
+            if holder.is_some() {
+                println!("⚠️  The holder's store stays decrypted until the daemon exits.");
+            }
+            let embedder = CandleEmbedder::new(Path::new(&config.model_dir), config.embedder)
+                .context("Failed to initialize embedder")?;
+            let store_config = store_config(&embedder);
+
+            // Proving key setup is the slowest step, so do it once here
+            let prover_mode = prover.unwrap_or(config.prover);
+            let prover = match RelevanceProver::new(Path::new(&config.elf), prover_mode) {
+                Ok(prover) => Some(Arc::new(prover)),
+                Err(e) => {
+                    println!("⚠️  Proving disabled: {:#}", e);
+                    None
+                }
+            };
+
+            let state = Arc::new(DaemonState {
+                store: Arc::new(Mutex::new(open_store(&store_config)?)),
+                lexical: Arc::new(LexicalIndex::open(&store_config.data_dir)?),
+                checkpoints: Arc::new(Checkpoints::open(&store_config.data_dir)?),
+                embedder: Arc::new(embedder),
+                prover,
+                ingest_options: IngestOptions {
+                    batch_size,
+                    threads,
+                },
+                jobs: JobQueue::start(),
+                token: daemon::new_token(),
+            });
+
+            let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            println!("🛰️  Daemon listening on http://{}", addr);
+            println!("🔑 Bearer token for this launch: {}", state.token);
+            daemon::serve(listener, state).await?;
+        }
+        Commands::Config { .. } | Commands::Wallet { .. } => {
+            unreachable!("handled before the store is opened")
         }
     }
 
     Ok(())
 }
+
+/// Writes the run's outputs into a bundle and signs its manifest with the holder key, or the
+/// prover key when no holder is active. Returns the bundle directory.
+fn seal_bundle(
+    config: &Config,
+    holder: Option<&Holder>,
+    public_values: &[u8],
+    proof: Option<&[u8]>,
+    manifest: ProofManifest,
+) -> Result<PathBuf> {
+    let bundle = ProofBundle::write(Path::new(&config.output_dir), public_values, proof)?;
+    let dir = bundle.dir().to_path_buf();
+    let key = match holder {
+        Some(holder) => holder.signing_key()?,
+        None => prover_key(Path::new(&config.data_dir))?,
+    };
+    let manifest_path = bundle.seal(manifest, &key)?;
+    println!("📜 Signed manifest saved to {}", manifest_path.display());
+    Ok(dir)
+}
+
+fn wallet_command(action: WalletAction, config: &Config) -> Result<()> {
+    let wallet = Wallet::new(&config.data_dir);
+    let unlock = || {
+        let name = config
+            .holder
+            .as_deref()
+            .context("Select a holder with --holder or PRIVATE_CONTEXT_HOLDER")?;
+        wallet.unlock(name, &passphrase_from_env()?)
+    };
+
+    match action {
+        WalletAction::Create { name, kind } => {
+            let profile = wallet.create(&name, kind, &passphrase_from_env()?)?;
+            println!("👤 Created {} holder `{}`", kind.as_str(), profile.name);
+            println!("🔑 Public key: {}", profile.public_key);
+        }
+        WalletAction::List => {
+            let profiles = wallet.profiles()?;
+            if profiles.is_empty() {
+                println!("No holders yet; create one with `wallet create <name>`.");
+            }
+            for profile in profiles {
+                println!(
+                    "{:<24} {:<7} created {}  key {}",
+                    profile.name,
+                    profile.kind.as_str(),
+                    profile.created_at.format("%Y-%m-%d"),
+                    profile.public_key
+                );
+            }
+        }
+        WalletAction::Proofs => {
+            let holder = unlock()?;
+            let proofs = holder.proofs()?;
+            let now = Utc::now();
+            println!(
+                "🗂️  {} proof(s) for holder {}",
+                proofs.len(),
+                holder.profile.name
+            );
+            for proof in proofs {
+                println!("\n[{}] {}", proof.id, proof.status(now).as_str());
+                println!("Issued: {}", proof.issued_at.to_rfc3339());
+                match proof.expires_at {
+                    Some(at) => println!("Expires: {}", at.to_rfc3339()),
+                    None => println!("Expires: never"),
+                }
+                if let Some(source) = &proof.source {
+                    println!("Source: {}", source);
+                }
+                match &proof.consumed_by {
+                    Some(c) => println!(
+                        "Consumed by: {} on {} (tx {}, {})",
+                        c.contract,
+                        c.chain_id,
+                        c.tx_hash,
+                        c.at.to_rfc3339()
+                    ),
+                    None => println!("Consumed by: -"),
+                }
+            }
+        }
+        WalletAction::Consume {
+            proof_id,
+            contract,
+            tx,
+            chain_id,
+        } => {
+            let chain_id = chain_id
+                .or_else(|| config.chain.chain_id.clone())
+                .context("Pass --chain-id or set chain.chain_id in the config")?;
+            let holder = unlock()?;
+            let record = holder
+                .record_consumption(&proof_id, Consumption::now(&contract, &chain_id, &tx))?;
+            println!("✅ Proof {} marked as consumed by {}", record.id, contract);
+        }
+    }
+    Ok(())
+}
+
+/// Vectors from different models are not comparable, so flag chunks indexed with other weights.
+fn warn_on_model_mismatch(chunk: &private_context_core::DocumentChunk, embedder: &impl Embedder) {
+    if let Some(hash) = &chunk.metadata.model_hash {
+        if hash != embedder.model_hash() {
+            println!(
+                "⚠️  Chunk was embedded with model {} but the active model is {}. Re-ingest to compare like with like.",
+                &hash[..hash.len().min(12)],
+                &embedder.model_hash()[..12]
+            );
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Provenance manifests: a signed record of the guest, model and parameters behind one proof
+//! run, written with the run's outputs into `<output_dir>/proofs/<proof_id>/`.
+use crate::config::PolicyConfig;
+use crate::parser::Chunker;
+use crate::prover::ProverMode;
+use crate::wallet::{proof_id, rand_bytes};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const PUBLIC_VALUES_FILE: &str = "public_values.bin";
+pub const PROOF_FILE: &str = "proof_groth16.bin";
+/// Signs manifests of runs without a holder; created under the data directory on first use
+const PROVER_KEY_FILE: &str = "prover.key";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofKind {
+    Relevance,
+    Chunked,
+    Exclusion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkerSettings {
+    pub chunker: Chunker,
+    pub chunk_size: u32,
+}
+
+/// A file of the bundle, relative to the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleFile {
+    pub name: String,
+    /// Hex SHA-256 of the contents
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofManifest {
+    pub kind: ProofKind,
+    /// See [`proof_id`]
+    pub proof_id: String,
+    /// Hex SHA-256 of the guest ELF
+    pub elf_sha256: String,
+    pub vkey_hash: String,
+    pub sp1_version: String,
+    pub prover: ProverMode,
+    /// Hex SHA-256 of the embedding model weights
+    pub model_hash: String,
+    pub policy: PolicyConfig,
+    /// See [`policy_hash`]
+    pub policy_hash: String,
+    /// Threshold actually proven against, which a flag may have overridden
+    pub threshold: f32,
+    pub uncertainty: f32,
+    /// `None` when the proven chunk records no chunking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunker: Option<ChunkerSettings>,
+    /// Filled in by [`ProofBundle::seal`]
+    #[serde(default)]
+    pub files: Vec<BundleFile>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// `manifest.json`: the manifest and an ed25519 signature over its JSON encoding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedManifest {
+    pub manifest: ProofManifest,
+    /// Hex ed25519 public key; the holder's key, or the prover key when no holder was active
+    pub signer: String,
+    /// Hex signature
+    pub signature: String,
+}
+
+impl ProofManifest {
+    pub fn sign(self, key: &SigningKey) -> Result<SignedManifest> {
+        let signature = key.sign(&serde_json::to_vec(&self)?);
+        Ok(SignedManifest {
+            manifest: self,
+            signer: hex::encode(key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+}
+
+impl SignedManifest {
+    /// Loads `path`, or the manifest inside it when it is a bundle directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let path = if path.is_dir() {
+            path.join(MANIFEST_FILE)
+        } else {
+            path.to_path_buf()
+        };
+        serde_json::from_str(&fs_err::read_to_string(&path)?)
+            .with_context(|| format!("Malformed manifest {}", path.display()))
+    }
+
+    /// Checks the signature, then every listed file in `bundle_dir` against its hash and the
+    /// public values against the proof id.
+    pub fn verify(&self, bundle_dir: &Path) -> Result<()> {
+        let signer: [u8; 32] = hex::decode(&self.signer)?
+            .try_into()
+            .map_err(|_| anyhow!("Signer is not an ed25519 public key"))?;
+        let signature: [u8; 64] = hex::decode(&self.signature)?
+            .try_into()
+            .map_err(|_| anyhow!("Signature is not 64 bytes"))?;
+        VerifyingKey::from_bytes(&signer)?
+            .verify(
+                &serde_json::to_vec(&self.manifest)?,
+                &Signature::from_bytes(&signature),
+            )
+            .map_err(|_| anyhow!("Manifest signature does not match its contents"))?;
+
+        if !self
+            .manifest
+            .files
+            .iter()
+            .any(|file| file.name == PUBLIC_VALUES_FILE)
+        {
+            bail!("Manifest lists no {}", PUBLIC_VALUES_FILE);
+        }
+        for file in &self.manifest.files {
+            let path = bundle_dir.join(&file.name);
+            if sha256_file(&path)? != file.sha256 {
+                bail!("{} does not match the manifest", path.display());
+            }
+        }
+        let public_values = fs_err::read(bundle_dir.join(PUBLIC_VALUES_FILE))?;
+        if proof_id(&public_values) != self.manifest.proof_id {
+            bail!("Public values belong to a different proof");
+        }
+        Ok(())
+    }
+}
+
+/// The outputs of one proof run, as they are written.
+pub struct ProofBundle {
+    dir: PathBuf,
+    files: Vec<BundleFile>,
+}
+
+impl ProofBundle {
+    /// Writes the public values and proof into `<output_dir>/proofs/<proof_id>/`.
+    pub fn write(output_dir: &Path, public_values: &[u8], proof: Option<&[u8]>) -> Result<Self> {
+        let dir = output_dir.join("proofs").join(proof_id(public_values));
+        fs_err::create_dir_all(&dir)?;
+        let mut bundle = Self {
+            dir,
+            files: Vec::new(),
+        };
+        bundle.add(PUBLIC_VALUES_FILE, public_values)?;
+        if let Some(proof) = proof {
+            bundle.add(PROOF_FILE, proof)?;
+        }
+        Ok(bundle)
+    }
+
+    fn add(&mut self, name: &str, contents: &[u8]) -> Result<()> {
+        fs_err::write(self.dir.join(name), contents)?;
+        self.files.push(BundleFile {
+            name: name.to_string(),
+            sha256: hex::encode(Sha256::digest(contents)),
+        });
+        Ok(())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lists the bundle's files in `manifest`, signs it and writes `manifest.json`.
+    pub fn seal(self, mut manifest: ProofManifest, key: &SigningKey) -> Result<PathBuf> {
+        manifest.files = self.files;
+        let signed = manifest.sign(key)?;
+        let path = self.dir.join(MANIFEST_FILE);
+        fs_err::write(&path, serde_json::to_string_pretty(&signed)?)?;
+        Ok(path)
+    }
+}
+
+pub fn sha256_file(path: &Path) -> Result<String> {
+    Ok(hex::encode(Sha256::digest(fs_err::read(path)?)))
+}
+
+/// Hex SHA-256 of the policy's JSON encoding.
+pub fn policy_hash(policy: &PolicyConfig) -> Result<String> {
+    Ok(hex::encode(Sha256::digest(serde_json::to_vec(policy)?)))
+}
+
+/// Key that signs manifests when no holder is active, created on first use.
+pub fn prover_key(data_dir: &Path) -> Result<SigningKey> {
+    let path = data_dir.join(PROVER_KEY_FILE);
+    if path.exists() {
+        let seed: [u8; 32] = hex::decode(fs_err::read_to_string(&path)?.trim())?
+            .try_into()
+            .map_err(|_| anyhow!("Corrupt prover key {}", path.display()))?;
+        return Ok(SigningKey::from_bytes(&seed));
+    }
+    fs_err::create_dir_all(data_dir)?;
+    let key = SigningKey::from_bytes(&rand_bytes());
+    fs_err::write(&path, hex::encode(key.to_bytes()))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn manifest() -> ProofManifest {
+        let policy = Config::default().policy;
+        ProofManifest {
+            kind: ProofKind::Chunked,
+            proof_id: String::new(),
+            elf_sha256: "11".repeat(32),
+            vkey_hash: "0x00ab".to_string(),
+            sp1_version: "v3.0.0".to_string(),
+            prover: ProverMode::Mock,
+            model_hash: "22".repeat(32),
+            policy_hash: policy_hash(&policy).unwrap(),
+            threshold: policy.threshold as f32,
+            uncertainty: policy.uncertainty as f32,
+            policy,
+            chunker: Some(ChunkerSettings {
+                chunker: Chunker::Verifiable,
+                chunk_size: 500,
+            }),
+            files: Vec::new(),
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn sealed_bundle_verifies_until_tampered() {
+        let dir = tempfile::tempdir().unwrap();
+        let public_values = [7u8; 69];
+        let bundle = ProofBundle::write(dir.path(), &public_values, Some(b"groth16")).unwrap();
+        let bundle_dir = bundle.dir().to_path_buf();
+        let key = prover_key(dir.path()).unwrap();
+        assert_eq!(prover_key(dir.path()).unwrap().to_bytes(), key.to_bytes());
+
+        let manifest = ProofManifest {
+            proof_id: proof_id(&public_values),
+            ..manifest()
+        };
+        bundle.seal(manifest, &key).unwrap();
+
+        let signed = SignedManifest::load(&bundle_dir).unwrap();
+        assert_eq!(signed.manifest.files.len(), 2);
+        signed.verify(&bundle_dir).unwrap();
+
+        let mut forged = signed.clone();
+        forged.manifest.threshold = 0.1;
+        assert!(forged.verify(&bundle_dir).is_err());
+
+        std::fs::write(bundle_dir.join(PROOF_FILE), b"other").unwrap();
+        assert!(signed.verify(&bundle_dir).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
+use private_context_core::chunking::{chunk_ranges, ChunkSpan};
 use private_context_core::{ChunkMetadata, DocumentChunk};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 use text_splitter::TextSplitter;
@@ -8,6 +10,34 @@ pub trait DocumentParser {
     fn parse(&self, path: &Path) -> Result<Vec<DocumentChunk>>;
 }
 
+impl<P: DocumentParser + ?Sized> DocumentParser for Box<P> {
+    fn parse(&self, path: &Path) -> Result<Vec<DocumentChunk>> {
+        (**self).parse(path)
+    }
+}
+
+/// Chunk length in characters the CLI ingests and screens with.
+pub const CHUNK_SIZE: usize = 500;
+
+/// How documents are cut into chunks at ingest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Chunker {
+    /// `text-splitter` boundaries; chunks cannot be re-derived inside a proof
+    Recursive,
+    /// Deterministic boundaries the chunked guest re-derives from the full document
+    Verifiable,
+}
+
+impl Chunker {
+    pub fn parser(self, chunk_size: usize) -> Box<dyn DocumentParser + Sync> {
+        match self {
+            Chunker::Recursive => Box::new(RecursiveCharacterParser::new(chunk_size)),
+            Chunker::Verifiable => Box::new(VerifiableParser::new(chunk_size as u32)),
+        }
+    }
+}
+
 pub struct RecursiveCharacterParser {
     chunk_size: usize,
 }
@@ -35,6 +65,54 @@ impl DocumentParser for RecursiveCharacterParser {
                     source: source.clone(),
                     proof_id: None,
                     provider: None,
+                    model_hash: None,
+                    claim: None,
+                    claim_binding: None,
+                    commitment: None,
+                    span: None,
+                },
+            })
+            .collect();
+
+        Ok(chunks)
+    }
+}
+
+/// Chunks with [`chunk_ranges`] and records each chunk's span, so a proof can cut the same
+/// chunk from the source document inside the circuit.
+pub struct VerifiableParser {
+    chunk_size: u32,
+}
+
+impl VerifiableParser {
+    pub fn new(chunk_size: u32) -> Self {
+        Self { chunk_size }
+    }
+}
+
+impl DocumentParser for VerifiableParser {
+    fn parse(&self, path: &Path) -> Result<Vec<DocumentChunk>> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file at {:?}", path))?;
+        let source = path.to_string_lossy().to_string();
+
+        let chunks = chunk_ranges(&content, self.chunk_size as usize)
+            .into_iter()
+            .enumerate()
+            .map(|(index, range)| DocumentChunk {
+                content: content[range].to_string(),
+                metadata: ChunkMetadata {
+                    source: source.clone(),
+                    proof_id: None,
+                    provider: None,
+                    model_hash: None,
+                    claim: None,
+                    claim_binding: None,
+                    commitment: None,
+                    span: Some(ChunkSpan {
+                        index: index as u32,
+                        chunk_size: self.chunk_size,
+                    }),
                 },
             })
             .collect();
@@ -0,0 +1,403 @@
+use crate::db::DynStore;
+use crate::embedder::Embedder;
+use crate::lexical::LexicalIndex;
+use crate::parser::DocumentParser;
+use anyhow::{bail, Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use private_context_core::claim::ReclaimClaim;
+use private_context_core::disclosure;
+use private_context_core::DocumentChunk;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sled::{Db, Tree};
+use std::path::{Path, PathBuf};
+
+/// Expands a file, directory (recursively) or glob pattern into a sorted list of files.
+pub fn collect_inputs(input: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(input);
+    let pattern = if path.is_dir() {
+        path.join("**").join("*").to_string_lossy().into_owned()
+    } else {
+        input.to_string()
+    };
+
+    let mut files: Vec<PathBuf> = glob::glob(&pattern)
+        .with_context(|| format!("Invalid input pattern `{}`", input))?
+        .filter_map(Result::ok)
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        bail!("No files match `{}`", input);
+    }
+    Ok(files)
+}
+
+/// Verifies a Reclaim proof file and decodes its claim.
+pub async fn load_claim(proof_path: &str) -> Result<ReclaimClaim> {
+    let proof_json = std::fs::read_to_string(proof_path).context("Failed to read proof file")?;
+    let proof = private_context_core::verifier::Proof::new(&proof_json);
+
+    if !private_context_core::verifier::verify_proof(&proof).await? {
+        bail!("❌ Proof verification failed!");
+    }
+    ReclaimClaim::from_proof_json(&proof_json)
+}
+
+/// Stamps model and provenance metadata onto a chunk of the file hashing to `document_hash`.
+pub fn annotate_chunk(
+    chunk: &mut DocumentChunk,
+    document_hash: &[u8; 32],
+    model_hash: &str,
+    claim: Option<&ReclaimClaim>,
+) -> Result<()> {
+    chunk.metadata.model_hash = Some(model_hash.to_string());
+    if let Some(claim) = claim {
+        chunk.metadata.proof_id = Some(claim.identifier.clone());
+        chunk.metadata.provider = Some(claim.provider.clone());
+        chunk.metadata.claim_binding = Some(hex::encode(claim.bind_to_document(document_hash)?));
+        chunk.metadata.claim = Some(claim.clone());
+    }
+    Ok(())
+}
+
+/// Per-file progress, so an interrupted run resumes at the next unembedded batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FileCheckpoint {
+    content_hash: String,
+    chunks_done: usize,
+    complete: bool,
+}
+
+/// Ingestion checkpoints, kept in their own Sled DB next to the store.
+pub struct Checkpoints {
+    db: Db,
+    files: Tree,
+}
+
+impl Checkpoints {
+    pub fn open(data_dir: &str) -> Result<Self> {
+        let db = sled::open(Path::new(data_dir).join("ingest_checkpoints"))
+            .context("Failed to open ingestion checkpoints")?;
+        let files = db.open_tree("files")?;
+        Ok(Self { db, files })
+    }
+
+    fn get(&self, path: &Path) -> Result<Option<FileCheckpoint>> {
+        Ok(match self.files.get(key(path))? {
+            Some(bytes) => Some(serde_json::from_slice(&bytes)?),
+            None => None,
+        })
+    }
+
+    fn record(&self, path: &Path, checkpoint: &FileCheckpoint) -> Result<()> {
+        self.files
+            .insert(key(path), serde_json::to_vec(checkpoint)?)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Forgets all progress so the next run re-ingests every file.
+    pub fn clear(&self) -> Result<()> {
+        self.files.clear()?;
+        self.db.flush()?;
+        Ok(())
+    }
+}
+
+fn key(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().as_bytes().to_vec()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct IngestOptions {
+    /// Chunks per embedding call; bounds peak memory per worker
+    pub batch_size: usize,
+    /// Worker threads for parsing and embedding; 0 uses one per core
+    pub threads: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct IngestReport {
+    pub files_ingested: usize,
+    pub files_skipped: usize,
+    pub chunks: usize,
+    pub failures: Vec<(PathBuf, String)>,
+}
+
+enum ParsedFile {
+    /// Already fully ingested with identical content
+    UpToDate,
+    Pending {
+        document_hash: [u8; 32],
+        chunks: Vec<DocumentChunk>,
+        resume_from: usize,
+    },
+}
+
+/// Drives parse → embed → store for many files with bounded memory.
+pub struct Ingestor<'a, P, E: ?Sized> {
+    pub parser: &'a P,
+    pub embedder: &'a E,
+    pub store: &'a mut DynStore,
+    pub lexical: &'a LexicalIndex,
+    pub checkpoints: &'a Checkpoints,
+    pub options: IngestOptions,
+}
+
+impl<'a, P, E> Ingestor<'a, P, E>
+where
+    P: DocumentParser + Sync,
+    E: Embedder + Sync + ?Sized,
+{
+    /// Ingests `paths`, calling `annotate` with each chunk and the SHA-256 of its source file
+    /// before the chunk is stored. A file that fails is reported and skipped; the rest of the
+    /// run continues.
+    pub fn run<F>(&mut self, paths: &[PathBuf], annotate: F) -> Result<IngestReport>
+    where
+        F: Fn(&mut DocumentChunk, &[u8; 32]) -> Result<()>,
+    {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.options.threads)
+            .build()?;
+
+        let progress = ProgressBar::new(paths.len() as u64);
+        progress.set_style(ProgressStyle::with_template(
+            "{spinner} [{bar:40}] {pos}/{len} files {wide_msg}",
+        )?);
+
+        let mut report = IngestReport::default();
+        let parser = self.parser;
+        let checkpoints = self.checkpoints;
+
+        // Parse one file per worker at a time so only a handful of documents are in memory
+        for group in paths.chunks(pool.current_num_threads()) {
+            let parsed: Vec<(&PathBuf, Result<ParsedFile>)> = pool.install(|| {
+                group
+                    .par_iter()
+                    .map(|path| (path, parse_file(parser, checkpoints, path)))
+                    .collect()
+            });
+
+            for (path, parsed) in parsed {
+                progress.set_message(path.display().to_string());
+
+                let outcome = parsed.and_then(|file| match file {
+                    ParsedFile::UpToDate => Ok(None),
+                    ParsedFile::Pending {
+                        document_hash,
+                        chunks,
+                        resume_from,
+                    } => self
+                        .ingest_file(&pool, path, document_hash, chunks, resume_from, &annotate)
+                        .map(Some),
+                });
+
+                match outcome {
+                    Ok(None) => report.files_skipped += 1,
+                    Ok(Some(count)) => {
+                        report.files_ingested += 1;
+                        report.chunks += count;
+                    }
+                    Err(e) => {
+                        progress.println(format!("❌ {}: {:#}", path.display(), e));
+                        report.failures.push((path.clone(), format!("{:#}", e)));
+                    }
+                }
+                progress.inc(1);
+            }
+        }
+
+        progress.finish_and_clear();
+        Ok(report)
+    }
+
+    fn ingest_file<F>(
+        &mut self,
+        pool: &ThreadPool,
+        path: &Path,
+        document_hash: [u8; 32],
+        chunks: Vec<DocumentChunk>,
+        resume_from: usize,
+        annotate: &F,
+    ) -> Result<usize>
+    where
+        F: Fn(&mut DocumentChunk, &[u8; 32]) -> Result<()>,
+    {
+        let content_hash = hex::encode(document_hash);
+        let batch_size = self.options.batch_size.max(1);
+        let window = batch_size * pool.current_num_threads();
+        let embedder = self.embedder;
+        let mut done = resume_from;
+
+        for slice in chunks[resume_from..].chunks(window) {
+            let embeddings: Vec<Vec<Vec<f32>>> = pool.install(|| {
+                slice
+                    .par_chunks(batch_size)
+                    .map(|batch| {
+                        let texts: Vec<&str> = batch.iter().map(|c| c.content.as_str()).collect();
+                        embedder.embed_passages(&texts)
+                    })
+                    .collect::<Result<_>>()
+            })?;
+
+            let mut batch = slice.to_vec();
+            for chunk in batch.iter_mut() {
+                annotate(chunk, &document_hash)?;
+            }
+
+            self.lexical.add_chunks(&batch)?;
+            self.store
+                .add_chunks(batch, embeddings.into_iter().flatten().collect())?;
+
+            done += slice.len();
+            self.checkpoints.record(
+                path,
+                &FileCheckpoint {
+                    content_hash: content_hash.clone(),
+                    chunks_done: done,
+                    complete: false,
+                },
+            )?;
+        }
+
+        self.checkpoints.record(
+            path,
+            &FileCheckpoint {
+                content_hash,
+                chunks_done: done,
+                complete: true,
+            },
+        )?;
+
+        Ok(done - resume_from)
+    }
+}
+
+fn parse_file<P: DocumentParser>(
+    parser: &P,
+    checkpoints: &Checkpoints,
+    path: &Path,
+) -> Result<ParsedFile> {
+    let bytes = fs_err::read(path)?;
+    let document_hash: [u8; 32] = Sha256::digest(&bytes).into();
+    let content_hash = hex::encode(document_hash);
+
+    // A changed file starts over; its earlier chunks stay in the store
+    let resume_from = match checkpoints.get(path)? {
+        Some(cp) if cp.content_hash == content_hash && cp.complete => {
+            return Ok(ParsedFile::UpToDate)
+        }
+        Some(cp) if cp.content_hash == content_hash => cp.chunks_done,
+        _ => 0,
+    };
+
+    let mut chunks = parser.parse(path)?;
+    let contents: Vec<&str> = chunks.iter().map(|c| c.content.as_str()).collect();
+    let commitments = disclosure::commit_document(&bytes, &contents);
+    for (chunk, commitment) in chunks.iter_mut().zip(commitments) {
+        chunk.metadata.commitment = Some(commitment);
+    }
+
+    let resume_from = resume_from.min(chunks.len());
+    Ok(ParsedFile::Pending {
+        document_hash,
+        chunks,
+        resume_from,
+    })
+}
+
+#[cfg(all(test, feature = "hnsw"))]
+mod tests {
+    use super::*;
+    use crate::db::LocalStore;
+    use crate::parser::RecursiveCharacterParser;
+
+    /// Deterministic stand-in so the pipeline can run without model weights
+    struct LengthEmbedder;
+
+    impl Embedder for LengthEmbedder {
+        fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+            Ok(vec![text.len() as f32, 1.0])
+        }
+
+        fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            texts.iter().map(|t| self.embed_query(t)).collect()
+        }
+
+        fn dimension(&self) -> usize {
+            2
+        }
+
+        fn model_hash(&self) -> &str {
+            "length"
+        }
+    }
+
+    fn run(data_dir: &Path, input: &Path) -> IngestReport {
+        let data_dir = data_dir.to_string_lossy();
+        let mut store: DynStore = Box::new(LocalStore::new(&data_dir).unwrap());
+        let lexical = LexicalIndex::open(&data_dir).unwrap();
+        let checkpoints = Checkpoints::open(&data_dir).unwrap();
+
+        let mut ingestor = Ingestor {
+            parser: &RecursiveCharacterParser::new(20),
+            embedder: &LengthEmbedder,
+            store: &mut store,
+            lexical: &lexical,
+            checkpoints: &checkpoints,
+            options: IngestOptions {
+                batch_size: 2,
+                threads: 2,
+            },
+        };
+        let paths = collect_inputs(&input.to_string_lossy()).unwrap();
+        ingestor.run(&paths, |_, _| Ok(())).unwrap()
+    }
+
+    #[test]
+    fn failed_file_does_not_abort_run() {
+        let docs = tempfile::tempdir().unwrap();
+        let data = tempfile::tempdir().unwrap();
+        std::fs::write(docs.path().join("a.txt"), "first document ".repeat(10)).unwrap();
+        std::fs::write(docs.path().join("b.txt"), [0xff, 0xfe, 0xfd]).unwrap();
+        std::fs::create_dir(docs.path().join("nested")).unwrap();
+        std::fs::write(docs.path().join("nested/c.txt"), "second document").unwrap();
+
+        let report = run(data.path(), docs.path());
+        assert_eq!(report.files_ingested, 2);
+        assert_eq!(report.failures.len(), 1);
+        assert!(report.failures[0].0.ends_with("b.txt"));
+        assert!(report.chunks > 2);
+    }
+
+    #[test]
+    fn rerun_skips_completed_files() {
+        let docs = tempfile::tempdir().unwrap();
+        let data = tempfile::tempdir().unwrap();
+        std::fs::write(docs.path().join("a.txt"), "unchanged").unwrap();
+        std::fs::write(docs.path().join("b.txt"), "before").unwrap();
+
+        assert_eq!(run(data.path(), docs.path()).files_ingested, 2);
+
+        std::fs::write(docs.path().join("b.txt"), "after").unwrap();
+        let report = run(data.path(), docs.path());
+        assert_eq!(report.files_skipped, 1);
+        assert_eq!(report.files_ingested, 1);
+    }
+
+    #[test]
+    fn glob_selects_matching_files() {
+        let docs = tempfile::tempdir().unwrap();
+        std::fs::write(docs.path().join("a.txt"), "x").unwrap();
+        std::fs::write(docs.path().join("b.md"), "x").unwrap();
+
+        let pattern = docs.path().join("*.txt");
+        let files = collect_inputs(&pattern.to_string_lossy()).unwrap();
+        assert_eq!(files, vec![docs.path().join("a.txt")]);
+        assert!(collect_inputs(&docs.path().join("*.pdf").to_string_lossy()).is_err());
+    }
+}
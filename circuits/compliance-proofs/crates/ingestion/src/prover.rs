@@ -0,0 +1,516 @@
+use anyhow::{anyhow, bail, Context, Result};
+use private_context_core::chunking::{chunk_at, ChunkSpan};
+use private_context_core::exclusion::validate_screen;
+use private_context_core::math::cosine_similarity_f32;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, ProverClient, SP1ProvingKey, SP1Stdin, SP1VerifyingKey};
+use std::path::Path;
+
+/// Guest ELF location relative to the compliance-proofs workspace root.
+pub const DEFAULT_ELF_PATH: &str = "crates/circuits/elf/riscv32im-succinct-zkvm-elf";
+
+/// Guest that cuts the chunk from the full document itself (`crates/circuits/src/bin/chunked.rs`).
+pub const CHUNKED_ELF_PATH: &str = "crates/circuits/elf/chunked-relevance-elf";
+
+/// Guest proving no chunk matches a watch-list (`crates/circuits/src/bin/exclusion.rs`).
+pub const EXCLUSION_ELF_PATH: &str = "crates/circuits/elf/exclusion-elf";
+
+/// SP1 circuit version the SDK proves with, recorded in proof manifests.
+pub fn sp1_version() -> &'static str {
+    sp1_sdk::SP1_CIRCUIT_VERSION
+}
+
+/// How proofs are produced, mirroring SP1's `SP1_PROVER` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProverMode {
+    /// Execute the guest on the host CPU; no Groth16 proof
+    Mock,
+    /// Full Groth16 proof on this machine
+    Local,
+    /// Full Groth16 proof via the Succinct prover network
+    Network,
+}
+
+impl ProverMode {
+    /// Reads `SP1_PROVER`, defaulting to local proving.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("SP1_PROVER") {
+            Ok(mode) => match mode.to_lowercase().as_str() {
+                "mock" => Ok(ProverMode::Mock),
+                "local" => Ok(ProverMode::Local),
+                "network" => Ok(ProverMode::Network),
+                other => bail!("Unknown SP1_PROVER `{}`", other),
+            },
+            Err(_) => Ok(ProverMode::Local),
+        }
+    }
+}
+
+/// Private and public inputs of the relevance circuit, in the order the guest reads them.
+#[derive(Clone)]
+pub struct RelevanceInputs {
+    pub query_vector: Vec<f32>,
+    pub chunk_vector: Vec<f32>,
+    pub threshold: f32,
+    pub document_hash: [u8; 32],
+    /// All zeros when the chunk has no Reclaim claim
+    pub claim_identifier: [u8; 32],
+}
+
+impl RelevanceInputs {
+    /// Similarity as the host computes it in f32.
+    pub fn host_similarity(&self) -> f32 {
+        cosine_similarity_f32(&self.query_vector, &self.chunk_vector)
+    }
+
+    /// Returns the host similarity, or fails when it lies within `band` of the threshold. The
+    /// guest decides in I32F32, so inside the band its `is_relevant` may not match the host's.
+    pub fn ensure_decidable(&self, band: f32) -> Result<f32> {
+        let similarity = self.host_similarity();
+        if (similarity - self.threshold).abs() <= band {
+            bail!(
+                "Similarity {} is within {} of the threshold {}; the circuit's fixed-point result could go either way",
+                similarity,
+                band,
+                self.threshold
+            );
+        }
+        Ok(similarity)
+    }
+}
+
+/// Inputs of the chunked guest, which re-derives the chunk from `document` so the proven chunk
+/// is provably part of the document whose hash is committed.
+pub struct ChunkedRelevanceInputs {
+    /// `document_hash` is SHA-256 of `document`, as the guest computes it
+    pub relevance: RelevanceInputs,
+    /// Full UTF-8 source document
+    pub document: Vec<u8>,
+    pub span: ChunkSpan,
+}
+
+impl ChunkedRelevanceInputs {
+    pub fn new(document: Vec<u8>, span: ChunkSpan, mut relevance: RelevanceInputs) -> Self {
+        relevance.document_hash = Sha256::digest(&document).into();
+        Self {
+            relevance,
+            document,
+            span,
+        }
+    }
+
+    /// The chunk the guest will cut, for checking against the embedded chunk before proving.
+    pub fn chunk(&self) -> Result<&str> {
+        let text = std::str::from_utf8(&self.document).context("Document is not UTF-8")?;
+        chunk_at(
+            text,
+            self.span.chunk_size as usize,
+            self.span.index as usize,
+        )
+        .ok_or_else(|| anyhow!("Document has no chunk {}", self.span.index))
+    }
+}
+
+/// Inputs of the exclusion guest, in the order it reads them.
+pub struct ExclusionInputs {
+    /// Full UTF-8 screened document; the guest commits its SHA-256
+    pub document: Vec<u8>,
+    /// Chunk size the document was cut with by the verifiable chunker
+    pub chunk_size: u32,
+    /// Every chunk vector of the screened document, in chunk order
+    /// Entity vectors of the watch-list
+    pub watchlist: Vec<Vec<f32>>,
+    pub threshold: f32,
+}
+
+impl ExclusionInputs {
+    /// Fails as the guest would: unless there is one vector per chunk of the document and
+    /// neither set is empty.
+    pub fn validate(&self) -> Result<()> {
+        let text = std::str::from_utf8(&self.document).context("Document is not UTF-8")?;
+        validate_screen(
+            text,
+            self.chunk_size as usize,
+            &self.chunk_vectors,
+            &self.watchlist,
+        )
+        .map_err(|e| anyhow!("Invalid screen: {}", e))
+    }
+
+    /// Highest host f32 similarity over all chunk/entity pairs; `None` when either set is empty.
+    pub fn max_host_similarity(&self) -> Option<f32> {
+        self.chunk_vectors
+            .iter()
+            .flat_map(|chunk| {
+                self.watchlist
+                    .iter()
+                    .map(move |entry| cosine_similarity_f32(chunk, entry))
+            })
+            .reduce(f32::max)
+    }
+
+    /// Returns the highest host similarity, or fails when it lies within `band` of the
+    /// threshold. The verdict hinges on that pair alone, so only it needs to be decidable.
+    pub fn ensure_decidable(&self, band: f32) -> Result<Option<f32>> {
+        let max = self.max_host_similarity();
+        if let Some(similarity) = max {
+            if (similarity - self.threshold).abs() <= band {
+                bail!(
+                    "Closest match {} is within {} of the threshold {}; the circuit's fixed-point result could go either way",
+                    similarity,
+                    band,
+                    self.threshold
+                );
+            }
+        }
+        Ok(max)
+    }
+}
+
+/// Values the exclusion guest commits, decoded from the public values buffer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExclusionOutputs {
+    /// SHA-256 of the screened document
+    pub document_hash: [u8; 32],
+    /// Merkle root over the document's chunk vectors
+    pub chunk_root: [u8; 32],
+    /// Merkle root over the watch-list's entity vectors
+    pub list_hash: [u8; 32],
+    pub threshold: f32,
+    /// No chunk reached the threshold against any entity
+    pub is_clear: bool,
+}
+
+impl ExclusionOutputs {
+    /// Decodes `document_hash (32) | chunk_root (32) | list_hash (32) | threshold (4, LE) |
+    /// is_clear (1)`.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 101 {
+            bail!("Public values are {} bytes; expected 101", bytes.len());
+        }
+        let is_clear = match bytes[100] {
+            0 => false,
+            1 => true,
+            other => bail!("Invalid is_clear byte {}", other),
+        };
+        Ok(Self {
+            document_hash: bytes[0..32].try_into()?,
+            chunk_root: bytes[32..64].try_into()?,
+            list_hash: bytes[64..96].try_into()?,
+            threshold: f32::from_le_bytes(bytes[96..100].try_into()?),
+            is_clear,
+        })
+    }
+}
+
+/// Chunk the chunked guest cut from the document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedChunk {
+    pub hash: [u8; 32],
+    pub span: ChunkSpan,
+}
+
+/// Values the guest commits, decoded from the public values buffer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicOutputs {
+    pub document_hash: [u8; 32],
+    pub is_relevant: bool,
+    pub similarity: f32,
+    /// `None` when the guest predates claim commitments
+    pub claim_identifier: Option<[u8; 32]>,
+    /// `None` unless the chunked guest produced the values
+    pub chunk: Option<CommittedChunk>,
+}
+
+impl PublicOutputs {
+    /// Decodes `document_hash (32) | is_relevant (1) | similarity (4, LE) | claim_identifier (32)`,
+    /// then `chunk_hash (32) | chunk_index (4, LE) | chunk_size (4, LE)` from the chunked guest.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 37 {
+            bail!("Public values are {} bytes; expected at least 37", bytes.len());
+        }
+        let document_hash: [u8; 32] = bytes[0..32].try_into()?;
+        let is_relevant = match bytes[32] {
+            0 => false,
+            1 => true,
+            b => bail!("Invalid is_relevant byte {}", b),
+        };
+        let similarity = f32::from_le_bytes(bytes[33..37].try_into()?);
+        let claim_identifier = match bytes.get(37..69) {
+            Some(claim) => Some(claim.try_into()?),
+            None => None,
+        };
+        let chunk = match bytes.get(69..109) {
+            Some(chunk) => Some(CommittedChunk {
+                hash: chunk[0..32].try_into()?,
+                span: ChunkSpan {
+                    index: u32::from_le_bytes(chunk[32..36].try_into()?),
+                    chunk_size: u32::from_le_bytes(chunk[36..40].try_into()?),
+                },
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            document_hash,
+            is_relevant,
+            similarity,
+            claim_identifier,
+            chunk,
+        })
+    }
+}
+
+pub struct RelevanceProof {
+    pub outputs: PublicOutputs,
+    pub public_values: Vec<u8>,
+    /// Groth16 proof bytes; `None` in mock mode
+    pub proof: Option<Vec<u8>>,
+}
+
+pub struct ExclusionProof {
+    pub outputs: ExclusionOutputs,
+    pub public_values: Vec<u8>,
+    /// Groth16 proof bytes; `None` in mock mode
+    pub proof: Option<Vec<u8>>,
+}
+
+/// Holds the SP1 client and keys so repeated proofs skip setup.
+pub struct RelevanceProver {
+    client: ProverClient,
+    pk: SP1ProvingKey,
+    vk: SP1VerifyingKey,
+    mode: ProverMode,
+}
+
+impl RelevanceProver {
+    pub fn new(elf_path: &Path, mode: ProverMode) -> Result<Self> {
+        let elf = std::fs::read(elf_path)
+            .with_context(|| format!("Guest ELF not found at {}", elf_path.display()))?;
+
+        let client = match mode {
+            ProverMode::Mock => ProverClient::mock(),
+            ProverMode::Local => ProverClient::local(),
+            ProverMode::Network => ProverClient::network(),
+        };
+        let (pk, vk) = client.setup(&elf);
+
+        Ok(Self {
+            client,
+            pk,
+            vk,
+            mode,
+        })
+    }
+
+    pub fn mode(&self) -> ProverMode {
+        self.mode
+    }
+
+    pub fn vkey_hash(&self) -> String {
+        self.vk.bytes32()
+    }
+
+    pub fn prove(&self, inputs: &RelevanceInputs) -> Result<RelevanceProof> {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&inputs.query_vector);
+        stdin.write(&inputs.chunk_vector);
+        stdin.write(&inputs.threshold);
+        stdin.write(&inputs.document_hash);
+        stdin.write(&inputs.claim_identifier);
+        self.run_relevance(stdin)
+    }
+
+    /// Proves with the chunked guest; the prover must be set up with [`CHUNKED_ELF_PATH`].
+    pub fn prove_chunked(&self, inputs: &ChunkedRelevanceInputs) -> Result<RelevanceProof> {
+        let relevance = &inputs.relevance;
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&inputs.document);
+        stdin.write(&inputs.span.chunk_size);
+        stdin.write(&inputs.span.index);
+        stdin.write(&relevance.query_vector);
+        stdin.write(&relevance.chunk_vector);
+        stdin.write(&relevance.threshold);
+        stdin.write(&relevance.claim_identifier);
+
+        let proof = self.run_relevance(stdin)?;
+        if proof.outputs.chunk.is_none() {
+            bail!("Guest committed no chunk; prove_chunked needs the chunked guest ELF");
+        }
+        Ok(proof)
+    }
+
+    /// Proves with the exclusion guest; the prover must be set up with [`EXCLUSION_ELF_PATH`].
+    pub fn prove_exclusion(&self, inputs: &ExclusionInputs) -> Result<ExclusionProof> {
+        inputs.validate()?;
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&inputs.document);
+        stdin.write(&inputs.chunk_size);
+        stdin.write(&inputs.chunk_vectors);
+        stdin.write(&inputs.watchlist);
+        stdin.write(&inputs.threshold);
+
+        let (public_values, proof) = self.run(stdin)?;
+        Ok(ExclusionProof {
+            outputs: ExclusionOutputs::decode(&public_values)
+                .context("prove_exclusion needs the exclusion guest ELF")?,
+            public_values,
+            proof,
+        })
+    }
+
+    fn run_relevance(&self, stdin: SP1Stdin) -> Result<RelevanceProof> {
+        let (public_values, proof) = self.run(stdin)?;
+        Ok(RelevanceProof {
+            outputs: PublicOutputs::decode(&public_values)?,
+            public_values,
+            proof,
+        })
+    }
+
+    /// Executes or proves the guest, returning its public values and the Groth16 proof bytes.
+    fn run(&self, stdin: SP1Stdin) -> Result<(Vec<u8>, Option<Vec<u8>>)> {
+        let (public_values, proof) = match self.mode {
+            ProverMode::Mock => {
+                let (public_values, _report) = self
+                    .client
+                    .execute(&self.pk.elf, stdin)
+                    .run()
+                    .map_err(|e| anyhow!("Guest execution failed: {}", e))?;
+                (public_values.to_vec(), None)
+            }
+            ProverMode::Local | ProverMode::Network => {
+                let proof = self
+                    .client
+                    .prove(&self.pk, stdin)
+                    .groth16()
+                    .run()
+                    .map_err(|e| anyhow!("Proof generation failed: {}", e))?;
+                (proof.public_values.to_vec(), Some(proof.bytes()))
+            }
+        };
+        Ok((public_values, proof))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_with_and_without_claim() {
+        let mut bytes = vec![7u8; 32];
+        bytes.push(1);
+        bytes.extend_from_slice(&0.5f32.to_le_bytes());
+
+        let legacy = PublicOutputs::decode(&bytes).unwrap();
+        assert!(legacy.is_relevant);
+        assert_eq!(legacy.similarity, 0.5);
+        assert_eq!(legacy.claim_identifier, None);
+
+        bytes.extend_from_slice(&[9u8; 32]);
+        let current = PublicOutputs::decode(&bytes).unwrap();
+        assert_eq!(current.claim_identifier, Some([9u8; 32]));
+        assert_eq!(current.chunk, None);
+
+        bytes.extend_from_slice(&[3u8; 32]);
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&500u32.to_le_bytes());
+        let chunked = PublicOutputs::decode(&bytes).unwrap();
+        assert_eq!(
+            chunked.chunk,
+            Some(CommittedChunk {
+                hash: [3u8; 32],
+                span: ChunkSpan {
+                    index: 4,
+                    chunk_size: 500
+                },
+            })
+        );
+        assert!(PublicOutputs::decode(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn refuses_scores_inside_uncertainty_band() {
+        let inputs = |threshold| RelevanceInputs {
+            query_vector: vec![0.6, 0.8],
+            chunk_vector: vec![1.0, 0.0],
+            threshold,
+            document_hash: [0u8; 32],
+            claim_identifier: [0u8; 32],
+        };
+
+        // cos = 0.6
+        assert!(inputs(0.6).ensure_decidable(1e-4).is_err());
+        assert!(inputs(0.60005).ensure_decidable(1e-4).is_err());
+        assert!((inputs(0.7).ensure_decidable(1e-4).unwrap() - 0.6).abs() < 1e-6);
+        assert!(inputs(0.5).ensure_decidable(1e-4).is_ok());
+    }
+
+    #[test]
+    fn exclusion_decides_on_closest_pair() {
+        let inputs = |threshold| ExclusionInputs {
+            document: b"First sentence here. Second sentence here.".to_vec(),
+            chunk_size: 25,
+            chunk_vectors: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+            watchlist: vec![vec![0.6, 0.8], vec![-1.0, 0.0]],
+            threshold,
+        };
+
+        // Closest pair is the second chunk against the first entity, cos = 0.8
+        assert!(inputs(0.8).ensure_decidable(1e-4).is_err());
+        assert!((inputs(0.9).ensure_decidable(1e-4).unwrap().unwrap() - 0.8).abs() < 1e-6);
+        let empty = ExclusionInputs {
+            watchlist: vec![],
+            ..inputs(0.9)
+        };
+        assert_eq!(empty.ensure_decidable(1e-4).unwrap(), None);
+    }
+
+    #[test]
+    fn exclusion_covers_every_chunk() {
+        let inputs = ExclusionInputs {
+            document: b"First sentence here. Second sentence here.".to_vec(),
+            chunk_size: 25,
+            chunk_vectors: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+            watchlist: vec![vec![0.6, 0.8]],
+            threshold: 0.9,
+        };
+        assert!(inputs.validate().is_ok());
+
+        let omitted = ExclusionInputs {
+            chunk_vectors: vec![vec![0.0, 1.0]],
+            ..inputs
+        };
+        assert!(omitted.validate().is_err());
+
+        let empty = ExclusionInputs {
+            document: Vec::new(),
+            chunk_vectors: Vec::new(),
+            ..omitted
+        };
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn decodes_exclusion_outputs() {
+        let mut bytes = vec![5u8; 32];
+        bytes.extend_from_slice(&[1u8; 32]);
+        bytes.extend_from_slice(&[2u8; 32]);
+        bytes.extend_from_slice(&0.75f32.to_le_bytes());
+        bytes.push(1);
+
+        let outputs = ExclusionOutputs::decode(&bytes).unwrap();
+        assert_eq!(outputs.document_hash, [5u8; 32]);
+        assert_eq!(outputs.chunk_root, [1u8; 32]);
+        assert_eq!(outputs.list_hash, [2u8; 32]);
+        assert_eq!(outputs.threshold, 0.75);
+        assert!(outputs.is_clear);
+
+        bytes[100] = 2;
+        assert!(ExclusionOutputs::decode(&bytes).is_err());
+        assert!(ExclusionOutputs::decode(&bytes[..100]).is_err());
+    }
+}
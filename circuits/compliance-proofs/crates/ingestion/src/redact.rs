@@ -0,0 +1,330 @@
+use anyhow::{bail, Context, Result};
+use private_context_core::disclosure::DisclosurePackage;
+use private_context_core::DocumentChunk;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Kinds of personal data the redactor hides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PiiCategory {
+    Name,
+    Account,
+    Address,
+    Id,
+}
+
+impl PiiCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiCategory::Name => "name",
+            PiiCategory::Account => "account",
+            PiiCategory::Address => "address",
+            PiiCategory::Id => "id",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "name" => Some(PiiCategory::Name),
+            "account" => Some(PiiCategory::Account),
+            "address" => Some(PiiCategory::Address),
+            "id" => Some(PiiCategory::Id),
+            _ => None,
+        }
+    }
+}
+
+/// Byte range of detected personal data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub category: PiiCategory,
+}
+
+#[derive(Clone)]
+struct Rule {
+    category: PiiCategory,
+    /// Matches are the `v` capture group when present, else the whole match
+    regex: Regex,
+    /// Drops matches without a digit, so labels followed by words are not redacted
+    needs_digit: bool,
+}
+
+fn default_rules() -> &'static [Rule] {
+    static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let rule = |category, pattern: &str, needs_digit| Rule {
+            category,
+            regex: Regex::new(pattern).unwrap(),
+            needs_digit,
+        };
+        vec![
+            rule(
+                PiiCategory::Name,
+                r"(?i:full name|account holder|holder name|investor name|holder|name)\s*[:\-]\s*(?P<v>[A-Z][A-Za-z'\-]+(?: [A-Z][A-Za-z'\-]+){0,3})",
+                false,
+            ),
+            rule(
+                PiiCategory::Name,
+                r"\b(?:Mr|Mrs|Ms|Miss|Dr|Prof)\.?\s+(?P<v>[A-Z][A-Za-z'\-]+(?:\s+[A-Z][A-Za-z'\-]+){0,2})",
+                false,
+            ),
+            rule(
+                PiiCategory::Account,
+                r"\b(?i:account|acct|a/c|iban)(?i:\s*(?:no\.?|number|#))?\s*[:#]?\s*(?P<v>[A-Z0-9][A-Z0-9 \-]{4,32}[0-9])",
+                true,
+            ),
+            rule(
+                PiiCategory::Account,
+                r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,4})?\b",
+                true,
+            ),
+            rule(PiiCategory::Account, r"\b\d{4}(?:[ \-]\d{4}){3}\b", true),
+            rule(PiiCategory::Account, r"\b\d{8,19}\b", true),
+            rule(
+                PiiCategory::Address,
+                r"\b(?i:address|residence)\s*[:\-]\s*(?P<v>[^\n;]+)",
+                false,
+            ),
+            rule(
+                PiiCategory::Address,
+                r"\b\d{1,5}\s+(?:[A-Z][A-Za-z]*\s+){1,4}(?:Street|St|Avenue|Ave|Road|Rd|Boulevard|Blvd|Lane|Ln|Drive|Dr|Court|Ct|Way|Place|Pl|Square|Sq)\b\.?",
+                false,
+            ),
+            rule(
+                PiiCategory::Id,
+                r"\b(?i:passport|national id|id card|tax id|tin|ssn|licen[cs]e)(?i:\s*(?:no\.?|number|#))?\s*[:#]?\s*(?P<v>[A-Z0-9][A-Z0-9\-]{4,16})",
+                true,
+            ),
+            rule(PiiCategory::Id, r"\b\d{3}-\d{2}-\d{4}\b", true),
+            rule(
+                PiiCategory::Id,
+                r"\b[\w.+\-]+@[\w\-]+(?:\.[\w\-]+)+\b",
+                false,
+            ),
+        ]
+    })
+}
+
+/// Finds personal data with built-in patterns plus user-supplied dictionary terms.
+#[derive(Clone)]
+pub struct Redactor {
+    rules: Vec<Rule>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redactor {
+    pub fn new() -> Self {
+        Self {
+            rules: default_rules().to_vec(),
+        }
+    }
+
+    /// Also redacts each of `terms` wherever it appears as a whole word, ignoring case.
+    pub fn with_terms<I, S>(mut self, category: PiiCategory, terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut terms: Vec<String> = terms
+            .into_iter()
+            .map(|t| t.as_ref().trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return self;
+        }
+        // Longest first so "Jane Doe" wins over "Jane"
+        terms.sort_by_key(|t| std::cmp::Reverse(t.len()));
+        let alternation = terms
+            .iter()
+            .map(|t| regex::escape(t))
+            .collect::<Vec<_>>()
+            .join("|");
+        self.rules.push(Rule {
+            category,
+            regex: Regex::new(&format!(r"(?i)\b(?:{})\b", alternation)).unwrap(),
+            needs_digit: false,
+        });
+        self
+    }
+
+    /// Loads a dictionary file with one term per line, optionally prefixed by its
+    /// category (`address: 1 Main Street`). Unprefixed terms are names; `#` starts a comment.
+    pub fn load_dictionary(self, path: &Path) -> Result<Self> {
+        let text = fs_err::read_to_string(path).context("Failed to read redaction dictionary")?;
+        let mut by_category: Vec<(PiiCategory, Vec<String>)> = Vec::new();
+
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (category, term) = match line.split_once(':') {
+                Some((prefix, term)) => match PiiCategory::parse(prefix) {
+                    Some(category) => (category, term.trim()),
+                    None if !prefix.trim().contains(' ') => {
+                        bail!("Unknown category `{}` on line {}", prefix, line_no + 1)
+                    }
+                    None => (PiiCategory::Name, line),
+                },
+                None => (PiiCategory::Name, line),
+            };
+            match by_category.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, terms)) => terms.push(term.to_string()),
+                None => by_category.push((category, vec![term.to_string()])),
+            }
+        }
+
+        Ok(by_category
+            .into_iter()
+            .fold(self, |redactor, (category, terms)| {
+                redactor.with_terms(category, terms)
+            }))
+    }
+
+    /// Detected spans in text order, with overlapping matches merged.
+    pub fn detect(&self, text: &str) -> Vec<Span> {
+        let mut spans: Vec<Span> = Vec::new();
+        for rule in &self.rules {
+            for caps in rule.regex.captures_iter(text) {
+                let m = caps.name("v").unwrap_or_else(|| caps.get(0).unwrap());
+                let value = m.as_str().trim_end();
+                if value.is_empty()
+                    || (rule.needs_digit && !value.bytes().any(|b| b.is_ascii_digit()))
+                {
+                    continue;
+                }
+                spans.push(Span {
+                    start: m.start(),
+                    end: m.start() + value.len(),
+                    category: rule.category,
+                });
+            }
+        }
+
+        spans.sort_by_key(|s| (s.start, std::cmp::Reverse(s.end)));
+        let mut merged: Vec<Span> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start < last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+        merged
+    }
+
+    /// Builds a disclosure package for an ingested chunk, hiding every segment that
+    /// overlaps detected personal data.
+    pub fn disclose(&self, chunk: &DocumentChunk) -> Result<DisclosurePackage> {
+        let Some(commitment) = &chunk.metadata.commitment else {
+            bail!(
+                "Chunk from {} has no document commitment; re-ingest it with --restart",
+                chunk.metadata.source
+            );
+        };
+        let spans = self.detect(&chunk.content);
+
+        Ok(DisclosurePackage::new(
+            &chunk.metadata.source,
+            &chunk.content,
+            commitment,
+            |offset, segment| {
+                let end = offset + segment.trim_end().len();
+                spans
+                    .iter()
+                    .find(|s| s.start < end && offset < s.end)
+                    .map(|s| s.category.as_str().to_string())
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use private_context_core::disclosure::commit_document;
+    use private_context_core::ChunkMetadata;
+
+    const TEXT: &str = "Account Holder: Jane Doe\nAddress: 12 Harbour Road, Leeds\nAccount No: 4400 1234 5678\nPassport X1234567 issued to Mr Smith. Balance of 5000 USD.";
+
+    fn detected(redactor: &Redactor, text: &str) -> Vec<(PiiCategory, String)> {
+        redactor
+            .detect(text)
+            .into_iter()
+            .map(|s| (s.category, text[s.start..s.end].to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn detects_each_category() {
+        let found = detected(&Redactor::new(), TEXT);
+        assert_eq!(
+            found,
+            vec![
+                (PiiCategory::Name, "Jane Doe".to_string()),
+                (PiiCategory::Address, "12 Harbour Road, Leeds".to_string()),
+                (PiiCategory::Account, "4400 1234 5678".to_string()),
+                (PiiCategory::Id, "X1234567".to_string()),
+                (PiiCategory::Name, "Smith".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn dictionary_terms_are_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("terms.txt");
+        std::fs::write(&path, "# known parties\njane roe\naddress: Flat 3\n").unwrap();
+
+        let redactor = Redactor::new().load_dictionary(&path).unwrap();
+        let found = detected(&redactor, "Signed by Jane Roe of Flat 3.");
+        assert_eq!(
+            found,
+            vec![
+                (PiiCategory::Name, "Jane Roe".to_string()),
+                (PiiCategory::Address, "Flat 3".to_string()),
+            ]
+        );
+
+        std::fs::write(&path, "phone: 555\n").unwrap();
+        assert!(Redactor::new().load_dictionary(&path).is_err());
+    }
+
+    #[test]
+    fn disclosure_hides_pii_and_verifies() {
+        let mut chunk = DocumentChunk {
+            content: TEXT.to_string(),
+            metadata: ChunkMetadata {
+                source: "kyc.txt".to_string(),
+                proof_id: None,
+                provider: None,
+                model_hash: None,
+                claim: None,
+                claim_binding: None,
+                commitment: None,
+                span: None,
+            },
+        };
+        assert!(Redactor::new().disclose(&chunk).is_err());
+
+        chunk.metadata.commitment = commit_document(TEXT.as_bytes(), &[TEXT]).pop();
+        let package = Redactor::new().disclose(&chunk).unwrap();
+        package.verify().unwrap();
+
+        for secret in ["Jane", "Harbour", "5678", "X1234567", "Smith"] {
+            assert!(!package.redacted_text.contains(secret), "{}", secret);
+        }
+        assert!(package.redacted_text.contains("Balance of 5000 USD."));
+        assert!(package.redacted_text.contains("[NAME]"));
+    }
+}
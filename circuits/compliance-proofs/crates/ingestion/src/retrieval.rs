@@ -0,0 +1,171 @@
+use crate::db::DynStore;
+use crate::lexical::LexicalIndex;
+use anyhow::Result;
+use private_context_core::DocumentChunk;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Standard RRF damping constant (Cormack et al.)
+const RRF_K: f32 = 60.0;
+
+/// Extra candidates fetched per result so post-filtering still fills `k`.
+const FILTER_OVERFETCH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// BM25 over chunk text only
+    Lexical,
+    /// Embedding nearest-neighbour only
+    Semantic,
+    /// Both, fused with reciprocal-rank fusion
+    Hybrid,
+}
+
+/// Exact-match filters on chunk metadata.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataFilter {
+    pub source: Option<String>,
+    pub provider: Option<String>,
+}
+
+impl MetadataFilter {
+    pub fn is_empty(&self) -> bool {
+        self.source.is_none() && self.provider.is_none()
+    }
+
+    pub fn matches(&self, chunk: &DocumentChunk) -> bool {
+        let source_ok = self
+            .source
+            .as_ref()
+            .map_or(true, |s| &chunk.metadata.source == s);
+        let provider_ok = self
+            .provider
+            .as_ref()
+            .map_or(true, |p| chunk.metadata.provider.as_ref() == Some(p));
+
+        source_ok && provider_ok
+    }
+}
+
+/// Identity used to recognise the same chunk across result lists.
+fn chunk_key(chunk: &DocumentChunk) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(chunk.metadata.source.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(chunk.content.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Stable hex id for a chunk, used to cite where extracted values came from.
+pub fn chunk_id(chunk: &DocumentChunk) -> String {
+    hex::encode(chunk_key(chunk))
+}
+
+/// Merges ranked lists by summing `1 / (RRF_K + rank)` for every list a chunk appears in.
+pub fn reciprocal_rank_fusion(lists: Vec<Vec<DocumentChunk>>, k: usize) -> Vec<DocumentChunk> {
+    let mut scores: HashMap<[u8; 32], (f32, usize, DocumentChunk)> = HashMap::new();
+    let mut order = 0;
+
+    for list in lists {
+        for (rank, chunk) in list.into_iter().enumerate() {
+            let entry = scores.entry(chunk_key(&chunk)).or_insert_with(|| {
+                order += 1;
+                (0.0, order, chunk)
+            });
+            entry.0 += 1.0 / (RRF_K + rank as f32 + 1.0);
+        }
+    }
+
+    let mut fused: Vec<_> = scores.into_values().collect();
+    // Ties keep first-seen order so results are deterministic
+    fused.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    fused.into_iter().take(k).map(|(_, _, chunk)| chunk).collect()
+}
+
+/// Runs the requested retrieval mode and returns up to `k` chunks passing `filter`.
+pub fn retrieve(
+    store: &DynStore,
+    lexical: &LexicalIndex,
+    query_text: &str,
+    query_vector: &Vec<f32>,
+    k: usize,
+    mode: SearchMode,
+    filter: &MetadataFilter,
+) -> Result<Vec<DocumentChunk>> {
+    let semantic_hits = |k: usize| -> Result<Vec<DocumentChunk>> {
+        let fetch = if filter.is_empty() {
+            k
+        } else {
+            k * FILTER_OVERFETCH
+        };
+        Ok(store
+            .search(query_vector, fetch)?
+            .into_iter()
+            .filter(|c| filter.matches(c))
+            .take(k)
+            .collect())
+    };
+    let lexical_hits = |k: usize| -> Result<Vec<DocumentChunk>> {
+        Ok(lexical
+            .search(query_text, k, |c| filter.matches(c))?
+            .into_iter()
+            .map(|(chunk, _score)| chunk)
+            .collect())
+    };
+
+    match mode {
+        SearchMode::Semantic => semantic_hits(k),
+        SearchMode::Lexical => lexical_hits(k),
+        SearchMode::Hybrid => {
+            // Widen each side so fusion has overlap to work with
+            let depth = k * 2;
+            Ok(reciprocal_rank_fusion(vec![semantic_hits(depth)?, lexical_hits(depth)?], k))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use private_context_core::ChunkMetadata;
+
+    fn chunk(content: &str, provider: Option<&str>) -> DocumentChunk {
+        DocumentChunk {
+            content: content.to_string(),
+            metadata: ChunkMetadata {
+                source: "doc.txt".to_string(),
+                proof_id: None,
+                provider: provider.map(str::to_string),
+                model_hash: None,
+                claim: None,
+                claim_binding: None,
+                commitment: None,
+                span: None,
+            },
+        }
+    }
+
+    #[test]
+    fn fusion_promotes_chunks_in_both_lists() {
+        let semantic = vec![chunk("a", None), chunk("b", None), chunk("c", None)];
+        let lexical = vec![chunk("c", None), chunk("d", None)];
+
+        let fused = reciprocal_rank_fusion(vec![semantic, lexical], 4);
+        let order: Vec<_> = fused.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(order, vec!["c", "a", "b", "d"]);
+    }
+
+    #[test]
+    fn filter_matches_provider() {
+        let filter = MetadataFilter {
+            source: Some("doc.txt".to_string()),
+            provider: Some("bank".to_string()),
+        };
+        assert!(filter.matches(&chunk("x", Some("bank"))));
+        assert!(!filter.matches(&chunk("x", Some("other"))));
+        assert!(!filter.matches(&chunk("x", None)));
+        assert!(MetadataFilter::default().matches(&chunk("x", None)));
+    }
+}
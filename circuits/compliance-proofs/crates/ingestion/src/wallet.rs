@@ -0,0 +1,583 @@
+//! Holder profiles: a person or entity with its own encrypted store, signing key and proof
+//! history, kept under `<data_dir>/wallet/<name>`.
+//!
+//! A random data key encrypts everything of a holder's; it is itself encrypted under a key
+//! derived from the holder's passphrase. The store is sealed into one file at rest and only
+//! decrypted into a working directory while a command uses it.
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Holder passphrases are only read from the environment, never from flags or config.
+pub const PASSPHRASE_ENV: &str = "PRIVATE_CONTEXT_PASSPHRASE";
+
+const PROFILE_FILE: &str = "holder.json";
+const STORE_FILE: &str = "store.sealed";
+const PROOFS_FILE: &str = "proofs.sealed";
+/// Decrypted store while a command runs; left behind only if the process dies
+const OPEN_DIR: &str = "store";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum HolderKind {
+    Person,
+    Entity,
+}
+
+impl HolderKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HolderKind::Person => "person",
+            HolderKind::Entity => "entity",
+        }
+    }
+}
+
+/// `holder.json`, the only plaintext file of a profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HolderProfile {
+    pub name: String,
+    pub kind: HolderKind,
+    pub created_at: DateTime<Utc>,
+    /// Hex ed25519 public key the holder signs proof records with
+    pub public_key: String,
+    /// Hex Argon2id salt for the passphrase
+    salt: String,
+    /// Hex data key sealed under the passphrase key
+    wrapped_key: String,
+    /// Hex ed25519 secret key sealed under the data key
+    sealed_signing_key: String,
+}
+
+/// Where a proof ended up on chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Consumption {
+    pub contract: String,
+    pub chain_id: String,
+    pub tx_hash: String,
+    pub at: DateTime<Utc>,
+}
+
+impl Consumption {
+    pub fn now(contract: &str, chain_id: &str, tx_hash: &str) -> Self {
+        Self {
+            contract: contract.to_string(),
+            chain_id: chain_id.to_string(),
+            tx_hash: tx_hash.to_string(),
+            at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofStatus {
+    Active,
+    Expired,
+    Consumed,
+}
+
+impl ProofStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofStatus::Active => "active",
+            ProofStatus::Expired => "expired",
+            ProofStatus::Consumed => "consumed",
+        }
+    }
+}
+
+/// A proof issued for a holder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofRecord {
+    /// See [`proof_id`]
+    pub id: String,
+    pub issued_at: DateTime<Utc>,
+    /// `None` when the policy sets no validity period
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Hex SHA-256 of the proven chunk
+    pub document_hash: String,
+    pub source: Option<String>,
+    pub query: Option<String>,
+    pub similarity: f32,
+    pub vkey_hash: Option<String>,
+    pub proof_path: Option<PathBuf>,
+    /// Hex ed25519 signature by the holder over the public values
+    pub signature: String,
+    pub consumed_by: Option<Consumption>,
+}
+
+impl ProofRecord {
+    pub fn status(&self, now: DateTime<Utc>) -> ProofStatus {
+        match (&self.consumed_by, self.expires_at) {
+            (Some(_), _) => ProofStatus::Consumed,
+            (None, Some(expiry)) if expiry <= now => ProofStatus::Expired,
+            _ => ProofStatus::Active,
+        }
+    }
+}
+
+/// Short identifier of a proof: the first 16 hex characters of SHA-256 over its public values.
+pub fn proof_id(public_values: &[u8]) -> String {
+    hex::encode(&Sha256::digest(public_values)[..8])
+}
+
+/// Expiry `validity_days` after `issued_at`; zero days means the proof does not expire.
+pub fn expiry(issued_at: DateTime<Utc>, validity_days: u32) -> Option<DateTime<Utc>> {
+    (validity_days > 0).then(|| issued_at + Duration::days(validity_days as i64))
+}
+
+/// Reads the holder passphrase from [`PASSPHRASE_ENV`].
+pub fn passphrase_from_env() -> Result<String> {
+    std::env::var(PASSPHRASE_ENV)
+        .map_err(|_| anyhow!("Set {} to unlock holder profiles", PASSPHRASE_ENV))
+}
+
+pub struct Wallet {
+    root: PathBuf,
+}
+
+impl Wallet {
+    pub fn new(data_dir: impl AsRef<Path>) -> Self {
+        Self {
+            root: data_dir.as_ref().join("wallet"),
+        }
+    }
+
+    fn dir(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    pub fn create(&self, name: &str, kind: HolderKind, passphrase: &str) -> Result<HolderProfile> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            bail!(
+                "Holder name `{}` must be lowercase letters, digits, `-` or `_`",
+                name
+            );
+        }
+        let dir = self.dir(name);
+        if dir.join(PROFILE_FILE).exists() {
+            bail!("Holder `{}` already exists", name);
+        }
+        fs_err::create_dir_all(&dir)?;
+
+        let salt: [u8; 16] = rand_bytes();
+        let data_key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let signing_key = SigningKey::from_bytes(&rand_bytes());
+        let wrapped_key = seal(
+            &passphrase_key(passphrase, &salt)?,
+            &data_key,
+            &aad("key", name),
+        )?;
+        let sealed_signing_key = seal(&data_key, &signing_key.to_bytes(), &aad("signing", name))?;
+
+        let profile = HolderProfile {
+            name: name.to_string(),
+            kind,
+            created_at: Utc::now(),
+            public_key: hex::encode(signing_key.verifying_key().as_bytes()),
+            salt: hex::encode(salt),
+            wrapped_key: hex::encode(wrapped_key),
+            sealed_signing_key: hex::encode(sealed_signing_key),
+        };
+        fs_err::write(
+            dir.join(PROFILE_FILE),
+            serde_json::to_string_pretty(&profile)?,
+        )?;
+        Ok(profile)
+    }
+
+    pub fn profiles(&self) -> Result<Vec<HolderProfile>> {
+        if !self.root.exists() {
+            return Ok(vec![]);
+        }
+        let mut profiles = Vec::new();
+        for entry in fs_err::read_dir(&self.root)? {
+            let path = entry?.path().join(PROFILE_FILE);
+            if path.exists() {
+                profiles.push(read_profile(&path)?);
+            }
+        }
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(profiles)
+    }
+
+    pub fn profile(&self, name: &str) -> Result<HolderProfile> {
+        let path = self.dir(name).join(PROFILE_FILE);
+        if !path.exists() {
+            bail!(
+                "No holder `{}`; create it with `wallet create {}`",
+                name,
+                name
+            );
+        }
+        read_profile(&path)
+    }
+
+    /// Decrypts the holder's data key. Fails on a wrong passphrase.
+    pub fn unlock(&self, name: &str, passphrase: &str) -> Result<Holder> {
+        let profile = self.profile(name)?;
+        let salt = hex::decode(&profile.salt)?;
+        let data_key = open(
+            &passphrase_key(passphrase, &salt)?,
+            &hex::decode(&profile.wrapped_key)?,
+            &aad("key", name),
+        )
+        .map_err(|_| anyhow!("Wrong passphrase for holder `{}`", name))?;
+
+        Ok(Holder {
+            dir: self.dir(name),
+            key: *Key::from_slice(&data_key),
+            profile,
+        })
+    }
+}
+
+fn read_profile(path: &Path) -> Result<HolderProfile> {
+    serde_json::from_str(&fs_err::read_to_string(path)?)
+        .with_context(|| format!("Malformed holder profile {}", path.display()))
+}
+
+/// An unlocked holder profile.
+pub struct Holder {
+    pub profile: HolderProfile,
+    dir: PathBuf,
+    key: Key,
+}
+
+impl Holder {
+    /// Decrypts the holder's store into a working directory and returns it. A working
+    /// directory left by an interrupted run is newer than the sealed copy and is reused.
+    pub fn open_store(&self) -> Result<PathBuf> {
+        let open_dir = self.dir.join(OPEN_DIR);
+        if open_dir.exists() {
+            return Ok(open_dir);
+        }
+        fs_err::create_dir_all(&open_dir)?;
+        let sealed = self.dir.join(STORE_FILE);
+        if sealed.exists() {
+            let bundle = open(&self.key, &fs_err::read(&sealed)?, &self.aad("store"))?;
+            unbundle(&bundle, &open_dir)?;
+        }
+        Ok(open_dir)
+    }
+
+    /// Seals the working directory back into the encrypted store and removes it. The store
+    /// must not be in use.
+    pub fn close(self) -> Result<()> {
+        let open_dir = self.dir.join(OPEN_DIR);
+        if !open_dir.exists() {
+            return Ok(());
+        }
+        let sealed = seal(&self.key, &bundle(&open_dir)?, &self.aad("store"))?;
+        // Write then rename so a crash never leaves a truncated store
+        let tmp = self.dir.join(format!("{}.tmp", STORE_FILE));
+        fs_err::write(&tmp, sealed)?;
+        fs_err::rename(&tmp, self.dir.join(STORE_FILE))?;
+        fs_err::remove_dir_all(&open_dir)?;
+        Ok(())
+    }
+
+    pub fn signing_key(&self) -> Result<SigningKey> {
+        let bytes = open(
+            &self.key,
+            &hex::decode(&self.profile.sealed_signing_key)?,
+            &self.aad("signing"),
+        )?;
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow!("Corrupt signing key"))?;
+        Ok(SigningKey::from_bytes(&bytes))
+    }
+
+    /// Signs `public_values` with the holder key, for [`ProofRecord::signature`].
+    pub fn sign(&self, public_values: &[u8]) -> Result<String> {
+        Ok(hex::encode(
+            self.signing_key()?.sign(public_values).to_bytes(),
+        ))
+    }
+
+    pub fn proofs(&self) -> Result<Vec<ProofRecord>> {
+        let path = self.dir.join(PROOFS_FILE);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let json = open(&self.key, &fs_err::read(&path)?, &self.aad("proofs"))?;
+        serde_json::from_slice(&json).context("Corrupt proof history")
+    }
+
+    fn save_proofs(&self, proofs: &[ProofRecord]) -> Result<()> {
+        let sealed = seal(&self.key, &serde_json::to_vec(proofs)?, &self.aad("proofs"))?;
+        fs_err::write(self.dir.join(PROOFS_FILE), sealed)?;
+        Ok(())
+    }
+
+    /// Adds a proof to the history, replacing an earlier record with the same id.
+    pub fn record_proof(&self, record: ProofRecord) -> Result<()> {
+        let mut proofs = self.proofs()?;
+        proofs.retain(|p| p.id != record.id);
+        proofs.push(record);
+        self.save_proofs(&proofs)
+    }
+
+    /// Marks a proof as consumed by a contract.
+    pub fn record_consumption(&self, id: &str, consumption: Consumption) -> Result<ProofRecord> {
+        let mut proofs = self.proofs()?;
+        let record = proofs
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| anyhow!("Holder `{}` has no proof {}", self.profile.name, id))?;
+        record.consumed_by = Some(consumption);
+        let record = record.clone();
+        self.save_proofs(&proofs)?;
+        Ok(record)
+    }
+
+    fn aad(&self, purpose: &str) -> Vec<u8> {
+        aad(purpose, &self.profile.name)
+    }
+}
+
+/// Binds each ciphertext to its holder and purpose so files cannot be swapped.
+fn aad(purpose: &str, name: &str) -> Vec<u8> {
+    format!("private-context/wallet/{}/{}", purpose, name).into_bytes()
+}
+
+pub(crate) fn rand_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    chacha20poly1305::aead::rand_core::RngCore::fill_bytes(&mut OsRng, &mut bytes);
+    bytes
+}
+
+fn passphrase_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// `nonce (12) | ciphertext`
+fn seal(key: &Key, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| anyhow!("Encryption failed"))?;
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+fn open(key: &Key, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < 12 {
+        bail!("Sealed data is truncated");
+    }
+    let (nonce, ciphertext) = sealed.split_at(12);
+    ChaCha20Poly1305::new(key)
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| anyhow!("Decryption failed; wrong key or tampered data"))
+}
+
+/// Packs every file under `dir` as `path len (u32 LE) | path | size (u64 LE) | bytes`.
+fn bundle(dir: &Path) -> Result<Vec<u8>> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+
+    let mut out = Vec::new();
+    for path in files {
+        let relative = path
+            .strip_prefix(dir)?
+            .to_str()
+            .ok_or_else(|| anyhow!("Non UTF-8 path in store: {}", path.display()))?
+            .replace('\\', "/");
+        let bytes = fs_err::read(&path)?;
+        out.extend_from_slice(&(relative.len() as u32).to_le_bytes());
+        out.extend_from_slice(relative.as_bytes());
+        out.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+        out.extend_from_slice(&bytes);
+    }
+    Ok(out)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs_err::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn unbundle(mut bytes: &[u8], dir: &Path) -> Result<()> {
+    fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+        if bytes.len() < n {
+            bail!("Store bundle is truncated");
+        }
+        let (head, rest) = bytes.split_at(n);
+        *bytes = rest;
+        Ok(head)
+    }
+
+    while !bytes.is_empty() {
+        let len = u32::from_le_bytes(take(&mut bytes, 4)?.try_into()?) as usize;
+        let relative = std::str::from_utf8(take(&mut bytes, len)?)?;
+        if relative
+            .split('/')
+            .any(|part| part == ".." || part.is_empty())
+        {
+            bail!("Invalid path `{}` in store bundle", relative);
+        }
+        let size = u64::from_le_bytes(take(&mut bytes, 8)?.try_into()?) as usize;
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        fs_err::write(&path, take(&mut bytes, size)?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    fn record(id: &str, expires_at: Option<DateTime<Utc>>) -> ProofRecord {
+        ProofRecord {
+            id: id.to_string(),
+            issued_at: Utc::now(),
+            expires_at,
+            document_hash: "00".repeat(32),
+            source: Some("fund.md".to_string()),
+            query: Some("accredited investor".to_string()),
+            similarity: 0.82,
+            vkey_hash: None,
+            proof_path: None,
+            signature: String::new(),
+            consumed_by: None,
+        }
+    }
+
+    #[test]
+    fn profiles_are_isolated_by_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet = Wallet::new(dir.path());
+        let fund = wallet
+            .create("fund-a", HolderKind::Entity, "alpha")
+            .unwrap();
+        wallet.create("alice", HolderKind::Person, "beta").unwrap();
+
+        assert!(wallet.create("fund-a", HolderKind::Entity, "x").is_err());
+        assert!(wallet.create("../up", HolderKind::Entity, "x").is_err());
+        let names: Vec<String> = wallet
+            .profiles()
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["alice", "fund-a"]);
+
+        let err = wallet.unlock("fund-a", "beta").err().unwrap().to_string();
+        assert!(err.contains("Wrong passphrase"), "{}", err);
+
+        let holder = wallet.unlock("fund-a", "alpha").unwrap();
+        let signature = holder.sign(b"public values").unwrap();
+        let signature = Signature::from_slice(&hex::decode(signature).unwrap()).unwrap();
+        let key = holder.signing_key().unwrap().verifying_key();
+        assert_eq!(hex::encode(key.as_bytes()), fund.public_key);
+        assert!(key.verify(b"public values", &signature).is_ok());
+    }
+
+    #[test]
+    fn store_is_sealed_at_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet = Wallet::new(dir.path());
+        wallet
+            .create("fund-a", HolderKind::Entity, "alpha")
+            .unwrap();
+
+        let holder = wallet.unlock("fund-a", "alpha").unwrap();
+        let store = holder.open_store().unwrap();
+        fs_err::create_dir_all(store.join("vectors/db")).unwrap();
+        fs_err::write(store.join("vectors/db/conf"), b"secret chunk").unwrap();
+        holder.close().unwrap();
+
+        let holder_dir = dir.path().join("wallet/fund-a");
+        assert!(!holder_dir.join(OPEN_DIR).exists());
+        let sealed = fs_err::read(holder_dir.join(STORE_FILE)).unwrap();
+        assert!(!sealed.windows(12).any(|w| w == b"secret chunk"));
+
+        let holder = wallet.unlock("fund-a", "alpha").unwrap();
+        let store = holder.open_store().unwrap();
+        assert_eq!(
+            fs_err::read(store.join("vectors/db/conf")).unwrap(),
+            b"secret chunk"
+        );
+        holder.close().unwrap();
+    }
+
+    #[test]
+    fn proof_history_tracks_consumption_and_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let wallet = Wallet::new(dir.path());
+        wallet
+            .create("fund-a", HolderKind::Entity, "alpha")
+            .unwrap();
+        let holder = wallet.unlock("fund-a", "alpha").unwrap();
+
+        let now = Utc::now();
+        holder.record_proof(record("aa", expiry(now, 90))).unwrap();
+        holder
+            .record_proof(record("bb", Some(now - Duration::days(1))))
+            .unwrap();
+        holder.record_proof(record("cc", expiry(now, 0))).unwrap();
+        holder
+            .record_consumption(
+                "aa",
+                Consumption::now("mantra1verifier", "mantra-local-1", "ABCDEF"),
+            )
+            .unwrap();
+        assert!(holder
+            .record_consumption("zz", Consumption::now("c", "x", "y"))
+            .is_err());
+
+        let proofs = wallet.unlock("fund-a", "alpha").unwrap().proofs().unwrap();
+        let status: Vec<ProofStatus> = proofs.iter().map(|p| p.status(now)).collect();
+        assert_eq!(
+            status,
+            vec![
+                ProofStatus::Consumed,
+                ProofStatus::Expired,
+                ProofStatus::Active
+            ]
+        );
+        assert_eq!(
+            proofs[0].consumed_by.as_ref().unwrap().contract,
+            "mantra1verifier"
+        );
+        assert_eq!(proofs[2].expires_at, None);
+    }
+}
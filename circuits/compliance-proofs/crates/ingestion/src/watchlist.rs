@@ -0,0 +1,119 @@
+use crate::embedder::Embedder;
+use anyhow::{bail, Context, Result};
+use private_context_core::exclusion::vector_root;
+use private_context_core::merkle::Hash;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Watch-list entities with the embeddings an exclusion proof commits to. Saved as JSON so the
+/// same vectors, and so the same list hash, can be published and reused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchList {
+    /// Hex SHA-256 of the weights that embedded the entries
+    pub model_hash: String,
+    pub entries: Vec<String>,
+    pub vectors: Vec<Vec<f32>>,
+}
+
+impl WatchList {
+    /// Embeds `entries` as queries, since each is matched against document chunks.
+    pub fn embed<E: Embedder + ?Sized>(entries: Vec<String>, embedder: &E) -> Result<Self> {
+        let vectors = entries
+            .iter()
+            .map(|entry| embedder.embed_query(entry))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            model_hash: embedder.model_hash().to_string(),
+            entries,
+            vectors,
+        })
+    }
+
+    /// Loads a `.json` list saved by [`WatchList::save`], or embeds a text file with one entity
+    /// per line, where `#` starts a comment. A JSON list must come from the same model.
+    pub fn load<E: Embedder + ?Sized>(path: &Path, embedder: &E) -> Result<Self> {
+        let text = fs_err::read_to_string(path).context("Failed to read watch-list")?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let list: Self = serde_json::from_str(&text).context("Invalid watch-list JSON")?;
+            if list.vectors.len() != list.entries.len() {
+                bail!(
+                    "Watch-list has {} entries but {} vectors",
+                    list.entries.len(),
+                    list.vectors.len()
+                );
+            }
+            if list.model_hash != embedder.model_hash() {
+                bail!(
+                    "Watch-list was embedded with model {}, but {} is loaded",
+                    list.model_hash,
+                    embedder.model_hash()
+                );
+            }
+            return Ok(list);
+        }
+
+        let entries = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        Self::embed(entries, embedder)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs_err::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The commitment exclusion proofs publish for this list.
+    pub fn list_hash(&self) -> Hash {
+        vector_root(&self.vectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct VowelEmbedder(&'static str);
+
+    impl Embedder for VowelEmbedder {
+        fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+            Ok("aeiou"
+                .chars()
+                .map(|v| text.matches(v).count() as f32)
+                .collect())
+        }
+
+        fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            texts.iter().map(|t| self.embed_query(t)).collect()
+        }
+
+        fn dimension(&self) -> usize {
+            5
+        }
+
+        fn model_hash(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn text_and_saved_lists_commit_the_same_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("sanctions.txt");
+        std::fs::write(&text, "# OFAC extract\nAcme Shipping\n\n  Orion Holdings \n").unwrap();
+
+        let embedder = VowelEmbedder("vowels");
+        let list = WatchList::load(&text, &embedder).unwrap();
+        assert_eq!(list.entries, vec!["Acme Shipping", "Orion Holdings"]);
+
+        let json = dir.path().join("sanctions.json");
+        list.save(&json).unwrap();
+        let reloaded = WatchList::load(&json, &embedder).unwrap();
+        assert_eq!(reloaded.list_hash(), list.list_hash());
+
+        assert!(WatchList::load(&json, &VowelEmbedder("other")).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! Runs the daemon on an ephemeral loopback port with the SP1 mock prover and a
+//! stand-in embedder, so no model weights or proving toolchain are needed.
+#![cfg(feature = "hnsw")]
+
+use anyhow::Result;
+use private_context_ingestion::daemon::{self, DaemonState, JobQueue};
+use private_context_ingestion::db::{DynStore, LocalStore};
+use private_context_ingestion::embedder::Embedder;
+use private_context_ingestion::lexical::LexicalIndex;
+use private_context_ingestion::pipeline::{Checkpoints, IngestOptions};
+use private_context_ingestion::prover::{ProverMode, RelevanceProver, DEFAULT_ELF_PATH};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Letter-frequency vectors: identical text gives cosine similarity 1.
+struct LetterEmbedder;
+
+impl Embedder for LetterEmbedder {
+    fn embed_query(&self, text: &str) -> Result<Vec<f32>> {
+        let mut v = vec![0.0; 26];
+        for c in text.to_lowercase().chars().filter(char::is_ascii_lowercase) {
+            v[(c as u8 - b'a') as usize] += 1.0;
+        }
+        Ok(v)
+    }
+
+    fn embed_passages(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        texts.iter().map(|t| self.embed_query(t)).collect()
+    }
+
+    fn dimension(&self) -> usize {
+        26
+    }
+
+    fn model_hash(&self) -> &str {
+        "letters"
+    }
+}
+
+const TOKEN: &str = "test-token";
+
+struct Daemon {
+    base: String,
+    client: reqwest::Client,
+    _data: tempfile::TempDir,
+}
+
+impl Daemon {
+    async fn start() -> Self {
+        let data = tempfile::tempdir().unwrap();
+        let data_dir = data.path().to_string_lossy().to_string();
+
+        // Tests run from the crate directory; the ELF path is relative to the workspace root
+        let elf = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../..")
+            .join(DEFAULT_ELF_PATH);
+        let store: DynStore = Box::new(LocalStore::new(&data_dir).unwrap());
+
+        let state = Arc::new(DaemonState {
+            embedder: Arc::new(LetterEmbedder),
+            store: Arc::new(Mutex::new(store)),
+            lexical: Arc::new(LexicalIndex::open(&data_dir).unwrap()),
+            checkpoints: Arc::new(Checkpoints::open(&data_dir).unwrap()),
+            prover: Some(Arc::new(
+                RelevanceProver::new(&elf, ProverMode::Mock).unwrap(),
+            )),
+            ingest_options: IngestOptions {
+                batch_size: 4,
+                threads: 1,
+            },
+            jobs: JobQueue::start(),
+            token: TOKEN.to_string(),
+        });
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(daemon::serve(listener, state));
+
+        Self {
+            base,
+            client: reqwest::Client::new(),
+            _data: data,
+        }
+    }
+
+    async fn post(&self, path: &str, body: Value) -> (u16, Value) {
+        let res = self
+            .client
+            .post(format!("{}{}", self.base, path))
+            .bearer_auth(TOKEN)
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        (res.status().as_u16(), res.json().await.unwrap())
+    }
+
+    async fn get(&self, path: &str) -> (u16, Value) {
+        let res = self
+            .client
+            .get(format!("{}{}", self.base, path))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        (res.status().as_u16(), res.json().await.unwrap())
+    }
+
+    async fn wait_for_job(&self, id: u64) -> Value {
+        for _ in 0..600 {
+            let (_, status) = self.get(&format!("/jobs/{}", id)).await;
+            match status["state"].as_str().unwrap() {
+                "queued" | "running" => tokio::time::sleep(Duration::from_millis(100)).await,
+                _ => return status,
+            }
+        }
+        panic!("job {} did not finish", id);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ingest_search_and_prove_in_mock_mode() {
+    let daemon = Daemon::start().await;
+    let docs = tempfile::tempdir().unwrap();
+    let text = "The holder is an accredited investor";
+    std::fs::write(docs.path().join("kyc.txt"), text).unwrap();
+
+    let (code, health) = daemon.get("/health").await;
+    assert_eq!(code, 200);
+    assert_eq!(health["prover"], "mock");
+
+    let (code, job) = daemon
+        .post("/ingest", json!({ "path": docs.path() }))
+        .await;
+    assert_eq!(code, 202);
+    let done = daemon.wait_for_job(job["id"].as_u64().unwrap()).await;
+    assert_eq!(done["state"], "succeeded", "{}", done);
+    assert_eq!(done["result"]["files_ingested"], 1);
+
+    let (code, hits) = daemon
+        .post("/search", json!({ "text": "accredited investor", "k": 3 }))
+        .await;
+    assert_eq!(code, 200);
+    assert_eq!(hits[0]["content"], text);
+    assert_eq!(hits[0]["metadata"]["model_hash"], "letters");
+
+    let (code, job) = daemon
+        .post("/prove", json!({ "text": text, "threshold": 0.9 }))
+        .await;
+    assert_eq!(code, 202);
+    let done = daemon.wait_for_job(job["id"].as_u64().unwrap()).await;
+    assert_eq!(done["state"], "succeeded", "{}", done);
+    assert_eq!(done["result"]["is_relevant"], true);
+    assert!(done["result"]["proof"].is_null());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bad_requests_are_rejected() {
+    let daemon = Daemon::start().await;
+
+    let (code, body) = daemon
+        .post("/ingest", json!({ "path": "/definitely/not/here/*.txt" }))
+        .await;
+    assert_eq!(code, 400);
+    assert!(body["error"].as_str().unwrap().contains("No files match"));
+
+    let (code, _) = daemon.get("/jobs/42").await;
+    assert_eq!(code, 404);
+
+    let (code, job) = daemon.post("/prove", json!({ "text": "empty store" })).await;
+    assert_eq!(code, 202);
+    let done = daemon.wait_for_job(job["id"].as_u64().unwrap()).await;
+    assert_eq!(done["state"], "failed");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unauthorized_requests_are_rejected() {
+    let daemon = Daemon::start().await;
+    let url = format!("{}/health", daemon.base);
+
+    let res = daemon.client.get(&url).send().await.unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+
+    let res = daemon
+        .client
+        .get(&url)
+        .bearer_auth("not-the-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+
+    // A page on a rebound DNS name reaches the loopback port with its own host name
+    let res = daemon
+        .client
+        .get(&url)
+        .bearer_auth(TOKEN)
+        .header("Host", "attacker.example:7878")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 403);
+
+    let res = daemon
+        .client
+        .get(&url)
+        .bearer_auth(TOKEN)
+        .header("Host", "localhost:7878")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+}
@@ -0,0 +1,104 @@
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Signer state needed to build a valid signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountInfo {
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BroadcastResult {
+    pub txhash: String,
+    #[serde(default)]
+    pub code: u32,
+    #[serde(default)]
+    pub raw_log: String,
+}
+
+/// Client for the Cosmos SDK REST (LCD) endpoints of a node.
+pub struct ChainClient {
+    base: String,
+    http: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct AccountResponse {
+    account: RawAccount,
+}
+
+/// Numbers are strings in the REST encoding.
+#[derive(Deserialize)]
+struct RawAccount {
+    account_number: String,
+    sequence: String,
+}
+
+#[derive(Deserialize)]
+struct BroadcastResponse {
+    tx_response: BroadcastResult,
+}
+
+impl ChainClient {
+    pub fn new(base: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn account(&self, address: &str) -> Result<AccountInfo> {
+        let res = self
+            .http
+            .get(format!(
+                "{}/cosmos/auth/v1beta1/accounts/{}",
+                self.base, address
+            ))
+            .send()
+            .await
+            .context("Failed to reach chain REST endpoint")?;
+        if !res.status().is_success() {
+            bail!(
+                "Account {} lookup failed with HTTP {}; is it funded?",
+                address,
+                res.status()
+            );
+        }
+        let body: AccountResponse = res.json().await.context("Unexpected account response")?;
+        Ok(AccountInfo {
+            account_number: body.account.account_number.parse()?,
+            sequence: body.account.sequence.parse()?,
+        })
+    }
+
+    /// Broadcasts in sync mode, failing if the node rejects the transaction in `CheckTx`.
+    pub async fn broadcast(&self, tx_bytes: &[u8]) -> Result<BroadcastResult> {
+        let res = self
+            .http
+            .post(format!("{}/cosmos/tx/v1beta1/txs", self.base))
+            .json(&json!({
+                "tx_bytes": base64::engine::general_purpose::STANDARD.encode(tx_bytes),
+                "mode": "BROADCAST_MODE_SYNC",
+            }))
+            .send()
+            .await
+            .context("Failed to reach chain REST endpoint")?;
+        if !res.status().is_success() {
+            bail!("Broadcast failed with HTTP {}", res.status());
+        }
+        let body: BroadcastResponse = res.json().await.context("Unexpected broadcast response")?;
+        let result = body.tx_response;
+        if result.code != 0 {
+            bail!(
+                "Transaction {} rejected (code {}): {}",
+                result.txhash,
+                result.code,
+                result.raw_log
+            );
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,6 @@
+//! Turns proof requests into SP1 proofs and `MintRwaAsset` transactions for the
+//! MANTRA verifier contract.
+
+pub mod chain;
+pub mod request;
+pub mod tx;
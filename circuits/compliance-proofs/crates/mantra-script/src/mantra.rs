@@ -1,115 +1,221 @@
-use cosmwasm_std::Binary;
-use serde::{Deserialize, Serialize};
-use sp1_sdk::{ProverClient, SP1Stdin};
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use mantra_script::chain::{AccountInfo, ChainClient};
+use mantra_script::request::{ProofOutput, ProofRequest};
+use mantra_script::tx::{address_of, mint_msg, signing_key, FeeConfig, MintTx};
+use private_context_ingestion::prover::{ProverMode, RelevanceProver};
+use private_context_ingestion::wallet::{passphrase_from_env, proof_id, Consumption, Wallet};
+use std::path::PathBuf;
 
-const ELF: &[u8] = include_bytes!("../../circuits/elf/riscv32im-succinct-zkvm-elf");
+const DEFAULT_ELF: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../circuits/elf/riscv32im-succinct-zkvm-elf"
+);
 
-#[derive(Serialize, Deserialize)]
-struct RwaMetadata {
-    jurisdiction: String,
-    accreditation_level: String,
-    document_hash: String,
+#[derive(Parser)]
+#[command(
+    author,
+    version,
+    about = "Prove compliance requests and submit them to MANTRA Chain"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Args)]
+struct ProveArgs {
+    /// Proof request JSON (inputs, threshold, policy)
+    request: PathBuf,
+    /// Where the proof is written, and looked up for reuse
+    #[arg(long, default_value = "proof_output.json")]
+    output: PathBuf,
+    /// Prover backend; defaults to SP1_PROVER or local
+    #[arg(long, value_enum)]
+    prover: Option<ProverMode>,
+    /// Guest ELF
+    #[arg(long, default_value = DEFAULT_ELF)]
+    elf: PathBuf,
+}
+
+impl ProveArgs {
+    fn run(&self) -> Result<ProofOutput> {
+        let request = ProofRequest::load(&self.request)?;
+        request.prove_or_reuse(
+            || {
+                let mode = match self.prover {
+                    Some(mode) => mode,
+                    None => ProverMode::from_env()?,
+                };
+                RelevanceProver::new(&self.elf, mode)
+            },
+            &self.output,
+        )
+    }
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate (or reuse) the proof for a request
+    Prove(ProveArgs),
+    /// Prove, then build a `MintRwaAsset` transaction and broadcast or print it
+    Submit {
+        #[command(flatten)]
+        prove: ProveArgs,
+        /// Verifier contract address
+        #[arg(long, env = "MANTRA_CONTRACT")]
+        contract: String,
+        /// Node REST (LCD) endpoint
+        #[arg(long, env = "MANTRA_REST", default_value = "http://localhost:1317")]
+        rest: String,
+        #[arg(long, env = "MANTRA_CHAIN_ID", default_value = "mantra-local-1")]
+        chain_id: String,
+        /// Sign with MANTRA_SIGNER_KEY and broadcast; otherwise print the unsigned tx
+        #[arg(long)]
+        broadcast: bool,
+        /// Sender for the unsigned tx; defaults to the signer key's address
+        #[arg(long)]
+        sender: Option<String>,
+        /// Fee amount in `denom`
+        #[arg(long, default_value_t = 5000)]
+        fee: u128,
+        #[arg(long, default_value = "uom")]
+        denom: String,
+        #[arg(long, default_value_t = 600_000)]
+        gas: u64,
+        /// Defaults to the request's policy
+        #[arg(long)]
+        memo: Option<String>,
+        /// Skip the account lookup (requires --sequence)
+        #[arg(long, requires = "sequence")]
+        account_number: Option<u64>,
+        #[arg(long, requires = "account_number")]
+        sequence: Option<u64>,
+        /// Record the submission in this wallet holder's proof history
+        #[arg(long, env = "PRIVATE_CONTEXT_HOLDER")]
+        holder: Option<String>,
+        /// Directory holding the wallet
+        #[arg(long, env = "PRIVATE_CONTEXT_DATA_DIR", default_value = "data")]
+        data_dir: String,
+    },
 }
 
 #[tokio::main]
-async fn main() {
-    println!("🚀 Z-RWA Proof Generation Starting...\n");
-
-    // 1. Setup SP1 Prover
-    let client = ProverClient::new();
-    
-    println!("✅ SP1 Client initialized");
-    println!("📦 Guest program ELF loaded ({} bytes)\n", ELF.len());
-
-    // 2. Prepare inputs for the circuit
-    // The circuit expects: query_vec, chunk_vec, threshold, document_hash
-    
-    // Sample document embedding (384-dimensional vector - typical for MiniLM)
-    let query_vec: Vec<f32> = vec![0.1; 384];
-    let chunk_vec: Vec<f32> = vec![0.1; 384]; // High similarity for demo
-    let threshold: f32 = 0.7;
-    
-    // Document hash (simulating SHA256 of compliance document)
-    let document_hash: [u8; 32] = [
-        0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
-        0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
-        0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
-        0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
-    ];
-
-    println!("📝 Input Parameters:");
-    println!("   - Query vector dimension: {}", query_vec.len());
-    println!("   - Chunk vector dimension: {}", chunk_vec.len());
-    println!("   - Similarity threshold: {}", threshold);
-    println!("   - Document hash: 0x{}", hex::encode(&document_hash));
-    println!();
-
-    // 3. Write inputs to SP1Stdin
-    let mut stdin = SP1Stdin::new();
-    stdin.write(&query_vec);
-    stdin.write(&chunk_vec);
-    stdin.write(&threshold);
-    stdin.write(&document_hash);
-
-    println!("🔧 Generating Groth16 proof (this may take several minutes)...");
-    
-    // 4. Generate proof
-    let (pk, vk) = client.setup(ELF);
-    
-    // Use Groth16 proof for on-chain verification
-    let proof = client
-        .prove(&pk, stdin)
-        .groth16()
-        .run()
-        .expect("Proof generation failed");
-
-    println!("✅ Proof generated successfully!\n");
-
-    // 5. Extract public values
-    let public_values = proof.public_values.as_slice();
-    
-    println!("📊 Proof Details:");
-    println!("   - Proof size: {} bytes", proof.bytes().len());
-    println!("   - Public values size: {} bytes", public_values.len());
-    println!();
-
-    // 6. Verify the proof locally first
-    println!("🔍 Verifying proof locally...");
-    client
-        .verify(&proof, &vk)
-        .expect("Proof verification failed");
-    
-    println!("✅ Local verification successful!\n");
-
-    // 7. Prepare output for CosmWasm contract
-    let proof_binary = Binary::from(proof.bytes());
-    let public_values_hex = hex::encode(public_values);
-    
-    let output = serde_json::json!({
-        "proof": proof_binary,
-        "public_values": public_values_hex,
-        "document_hash": hex::encode(&document_hash),
-        "metadata": {
-            "jurisdiction": "VARA_DUBAI",
-            "accreditation": "QUALIFIED_INSTITUTIONAL",
-            "proof_system": "SP1_GROTH16"
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Prove(args) => {
+            println!("🚀 Z-RWA proof for {}", args.request.display());
+            let output = args.run()?;
+            print_summary(&output);
+        }
+        Commands::Submit {
+            prove,
+            contract,
+            rest,
+            chain_id,
+            broadcast,
+            sender,
+            fee,
+            denom,
+            gas,
+            memo,
+            account_number,
+            sequence,
+            holder,
+            data_dir,
+        } => {
+            // Status goes to stderr so the unsigned tx can be piped to `mantrachaind tx sign`
+            let output = prove.run()?;
+
+            // Secret material only ever comes from the environment, never from flags
+            let key = match std::env::var("MANTRA_SIGNER_KEY") {
+                Ok(hex_key) => Some(signing_key(&hex_key)?),
+                Err(_) => None,
+            };
+            let sender = match (&sender, &key) {
+                (Some(sender), _) => sender.clone(),
+                (None, Some(key)) => address_of(key)?,
+                (None, None) => bail!("Pass --sender or set MANTRA_SIGNER_KEY"),
+            };
+
+            let tx = MintTx {
+                sender,
+                contract,
+                msg: mint_msg(&output)?,
+                fee: FeeConfig {
+                    amount: fee,
+                    denom,
+                    gas,
+                },
+                memo: memo.unwrap_or_else(|| {
+                    format!(
+                        "z-rwa {} {}",
+                        output.metadata.jurisdiction, output.metadata.accreditation
+                    )
+                }),
+            };
+
+            if !broadcast {
+                println!("{}", serde_json::to_string_pretty(&tx.unsigned_json()?)?);
+                return Ok(());
+            }
+
+            let Some(key) = key else {
+                bail!("--broadcast needs MANTRA_SIGNER_KEY");
+            };
+            if address_of(&key)? != tx.sender {
+                bail!("--sender does not match MANTRA_SIGNER_KEY");
+            }
+            // Unlock before broadcasting so a wrong passphrase fails while nothing is on chain
+            let holder = match &holder {
+                Some(name) => Some(Wallet::new(&data_dir).unlock(name, &passphrase_from_env()?)?),
+                None => None,
+            };
+
+            let chain = ChainClient::new(&rest);
+            let account = match (account_number, sequence) {
+                (Some(account_number), Some(sequence)) => AccountInfo {
+                    account_number,
+                    sequence,
+                },
+                _ => chain.account(&tx.sender).await?,
+            };
+            print_summary(&output);
+            let tx_bytes = tx.sign(&key, &chain_id, account.account_number, account.sequence)?;
+
+            println!("📡 Broadcasting to {} ({})...", rest, chain_id);
+            let result = chain
+                .broadcast(&tx_bytes)
+                .await
+                .context("Broadcast failed")?;
+            println!("✅ Submitted MintRwaAsset: {}", result.txhash);
+
+            if let Some(holder) = holder {
+                let id = proof_id(&hex::decode(&output.public_values)?);
+                let consumption = Consumption::now(&tx.contract, &chain_id, &result.txhash);
+                // The transaction is already on chain, so a missing record only warns
+                match holder.record_consumption(&id, consumption) {
+                    Ok(_) => println!(
+                        "🗂️  Proof {} recorded as consumed in {}'s wallet",
+                        id, holder.profile.name
+                    ),
+                    Err(e) => eprintln!("⚠️  {:#}", e),
+                }
+            }
         }
-    });
-
-    println!("📄 Proof Output:");
-    println!("{}\n", serde_json::to_string_pretty(&output).unwrap());
-
-    // 8. Write to file for contract submission
-    std::fs::write(
-        "proof_output.json",
-        serde_json::to_string_pretty(&output).unwrap(),
-    )
-    .expect("Failed to write proof output");
-
-    println!("✅ Proof saved to: proof_output.json");
-    println!("\n🎉 Proof generation complete!");
-    println!("\n📋 Next steps:");
-    println!("   1. Deploy the verifier contract to MANTRA testnet");
-    println!("   2. Submit this proof via the MintRwaAsset execute message");
-    println!("   3. The contract will verify the proof on-chain");
+    }
+
+    Ok(())
+}
+
+fn print_summary(output: &ProofOutput) {
+    println!("📊 Proof Details:");
+    println!("   - Proof size: {} bytes", output.proof.len());
+    println!("   - Document hash: 0x{}", output.document_hash);
+    println!(
+        "   - Policy: {} / {}",
+        output.metadata.jurisdiction, output.metadata.accreditation
+    );
 }
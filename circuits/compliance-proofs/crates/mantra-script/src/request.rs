@@ -0,0 +1,322 @@
+use anyhow::{anyhow, bail, Context, Result};
+use cosmwasm_std::Binary;
+use private_context_ingestion::db::{open_store, StoreBackend, StoreConfig};
+use private_context_ingestion::embedder::{CandleEmbedder, Embedder};
+use private_context_ingestion::lexical::LexicalIndex;
+use private_context_ingestion::prover::{RelevanceInputs, RelevanceProver};
+use private_context_ingestion::retrieval::{chunk_id, retrieve, MetadataFilter, SearchMode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+fn default_threshold() -> f32 {
+    0.7
+}
+
+fn default_model_dir() -> String {
+    "./models/all-MiniLM-L6-v2".to_string()
+}
+
+fn default_data_dir() -> String {
+    "data".to_string()
+}
+
+fn default_backend() -> StoreBackend {
+    StoreBackend::Hnsw
+}
+
+fn default_candidates() -> usize {
+    20
+}
+
+/// What to prove and under which policy, read from a JSON request file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProofRequest {
+    pub input: ProofInput,
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    pub policy: Policy,
+    /// Existing proof output to submit instead of proving
+    #[serde(default)]
+    pub reuse: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofInput {
+    /// Raw embeddings, e.g. exported from another pipeline
+    Vectors {
+        query: Vec<f32>,
+        chunk: Vec<f32>,
+        /// Hex SHA-256 of the chunk content
+        document_hash: String,
+        /// Hex Reclaim claim identifier; zero when absent
+        #[serde(default)]
+        claim_identifier: Option<String>,
+    },
+    /// A chunk from a local ingestion store, found by the id `search` prints
+    Chunk {
+        id: String,
+        /// Query text the chunk is proven relevant to
+        query: String,
+        #[serde(default = "default_model_dir")]
+        model_dir: String,
+        #[serde(default = "default_data_dir")]
+        data_dir: String,
+        #[serde(default = "default_backend")]
+        backend: StoreBackend,
+        /// How many hybrid search hits to scan for `id`
+        #[serde(default = "default_candidates")]
+        candidates: usize,
+    },
+}
+
+/// Compliance policy the proof is submitted under; recorded alongside the proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy {
+    pub jurisdiction: String,
+    pub accreditation: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    pub jurisdiction: String,
+    pub accreditation: String,
+    pub proof_system: String,
+}
+
+/// The `proof_output.json` written after proving and read back for submission.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofOutput {
+    pub proof: Binary,
+    pub public_values: String,
+    pub document_hash: String,
+    #[serde(default)]
+    pub claim_identifier: Option<String>,
+    pub metadata: ProofMetadata,
+    /// Digest of the circuit inputs; a later request with the same inputs reuses this proof
+    #[serde(default)]
+    pub request_digest: Option<String>,
+}
+
+impl ProofRequest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read proof request {}", path.display()))?;
+        let mut request: Self = serde_json::from_str(&json).context("Malformed proof request")?;
+
+        // A relative `reuse` path is relative to the request file, not the working directory
+        if let (Some(reuse), Some(dir)) = (&request.reuse, path.parent()) {
+            request.reuse = Some(dir.join(reuse));
+        }
+        Ok(request)
+    }
+
+    /// Turns the request into circuit inputs, embedding from the local store when needed.
+    pub fn resolve(&self) -> Result<RelevanceInputs> {
+        match &self.input {
+            ProofInput::Vectors {
+                query,
+                chunk,
+                document_hash,
+                claim_identifier,
+            } => {
+                if query.len() != chunk.len() {
+                    bail!(
+                        "Query and chunk vectors differ in length ({} vs {})",
+                        query.len(),
+                        chunk.len()
+                    );
+                }
+                Ok(RelevanceInputs {
+                    query_vector: query.clone(),
+                    chunk_vector: chunk.clone(),
+                    threshold: self.threshold,
+                    document_hash: decode_hash(document_hash)?,
+                    claim_identifier: match claim_identifier {
+                        Some(id) => decode_hash(id)?,
+                        None => [0u8; 32],
+                    },
+                })
+            }
+            ProofInput::Chunk {
+                id,
+                query,
+                model_dir,
+                data_dir,
+                backend,
+                candidates,
+            } => {
+                let embedder = CandleEmbedder::new(Path::new(model_dir), None)
+                    .context("Failed to initialize embedder")?;
+                let store = open_store(&StoreConfig {
+                    backend: *backend,
+                    data_dir: data_dir.clone(),
+                    dimension: embedder.dimension(),
+                })?;
+                let lexical = LexicalIndex::open(data_dir)?;
+
+                let query_vector = embedder.embed_query(query)?;
+                let hits = retrieve(
+                    &store,
+                    &lexical,
+                    query,
+                    &query_vector,
+                    *candidates,
+                    SearchMode::Hybrid,
+                    &MetadataFilter::default(),
+                )?;
+                let chunk = hits
+                    .into_iter()
+                    .find(|c| &chunk_id(c) == id)
+                    .ok_or_else(|| {
+                        anyhow!("Chunk {} is not among the top {} hits", id, candidates)
+                    })?;
+
+                Ok(RelevanceInputs {
+                    chunk_vector: embedder
+                        .embed_passages(&[chunk.content.as_str()])?
+                        .remove(0),
+                    query_vector,
+                    threshold: self.threshold,
+                    document_hash: Sha256::digest(chunk.content.as_bytes()).into(),
+                    claim_identifier: match &chunk.metadata.claim {
+                        Some(claim) => claim.identifier_bytes()?,
+                        None => [0u8; 32],
+                    },
+                })
+            }
+        }
+    }
+
+    /// Reuses a proof when the request names one or `output` already holds a proof of the
+    /// same inputs; otherwise proves with `prover` and writes the result to `output`.
+    pub fn prove_or_reuse(
+        &self,
+        prover: impl FnOnce() -> Result<RelevanceProver>,
+        output: &Path,
+    ) -> Result<ProofOutput> {
+        if let Some(path) = &self.reuse {
+            eprintln!("♻️  Reusing proof from {}", path.display());
+            let mut reused = ProofOutput::load(path)?;
+            reused.metadata.jurisdiction = self.policy.jurisdiction.clone();
+            reused.metadata.accreditation = self.policy.accreditation.clone();
+            return Ok(reused);
+        }
+
+        let inputs = self.resolve()?;
+        let digest = request_digest(&inputs);
+        if output.exists() {
+            let cached = ProofOutput::load(output)?;
+            if cached.request_digest.as_deref() == Some(digest.as_str()) {
+                eprintln!("♻️  {} already proves these inputs", output.display());
+                return Ok(cached);
+            }
+        }
+
+        let prover = prover()?;
+        eprintln!("🔧 Generating proof ({:?})...", prover.mode());
+        let proof = prover.prove(&inputs)?;
+        let Some(proof_bytes) = proof.proof else {
+            bail!("Mock execution produced no Groth16 proof; rerun with a local or network prover");
+        };
+        if !proof.outputs.is_relevant {
+            bail!(
+                "Similarity {} is below the threshold {}; nothing to submit",
+                proof.outputs.similarity,
+                self.threshold
+            );
+        }
+
+        let out = ProofOutput {
+            proof: Binary::from(proof_bytes),
+            public_values: hex::encode(&proof.public_values),
+            document_hash: hex::encode(inputs.document_hash),
+            claim_identifier: Some(hex::encode(inputs.claim_identifier)),
+            metadata: ProofMetadata {
+                jurisdiction: self.policy.jurisdiction.clone(),
+                accreditation: self.policy.accreditation.clone(),
+                proof_system: "SP1_GROTH16".to_string(),
+            },
+            request_digest: Some(digest),
+        };
+        out.save(output)?;
+        eprintln!("💾 Proof saved to {}", output.display());
+        Ok(out)
+    }
+}
+
+impl ProofOutput {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read proof output {}", path.display()))?;
+        serde_json::from_str(&json).context("Malformed proof output")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Hex SHA-256 over the circuit inputs in the order the guest reads them.
+pub fn request_digest(inputs: &RelevanceInputs) -> String {
+    let mut hasher = Sha256::new();
+    for v in &inputs.query_vector {
+        hasher.update(v.to_le_bytes());
+    }
+    hasher.update([0xff]);
+    for v in &inputs.chunk_vector {
+        hasher.update(v.to_le_bytes());
+    }
+    hasher.update(inputs.threshold.to_le_bytes());
+    hasher.update(inputs.document_hash);
+    hasher.update(inputs.claim_identifier);
+    hex::encode(hasher.finalize())
+}
+
+fn decode_hash(s: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).context("Hash is not hex")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Hash must be 32 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vector_request_and_digests_inputs() {
+        let request: ProofRequest = serde_json::from_value(serde_json::json!({
+            "input": { "vectors": {
+                "query": [0.1, 0.2],
+                "chunk": [0.1, 0.2],
+                "document_hash": "0x".to_string() + &"ab".repeat(32),
+            }},
+            "policy": { "jurisdiction": "VARA_DUBAI", "accreditation": "QUALIFIED_INSTITUTIONAL" },
+        }))
+        .unwrap();
+
+        let inputs = request.resolve().unwrap();
+        assert_eq!(inputs.threshold, 0.7);
+        assert_eq!(inputs.document_hash, [0xab; 32]);
+        assert_eq!(inputs.claim_identifier, [0u8; 32]);
+
+        let mut other = request.resolve().unwrap();
+        assert_eq!(request_digest(&inputs), request_digest(&other));
+        other.threshold = 0.8;
+        assert_ne!(request_digest(&inputs), request_digest(&other));
+    }
+
+    #[test]
+    fn rejects_mismatched_vectors() {
+        let request: ProofRequest = serde_json::from_value(serde_json::json!({
+            "input": { "vectors": { "query": [0.1], "chunk": [0.1, 0.2], "document_hash": "00".repeat(32) }},
+            "policy": { "jurisdiction": "X", "accreditation": "Y" },
+        }))
+        .unwrap();
+        assert!(request.resolve().is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::request::ProofOutput;
+use anyhow::{anyhow, Context, Result};
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::{self, Fee, Msg, SignDoc, SignerInfo};
+use cosmrs::{AccountId, Coin};
+use cosmwasm_std::Binary;
+use mantra_contract::msg::ExecuteMsg;
+use serde_json::{json, Value};
+
+/// Bech32 prefix of MANTRA Chain accounts.
+pub const ACCOUNT_PREFIX: &str = "mantra";
+
+#[derive(Debug, Clone)]
+pub struct FeeConfig {
+    pub amount: u128,
+    pub denom: String,
+    pub gas: u64,
+}
+
+/// Everything that goes into the transaction apart from the signer's account state.
+#[derive(Debug, Clone)]
+pub struct MintTx {
+    pub sender: String,
+    pub contract: String,
+    /// JSON-encoded `ExecuteMsg::MintRwaAsset`
+    pub msg: Vec<u8>,
+    pub fee: FeeConfig,
+    pub memo: String,
+}
+
+/// Encodes the verifier contract's `MintRwaAsset` for a proof output.
+pub fn mint_msg(output: &ProofOutput) -> Result<Vec<u8>> {
+    let document_hash = hex::decode(output.document_hash.trim_start_matches("0x"))
+        .context("Proof output document hash is not hex")?;
+    let msg = ExecuteMsg::MintRwaAsset {
+        document_hash: Binary::from(document_hash),
+        proof: output.proof.clone(),
+    };
+    Ok(serde_json::to_vec(&msg)?)
+}
+
+/// Reads a hex secp256k1 private key.
+pub fn signing_key(hex_key: &str) -> Result<SigningKey> {
+    let bytes =
+        hex::decode(hex_key.trim().trim_start_matches("0x")).context("Signer key is not hex")?;
+    SigningKey::from_slice(&bytes).map_err(|e| anyhow!("Invalid signer key: {}", e))
+}
+
+pub fn address_of(key: &SigningKey) -> Result<String> {
+    Ok(key
+        .public_key()
+        .account_id(ACCOUNT_PREFIX)
+        .map_err(|e| anyhow!("{}", e))?
+        .to_string())
+}
+
+impl MintTx {
+    fn body(&self) -> Result<tx::Body> {
+        let sender: AccountId = self.sender.parse().map_err(|e| anyhow!("Sender: {}", e))?;
+        let contract: AccountId = self
+            .contract
+            .parse()
+            .map_err(|e| anyhow!("Contract: {}", e))?;
+        let msg = MsgExecuteContract {
+            sender,
+            contract,
+            msg: self.msg.clone(),
+            funds: vec![],
+        }
+        .to_any()
+        .map_err(|e| anyhow!("{}", e))?;
+        Ok(tx::Body::new(vec![msg], &self.memo, 0u32))
+    }
+
+    fn fee(&self) -> Result<Fee> {
+        let coin = Coin {
+            denom: self
+                .fee
+                .denom
+                .parse()
+                .map_err(|e| anyhow!("Fee denom: {}", e))?,
+            amount: self.fee.amount,
+        };
+        Ok(Fee::from_amount_and_gas(coin, self.fee.gas))
+    }
+
+    /// Signs in SIGN_MODE_DIRECT and returns the protobuf `TxRaw` bytes ready to broadcast.
+    pub fn sign(
+        &self,
+        key: &SigningKey,
+        chain_id: &str,
+        account_number: u64,
+        sequence: u64,
+    ) -> Result<Vec<u8>> {
+        let auth_info =
+            SignerInfo::single_direct(Some(key.public_key()), sequence).auth_info(self.fee()?);
+        let chain_id = chain_id.parse().map_err(|e| anyhow!("Chain id: {}", e))?;
+        let sign_doc = SignDoc::new(&self.body()?, &auth_info, &chain_id, account_number)
+            .map_err(|e| anyhow!("{}", e))?;
+        sign_doc
+            .sign(key)
+            .and_then(|raw| raw.to_bytes())
+            .map_err(|e| anyhow!("Signing failed: {}", e))
+    }
+
+    /// The transaction in the JSON shape of `mantrachaind tx ... --generate-only`, so it can
+    /// be signed offline with `mantrachaind tx sign`.
+    pub fn unsigned_json(&self) -> Result<Value> {
+        let msg: Value = serde_json::from_slice(&self.msg)?;
+        Ok(json!({
+            "body": {
+                "messages": [{
+                    "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+                    "sender": self.sender,
+                    "contract": self.contract,
+                    "msg": msg,
+                    "funds": [],
+                }],
+                "memo": self.memo,
+                "timeout_height": "0",
+                "extension_options": [],
+                "non_critical_extension_options": [],
+            },
+            "auth_info": {
+                "signer_infos": [],
+                "fee": {
+                    "amount": [{ "denom": self.fee.denom, "amount": self.fee.amount.to_string() }],
+                    "gas_limit": self.fee.gas.to_string(),
+                    "payer": "",
+                    "granter": "",
+                },
+            },
+            "signatures": [],
+        }))
+    }
+}
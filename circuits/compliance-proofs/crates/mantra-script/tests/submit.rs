@@ -0,0 +1,180 @@
+//! Submits the cached fixture proof to a mock chain that serves the two Cosmos SDK
+//! REST endpoints the tool uses, so no node or prover is needed.
+
+use axum::extract::{Path as UrlPath, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::Engine;
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::tx::Msg;
+use mantra_script::chain::{AccountInfo, ChainClient};
+use mantra_script::request::ProofRequest;
+use mantra_script::tx::{address_of, mint_msg, signing_key, FeeConfig, MintTx};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct MockChain {
+    received: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// Non-zero makes CheckTx fail with this code
+    reject_code: u32,
+}
+
+async fn account(UrlPath(address): UrlPath<String>) -> Json<Value> {
+    Json(json!({
+        "account": {
+            "@type": "/cosmos.auth.v1beta1.BaseAccount",
+            "address": address,
+            "pub_key": null,
+            "account_number": "7",
+            "sequence": "3",
+        }
+    }))
+}
+
+async fn broadcast(State(chain): State<MockChain>, Json(body): Json<Value>) -> Json<Value> {
+    assert_eq!(body["mode"], "BROADCAST_MODE_SYNC");
+    let tx_bytes = base64::engine::general_purpose::STANDARD
+        .decode(body["tx_bytes"].as_str().unwrap())
+        .unwrap();
+    chain.received.lock().unwrap().push(tx_bytes);
+    Json(json!({
+        "tx_response": {
+            "txhash": "ABCDEF",
+            "code": chain.reject_code,
+            "raw_log": if chain.reject_code == 0 { "" } else { "out of gas" },
+        }
+    }))
+}
+
+async fn start(chain: MockChain) -> String {
+    let app = Router::new()
+        .route("/cosmos/auth/v1beta1/accounts/:address", get(account))
+        .route("/cosmos/tx/v1beta1/txs", post(broadcast))
+        .with_state(chain);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    base
+}
+
+fn fixture() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../fixtures/proof_output.json")
+}
+
+/// A request that reuses the cached Groth16 fixture instead of proving.
+fn reuse_request(dir: &std::path::Path) -> ProofRequest {
+    let path = dir.join("request.json");
+    std::fs::write(
+        &path,
+        json!({
+            "input": { "vectors": { "query": [0.1], "chunk": [0.1], "document_hash": "00".repeat(32) }},
+            "policy": { "jurisdiction": "ADGM", "accreditation": "PROFESSIONAL" },
+            "reuse": fixture(),
+        })
+        .to_string(),
+    )
+    .unwrap();
+    ProofRequest::load(&path).unwrap()
+}
+
+fn mint_tx(request: &ProofRequest, dir: &std::path::Path) -> MintTx {
+    let output = request
+        .prove_or_reuse(|| panic!("fixture should be reused"), &dir.join("out.json"))
+        .unwrap();
+    assert_eq!(output.metadata.jurisdiction, "ADGM");
+
+    MintTx {
+        sender: address_of(&signing_key(&"01".repeat(32)).unwrap()).unwrap(),
+        contract: address_of(&signing_key(&"02".repeat(32)).unwrap()).unwrap(),
+        msg: mint_msg(&output).unwrap(),
+        fee: FeeConfig {
+            amount: 5000,
+            denom: "uom".to_string(),
+            gas: 600_000,
+        },
+        memo: "z-rwa ADGM PROFESSIONAL".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn signed_mint_is_broadcast_with_chain_account_state() {
+    let dir = tempfile::tempdir().unwrap();
+    let chain = MockChain::default();
+    let client = ChainClient::new(&start(chain.clone()).await);
+    let tx = mint_tx(&reuse_request(dir.path()), dir.path());
+    let key = signing_key(&"01".repeat(32)).unwrap();
+
+    let account = client.account(&tx.sender).await.unwrap();
+    assert_eq!(
+        account,
+        AccountInfo {
+            account_number: 7,
+            sequence: 3
+        }
+    );
+    let bytes = tx
+        .sign(
+            &key,
+            "mantra-local-1",
+            account.account_number,
+            account.sequence,
+        )
+        .unwrap();
+    let result = client.broadcast(&bytes).await.unwrap();
+    assert_eq!(result.txhash, "ABCDEF");
+
+    let received = chain.received.lock().unwrap();
+    let decoded = cosmrs::Tx::from_bytes(&received[0]).unwrap();
+    assert_eq!(decoded.signatures.len(), 1);
+    assert_eq!(decoded.auth_info.signer_infos[0].sequence, 3);
+    assert_eq!(decoded.body.memo, "z-rwa ADGM PROFESSIONAL");
+
+    let execute = MsgExecuteContract::from_any(&decoded.body.messages[0]).unwrap();
+    assert_eq!(execute.sender.to_string(), tx.sender);
+    assert_eq!(execute.contract.to_string(), tx.contract);
+    let msg: Value = serde_json::from_slice(&execute.msg).unwrap();
+    let fixture: Value =
+        serde_json::from_str(&std::fs::read_to_string(fixture()).unwrap()).unwrap();
+    assert_eq!(msg["mint_rwa_asset"]["proof"], fixture["proof"]);
+    assert!(msg["mint_rwa_asset"]["document_hash"].is_string());
+}
+
+#[tokio::test]
+async fn rejected_tx_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = ChainClient::new(
+        &start(MockChain {
+            reject_code: 11,
+            ..Default::default()
+        })
+        .await,
+    );
+    let tx = mint_tx(&reuse_request(dir.path()), dir.path());
+    let bytes = tx
+        .sign(
+            &signing_key(&"01".repeat(32)).unwrap(),
+            "mantra-local-1",
+            7,
+            3,
+        )
+        .unwrap();
+
+    let err = client.broadcast(&bytes).await.unwrap_err().to_string();
+    assert!(err.contains("code 11"), "{}", err);
+}
+
+#[test]
+fn unsigned_tx_matches_generate_only_shape() {
+    let dir = tempfile::tempdir().unwrap();
+    let tx = mint_tx(&reuse_request(dir.path()), dir.path());
+    let unsigned = tx.unsigned_json().unwrap();
+
+    let message = &unsigned["body"]["messages"][0];
+    assert_eq!(message["@type"], "/cosmwasm.wasm.v1.MsgExecuteContract");
+    assert_eq!(message["contract"], tx.contract.as_str());
+    assert!(message["msg"]["mint_rwa_asset"]["proof"].is_string());
+    assert_eq!(unsigned["auth_info"]["fee"]["gas_limit"], "600000");
+    assert!(unsigned["signatures"].as_array().unwrap().is_empty());
+}
@@ -1,10 +1,23 @@
 #!/bin/bash
 set -e
 
-MODEL_DIR="models/all-MiniLM-L6-v2"
+# Usage: ./setup_models.sh [minilm|bge|e5]
+FAMILY="${1:-minilm}"
+
+case "$FAMILY" in
+    minilm) REPO="sentence-transformers/all-MiniLM-L6-v2" ;;
+    bge)    REPO="BAAI/bge-small-en-v1.5" ;;
+    e5)     REPO="intfloat/e5-small-v2" ;;
+    *)
+        echo "Unknown model family: $FAMILY (expected minilm, bge or e5)"
+        exit 1
+        ;;
+esac
+
+MODEL_DIR="models/${REPO##*/}"
 mkdir -p "$MODEL_DIR"
 
-BASE_URL="https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main"
+BASE_URL="https://huggingface.co/$REPO/resolve/main"
 
 echo "Downloading model files to $MODEL_DIR..."
 
@@ -0,0 +1,31 @@
+mantrachaind tx wasm execute \
+    tp16h50hcp3m777t68vv42x6kzdrym9dyn5ucxq6tpj46qnnye0k97slzkku3 \
+    '{
+    "create_dvp_order": {
+        "params": {
+            "order_id": "order-1",
+            "denom": "MCustomMarker",
+            "amount": "200",
+            "seller": "tp1lz7rw3p48tsztjaqpnqzz7vzwfczrlkcrwkgqy",
+            "buyer": "tp109582adr63kjgnr5psznfkqhlratx48e4ycslz",
+            "payment_asset": {
+                "native": {
+                    "denom": "nhash"
+                }
+            },
+            "price": "2000000",
+            "expiry": "1767225600"
+        }
+    }
+}' \
+    --from $buyer \
+    --keyring-backend test \
+    --home $prov_path \
+    --chain-id pio-testnet-1 \
+    --gas 4000000 \
+    --gas-prices 1905nhash \
+    --broadcast-mode block \
+    --yes \
+    --testnet \
+	--output json \
+	--node=https://rpc.test.provenance.io:443 | jq
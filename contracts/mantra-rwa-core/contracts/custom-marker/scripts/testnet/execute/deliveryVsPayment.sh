@@ -1,25 +0,0 @@
-
-mantrachaind tx wasm execute \
-    tp16h50hcp3m777t68vv42x6kzdrym9dyn5ucxq6tpj46qnnye0k97slzkku3 \
-    '{
-    "delivery_vs_payment": {
-        "denom": "prov-dvpxx",
-        "mint_data": [
-            {
-                "address": "tp109582adr63kjgnr5psznfkqhlratx48e4ycslz",
-                "amount": "100"
-            }
-        ]
-    }
-}' \
-    --from $minter \
-    --keyring-backend test \
-    --home $prov_path \
-    --chain-id pio-testnet-1 \
-    --gas 4000000 \
-    --gas-prices 26905nhash \
-    --broadcast-mode block \
-    --yes \
-    --testnet \
-	--output json \
-	--node=https://rpc.test.provenance.io:443 | jq '.txhash, .code, .raw_log'
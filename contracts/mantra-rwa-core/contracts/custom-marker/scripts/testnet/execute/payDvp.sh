@@ -0,0 +1,19 @@
+mantrachaind tx wasm execute \
+    tp16h50hcp3m777t68vv42x6kzdrym9dyn5ucxq6tpj46qnnye0k97slzkku3 \
+    '{
+    "pay_dvp": {
+        "order_id": "order-1"
+    }
+}' \
+    --amount 2000000nhash \
+    --from $buyer \
+    --keyring-backend test \
+    --home $prov_path \
+    --chain-id pio-testnet-1 \
+    --gas 4000000 \
+    --gas-prices 1905nhash \
+    --broadcast-mode block \
+    --yes \
+    --testnet \
+	--output json \
+	--node=https://rpc.test.provenance.io:443 | jq
@@ -0,0 +1,10 @@
+
+mantrachaind query wasm contract-state smart tp16h50hcp3m777t68vv42x6kzdrym9dyn5ucxq6tpj46qnnye0k97slzkku3 \
+	'{
+    "get_dvp_orders": {
+        "address": "tp109582adr63kjgnr5psznfkqhlratx48e4ycslz"
+    }
+}' \
+    --testnet \
+	--output json \
+	--node=https://rpc.test.provenance.io:443 | jq
@@ -1,13 +1,22 @@
 #![cfg(not(feature = "library"))]
 #[cfg(not(feature = "library"))]
-use crate::{enums::*, error::ContractError, helper::*, msg::*, state::*, structs::*};
+use crate::{
+    country::*, enums::*, error::ContractError, helper::*, msg::*, rules::*, state::*, structs::*,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    attr, entry_point, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, Uint128,
+    attr, entry_point, from_json, Addr, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128, Uint64,
 };
 #[cfg(not(feature = "library"))]
+use cw20::Cw20ReceiveMsg;
+#[cfg(not(feature = "library"))]
 use provwasm_std::{ProvenanceMsg, ProvenanceQuerier, ProvenanceQuery};
 
+// Holders per batch of a corporate action
+const DEFAULT_BATCH_SIZE: u32 = 10;
+const MAX_BATCH_SIZE: u32 = 30;
+
 /// Handle messages that create and interact with with native provenance markers.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -38,11 +47,14 @@ pub fn execute(
             try_update_freezelist(deps, info.sender, denom, update_type)
         }
         ExecuteMsg::PartialFreeze { denom, params } => {
-            try_partial_freeze(deps, info.sender, denom, params)
+            try_partial_freeze(deps, info.sender, denom, params, env)
         }
         ExecuteMsg::UpdateCountryCode { update_type, denom } => {
             try_update_country_code(deps, update_type, denom, info.sender)
         }
+        ExecuteMsg::UpdateRegion { name, codes } => {
+            try_update_region(deps, name, codes, info.sender)
+        }
         ExecuteMsg::Send { amount, denom, to } => {
             try_send(deps, amount, denom, to, info.sender, env)
         }
@@ -51,10 +63,10 @@ pub fn execute(
         }
         ExecuteMsg::Whitelist { lists } => try_update_whitelist(deps, lists, info.sender),
         ExecuteMsg::MintTo { mint_to_params } => {
-            try_mint_to(deps, mint_to_params, info.sender, env.contract.address)
+            try_mint_to(deps, mint_to_params, info.sender, env)
         }
         ExecuteMsg::BurnFrom { burn_from_params } => {
-            try_burn_from(deps, burn_from_params, env.contract.address, info.sender)
+            try_burn_from(deps, burn_from_params, info.sender, env)
         }
         ExecuteMsg::ManageRoles { denom, roles } => {
             try_manage_roles(deps, info, env.contract.address, denom, roles)
@@ -62,8 +74,68 @@ pub fn execute(
         ExecuteMsg::ForceTransfer { denom, params } => {
             try_force_transfer(deps, denom, params, info.sender, env)
         }
-        ExecuteMsg::DeliveryVsPayment { denom, mint_data } => {
-            try_dvp(deps, denom, mint_data, info.sender, env.contract.address)
+        ExecuteMsg::CreateDvpOrder { params } => {
+            try_create_dvp_order(deps, params, info.sender, env)
+        }
+        ExecuteMsg::DeliverDvp { order_id } => try_deliver_dvp(deps, order_id, info.sender, env),
+        ExecuteMsg::PayDvp { order_id } => {
+            let payment = match info.funds.as_slice() {
+                [funds] => Some((
+                    PaymentAsset::Native {
+                        denom: funds.denom.clone(),
+                    },
+                    funds.amount,
+                )),
+                _ => None,
+            };
+            try_pay_dvp(deps, order_id, info.sender, payment, env)
+        }
+        ExecuteMsg::CancelDvp { order_id } => try_cancel_dvp(deps, order_id, info.sender, env),
+        ExecuteMsg::Receive(msg) => try_receive(deps, msg, info.sender, env),
+        ExecuteMsg::UpdateLockupPeriod { denom, period } => {
+            try_update_lockup_period(deps, denom, period, info.sender)
+        }
+        ExecuteMsg::UpdateVesting { denom, params } => {
+            try_update_vesting(deps, denom, params, info.sender)
+        }
+        ExecuteMsg::UpdateTransferRules { denom, rules } => {
+            try_update_transfer_rules(deps, denom, rules, info.sender)
+        }
+        ExecuteMsg::UpdateInvestorCategory { denom, params } => {
+            try_update_investor_category(deps, denom, params, info.sender)
+        }
+        ExecuteMsg::UpdateTierPolicies { denom, policies } => {
+            try_update_tier_policies(deps, denom, policies, info.sender)
+        }
+        ExecuteMsg::UpdateTierVerifier { denom, verifier } => {
+            try_update_tier_verifier(deps, denom, verifier, info.sender)
+        }
+        ExecuteMsg::SyncHolders { denom, addresses } => {
+            try_sync_holders(deps, denom, addresses, info.sender)
+        }
+        ExecuteMsg::StartCorporateAction { params } => {
+            try_start_corporate_action(deps, params, info.sender, env)
+        }
+        ExecuteMsg::ProcessCorporateAction { action_id, limit } => {
+            try_process_corporate_action(deps, action_id, limit, info.sender, env)
+        }
+        ExecuteMsg::CancelCorporateAction { action_id } => {
+            try_cancel_corporate_action(deps, action_id, info.sender, env)
+        }
+        ExecuteMsg::RecoverAccount {
+            denom,
+            lost,
+            new,
+            evidence_hash,
+        } => try_recover_account(deps, denom, lost, new, evidence_hash, info.sender, env),
+        ExecuteMsg::ApproveRecovery { denom, lost } => {
+            try_approve_recovery(deps, denom, lost, info.sender, env)
+        }
+        ExecuteMsg::CancelRecovery { denom, lost } => {
+            try_cancel_recovery(deps, denom, lost, info.sender)
+        }
+        ExecuteMsg::UpdateRecoveryThreshold { denom, threshold } => {
+            try_update_recovery_threshold(deps, denom, threshold, info.sender)
         }
     }
 }
@@ -78,6 +150,7 @@ pub fn execute(
 /// Fails when:-
 ///     - caller is not sub_admin
 ///     - supply is lesser than token_limit
+///     - a country code is not an ISO 3166-1 numeric code
 ///
 /// Emits event:-
 ///     - provwasm.contracts.custom_marker.create
@@ -93,6 +166,9 @@ fn try_create(
     // checking caller is sub_admin
     is_subadmin(&deps, info.sender.clone())?;
 
+    // ensuring country codes are ISO 3166-1 numeric codes
+    ensure_country_codes(&params.denom_config.country_codes)?;
+
     // storing denom configuration
     DENOM_CONFIG.save(
         deps.storage,
@@ -322,6 +398,7 @@ fn try_destroy(
 ///     - caller is not issuer, transfer_agent, sub_admin or having freeze access for freezing
 ///     - caller is not issuer, transfer_agent, sub_admin or having unfreeze access for unfreezing
 ///     - freeze list doesn't contain the address that is going to be removed
+///     - an address that is going to be removed is recovered
 /// Emits event:-
 ///     - when update_type = UpdateType::Add(Vec<Addr>), then
 ///         1. provwasm.contracts.custom_marker.freeze
@@ -388,6 +465,16 @@ fn try_update_freezelist(
                 return Err(ContractError::Unauthorized { err });
             }
 
+            // Recovered addresses stay freezed
+            for addr in &addrs {
+                let key = Key::new(denom.clone(), addr.clone()).as_bytes()?;
+                if RECOVERED_ACCOUNTS.has(deps.storage, &key) {
+                    return Err(ContractError::AccountRecovered {
+                        address: addr.clone(),
+                    });
+                }
+            }
+
             // Removing addresses from Freeze List
             FREEZE_LIST.update(
                 deps.storage,
@@ -419,21 +506,22 @@ fn try_update_freezelist(
 /// Update whitelist.
 /// This function is used to whitelist address based on country_code.
 /// Both set and unset whitelist can be performed by this single entry function.
-/// For set `update_kind` will be `UpdateKind::Set(u8)`
-/// For unset `update_kind` will be `UpdateKind::Unset(u8)`
+/// For set `update_kind` will be `UpdateKind::Set(CountryCode)`, an ISO 3166-1 alpha-2 or numeric code
+/// For unset `update_kind` will be `UpdateKind::Unset {}`
 /// This function supports batch operations, i.e. multiple addresses can be set / unset from whitelist as well as
 /// multiple denoms simultaneously.
 ///
 /// Fails when:-
 ///     - caller is not sub_admin or tokenization agent
+///     - country code is not an ISO 3166-1 alpha-2 or numeric code
 ///     - the list already contains the address for addition
 ///     - the list doesn't have the address for removal
 ///
 /// Emits event:-
-///     - when update_kind = UpdateKind::Set(u8), then
+///     - when update_kind = UpdateKind::Set(CountryCode), then
 ///         1. provwasm.contracts.custom_marker.whitelist.set
 ///         2. address
-///     - when update_kind = UpdateKind::Unset(u8), then
+///     - when update_kind = UpdateKind::Unset {}, then
 ///         1. provwasm.contracts.custom_marker.whitelist.unset
 ///         2. address
 fn try_update_whitelist(
@@ -459,6 +547,7 @@ fn try_update_whitelist(
 
             match cd_data.country_code {
                 UpdateKind::Set(code) => {
+                    let code = code.numeric()?;
                     if WHITELIST.has(deps.storage, &key) {
                         return Err(ContractError::CountryCodeAlreadyExists { code });
                     }
@@ -497,14 +586,17 @@ fn try_update_whitelist(
 /// For partial unfreeze  `update_type` will be `UpdateType::Remove(Uint128)`
 /// This function supports batch operations, i.e. multiple addresses can be partially freezed / unfreezed
 /// simultaneously.
-/// When the tokens are partially freezed then entire amount can't be used in any transactions, only unfreezed tokens
-/// can take part in the transactions.
+/// When the tokens are partially freezed then the frozen amount can't be sent, force transferred or burned,
+/// only the spendable balance, i.e. the balance not frozen, can take part in the transactions.
+/// A freeze may carry a reason code and an expiry, after which its amount is released; an unfreeze
+/// releases the latest freezes first, only those with its reason code when it has one.
 ///  
 /// Fails when:-
 ///     - caller is not sub_admin, issuer, transfer agent or having freeze access for partial freeze
 ///     - caller is not sub_admin, issuer, transfer agent or having unfreeze access for partial unfreeze
+///     - the expiry of a freeze is not in the future
 ///     - the partial freeze list dosen't have the address for removal
-///     - the frozen token list is empty
+///     - the unfrozen amount exceeds the frozen amount
 ///
 /// Emits event:-
 ///     - when update_type = UpdateType::Add(Uint128), then
@@ -518,9 +610,10 @@ fn try_partial_freeze(
     sender: Addr,
     denom: String,
     params: Vec<PartialFreezeParams>,
+    env: Env,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let now = env.block.time.seconds();
     let mut attrs = Vec::new();
-    let mut rem_bal = Uint128::zero();
     for param in params {
         let key = Key::new(denom.clone(), param.address.clone()).as_bytes()?;
 
@@ -539,44 +632,28 @@ fn try_partial_freeze(
                     return Err(ContractError::Unauthorized { err });
                 }
 
-                // Updating balance for the account that already exists and adding the address and balance pairs that
-                // do not exist
-                if PARTIAL_FREEZE
-                    .update(deps.storage, &key, |bals_opt| -> Result<_, ContractError> {
-                        match bals_opt {
-                            Some(mut bals) => Ok({
-                                bals += bal;
-                                bals
-                            }),
-                            None => Ok(bal),
-                        }
-                    })
-                    .is_err()
-                {
-                    // Adding address and balance pair that doesn't exist
-                    PARTIAL_FREEZE.save(deps.storage, &key, &bal)?;
+                if let Some(expires_at) = param.expires_at {
+                    if expires_at.u64() <= now {
+                        return Err(ContractError::InvalidFreezeExpiry { expires_at });
+                    }
                 }
 
+                // Adding the freeze to the unexpired freezes of the address
+                let mut lots = FREEZE_LOTS
+                    .may_load(deps.storage, &key)?
+                    .unwrap_or_default();
+                prune_freeze_lots(deps.storage, &denom, &mut lots, now)?;
+                lots.push(FreezeLot {
+                    amount: bal,
+                    reason: param.reason,
+                    expires_at: param.expires_at,
+                });
+                FREEZE_LOTS.save(deps.storage, &key, &lots)?;
+
                 // Updating frozen tokens balance
-                if FROZEN_TOKENS
-                    .update(
-                        deps.storage,
-                        denom.as_bytes(),
-                        |bals_opt| -> Result<_, ContractError> {
-                            match bals_opt {
-                                Some(mut bals) => Ok({
-                                    bals += bal;
-                                    bals
-                                }),
-                                None => Ok(bal),
-                            }
-                        },
-                    )
-                    .is_err()
-                {
-                    // Adding balance as new entry
-                    FROZEN_TOKENS.save(deps.storage, denom.as_bytes(), &bal)?;
-                }
+                FROZEN_TOKENS.update(deps.storage, denom.as_bytes(), |tokens| -> StdResult<_> {
+                    Ok(tokens.unwrap_or_default() + bal)
+                })?;
                 attrs.push(attr(
                     "action",
                     "provwasm.contracts.custom_marker.partial_freeze",
@@ -597,45 +674,40 @@ fn try_partial_freeze(
                     return Err(ContractError::Unauthorized { err });
                 }
 
-                // Removed from frozen tokens that are unfreezed
-                FROZEN_TOKENS.update(
-                    deps.storage,
-                    denom.as_bytes(),
-                    |bals_opt| -> Result<_, ContractError> {
-                        match bals_opt {
-                            Some(mut bals) => Ok({
-                                bals -= bal;
-                                bals
-                            }),
-                            None => Err(ContractError::NotFound {
-                                addr: param.address.clone(),
-                            }),
-                        }
-                    },
-                )?;
-
-                // Reducing partial freeze balance
-                PARTIAL_FREEZE.update(
-                    deps.storage,
-                    &key,
-                    |bals_opt| -> Result<_, ContractError> {
-                        match bals_opt {
-                            Some(mut bals) => Ok({
-                                bals -= bal;
-                                rem_bal = bals;
-                                bals
-                            }),
-                            None => Err(ContractError::NotFound {
-                                addr: param.address.clone(),
-                            }),
-                        }
-                    },
-                )?;
+                let mut lots = FREEZE_LOTS.may_load(deps.storage, &key)?.ok_or_else(|| {
+                    ContractError::NotFound {
+                        addr: param.address.clone(),
+                    }
+                })?;
+                prune_freeze_lots(deps.storage, &denom, &mut lots, now)?;
+
+                // Releasing the latest freezes with the reason first
+                let mut remaining = bal;
+                for lot in lots.iter_mut().rev() {
+                    if param.reason.is_some() && lot.reason != param.reason {
+                        continue;
+                    }
+                    let released = lot.amount.min(remaining);
+                    lot.amount -= released;
+                    remaining -= released;
+                }
+                if !remaining.is_zero() {
+                    return Err(ContractError::UnfreezeExceedsFrozen {
+                        address: param.address,
+                        frozen: bal - remaining,
+                    });
+                }
+                lots.retain(|lot| !lot.amount.is_zero());
 
-                // Removing the address and balance pair from the partial freeze list, if amount becomes zero
-                if rem_bal.is_zero() {
-                    PARTIAL_FREEZE.remove(deps.storage, &key);
+                // Removing the address from the partial freeze list, if nothing stays frozen
+                if lots.is_empty() {
+                    FREEZE_LOTS.remove(deps.storage, &key);
+                } else {
+                    FREEZE_LOTS.save(deps.storage, &key, &lots)?;
                 }
+
+                // Removed from frozen tokens that are unfreezed
+                release_frozen_tokens(deps.storage, &denom, bal)?;
                 attrs.push(attr(
                     "action",
                     "provwasm.contracts.custom_marker.partial_unfreeze",
@@ -653,125 +725,1072 @@ fn try_partial_freeze(
 /// Update country code.
 /// This list contains set of country codes that are authorised to transact on this platform.
 /// Both add and remove can be performed by this single entry function.
-/// For addition `update_type` will be `UpdateType::Add(u8)`
-/// For removal `update_type` will be `UpdateType::Remove(u8)`
+/// For addition `update_type` will be `UpdateType::Add(Countries)`
+/// For removal `update_type` will be `UpdateType::Remove(Countries)`
+/// A region group is added or removed as a unit; its codes already in, respectively not in,
+/// the list are skipped.
 ///  
 /// Fails when:-
 ///     - caller is not sub_admin
 ///     - denom config (also known as token config) is not available for particular denom
+///     - country code is not an ISO 3166-1 alpha-2 or numeric code
+///     - region group doesn't exist
+///     - the list already contains the country code for addition
+///     - the list doesn't have the country code for removal
 ///
 /// Emits event:-
-///     - when update_type = UpdateType::Add(u8), then
+///     - when update_type = UpdateType::Add(Countries), then
 ///         1. provwasm.contracts.custom_marker.add_country_code
 ///         2. country_code
-///     - when update_type = UpdateType::Remove(u8), then
+///     - when update_type = UpdateType::Remove(Countries), then
 ///         1. provwasm.contracts.custom_marker.remove_country_code
 ///         2. country_code
 fn try_update_country_code(
     deps: DepsMut<ProvenanceQuery>,
-    update_type: UpdateType<u8>,
+    update_type: UpdateType<Countries>,
+    denom: String,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update country code
+    is_subadmin(&deps, sender)?;
+
+    let mut denom_config = DENOM_CONFIG
+        .may_load(deps.storage, denom.as_bytes())?
+        .ok_or_else(|| ContractError::MissingDenomConfig {
+            denom: denom.clone(),
+        })?;
+
+    let (adding, countries) = match update_type {
+        UpdateType::Add(countries) => (true, countries),
+        UpdateType::Remove(countries) => (false, countries),
+    };
+
+    let mut codes = Vec::new();
+    match countries {
+        Countries::Code(code) => {
+            let code = code.numeric()?;
+            if adding {
+                add_country_codes(&mut denom_config.country_codes, code)?;
+            } else {
+                remove_country_codes(&mut denom_config.country_codes, code)?;
+            }
+            codes.push(code);
+        }
+        Countries::Region(name) => {
+            for code in load_region(deps.storage, &name)? {
+                if adding == denom_config.country_codes.contains(&code) {
+                    continue;
+                }
+                if adding {
+                    denom_config.country_codes.push(code);
+                } else {
+                    denom_config.country_codes.retain(|cd| cd.ne(&code));
+                }
+                codes.push(code);
+            }
+        }
+    }
+    DENOM_CONFIG.save(deps.storage, denom.as_bytes(), &denom_config)?;
+
+    let action = if adding {
+        "provwasm.contracts.custom_marker.add_country_code"
+    } else {
+        "provwasm.contracts.custom_marker.remove_country_code"
+    };
+    let mut attrs = vec![attr("action", action)];
+    attrs.extend(
+        codes
+            .iter()
+            .map(|code| attr("country_code", code.to_string())),
+    );
+    let res = Response::<ProvenanceMsg>::new().add_attributes(attrs);
+
+    Ok(res)
+}
+
+/// Set the countries of a region group, which `update_country_code` adds or removes as a
+/// unit. Names are case insensitive; an empty list removes the group.
+///
+/// Fails when:-
+///     - caller is not sub_admin
+///     - country code is not an ISO 3166-1 alpha-2 or numeric code
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_region
+///     - name
+///     - country_codes
+fn try_update_region(
+    deps: DepsMut<ProvenanceQuery>,
+    name: String,
+    codes: Vec<CountryCode>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update regions
+    is_subadmin(&deps, sender)?;
+
+    let name = name.to_ascii_lowercase();
+    let mut country_codes: Vec<u16> = Vec::new();
+    for code in codes {
+        let code = code.numeric()?;
+        if !country_codes.contains(&code) {
+            country_codes.push(code);
+        }
+    }
+
+    if country_codes.is_empty() {
+        REGIONS.remove(deps.storage, &name);
+    } else {
+        REGIONS.save(deps.storage, &name, &country_codes)?;
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute("action", "provwasm.contracts.custom_marker.update_region")
+        .add_attribute("name", name)
+        .add_attribute("country_codes", format!("{:?}", country_codes));
+
+    Ok(res)
+}
+
+/// Update token limit.
+/// This list contains token limit, i.e., the maximum permissible token that an account can hold.
+///  
+/// Fails when:-
+///     - caller is not sub_admin
+///     - denom config (also known as token config) is not available for particular denom
+///
+/// Emits event:-
+///     - update_token_limit
+///     - new_limit
+fn try_update_token_limit(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    limit: Uint128,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update token limit
+    is_subadmin(&deps, sender)?;
+
+    // Setting new token_limit
+    DENOM_CONFIG.update(
+        deps.storage,
+        denom.clone().as_bytes(),
+        |denom_config_opt: Option<DenomConfig>| -> Result<_, ContractError> {
+            match denom_config_opt {
+                Some(mut denom_config) => {
+                    denom_config.token_limit = limit;
+                    Ok(denom_config)
+                }
+                None => Err(ContractError::MissingDenomConfig { denom }),
+            }
+        },
+    )?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.update_token_limit",
+        )
+        .add_attribute("new_limit", limit);
+
+    Ok(res)
+}
+
+/// Update the lock-up period of a denom. Every later mint to an address is locked as a lot
+/// for this many seconds; a zero period stops locking new mints.
+///
+/// Fails when:-
+///     - caller is not sub_admin
+///     - denom config (also known as token config) is not available for particular denom
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_lockup_period
+///     - period
+fn try_update_lockup_period(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    period: Uint64,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update lockup period
+    is_subadmin(&deps, sender)?;
+
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    if period.is_zero() {
+        LOCKUP_PERIOD.remove(deps.storage, denom.as_bytes());
+    } else {
+        LOCKUP_PERIOD.save(deps.storage, denom.as_bytes(), &period)?;
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.update_lockup_period",
+        )
+        .add_attribute("period", period);
+
+    Ok(res)
+}
+
+/// Set or unset the vesting schedules of addresses. Unvested tokens can't be sent.
+/// This function supports batch operations, i.e. multiple addresses can be updated simultaneously.
+///
+/// Fails when:-
+///     - caller is not sub_admin or issuer
+///     - schedule has a zero amount or doesn't satisfy start <= cliff <= end
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_vesting
+///     - set
+///     - unset
+fn try_update_vesting(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    params: Vec<VestingParams>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Ensuring authorized sender
+    if is_issuer(&deps, denom.clone(), sender.clone()).is_err()
+        && is_subadmin(&deps, sender.clone()).is_err()
+    {
+        let err = format!(
+            "Address `{}`: Don't have Issuer or Sub Admin rights!",
+            &sender
+        );
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    let mut set: Vec<Addr> = Vec::new();
+    let mut unset: Vec<Addr> = Vec::new();
+
+    for param in params {
+        let key = Key::new(denom.clone(), param.address.clone()).as_bytes()?;
+
+        match param.schedule {
+            UpdateKind::Set(schedule) => {
+                schedule.validate()?;
+                VESTING.save(deps.storage, &key, &schedule)?;
+                set.push(param.address);
+            }
+            UpdateKind::Unset {} => {
+                VESTING.remove(deps.storage, &key);
+                unset.push(param.address);
+            }
+        }
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute("action", "provwasm.contracts.custom_marker.update_vesting")
+        .add_attribute("set", format!("{:?}", set))
+        .add_attribute("unset", format!("{:?}", unset));
+
+    Ok(res)
+}
+
+/// Replace the transfer rules of a denom. Send, mint_to, force_transfer and DvP delivery run
+/// them in order after the built-in checks; an empty list removes every rule.
+///
+/// Fails when:-
+///     - caller is not sub_admin
+///     - denom config (also known as token config) is not available for particular denom
+///     - a country pair has a code that is not an ISO 3166-1 numeric code
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_transfer_rules
+///     - rules
+fn try_update_transfer_rules(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    rules: Vec<TransferRule>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update transfer rules
+    is_subadmin(&deps, sender)?;
+
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    // ensuring country pairs are ISO 3166-1 numeric codes
+    for rule in &rules {
+        if let TransferRule::BlockedCountryPairs { pairs } = rule {
+            for pair in pairs {
+                ensure_country_codes(&[pair.from, pair.to])?;
+            }
+        }
+    }
+
+    if rules.is_empty() {
+        TRANSFER_RULES.remove(deps.storage, denom.as_bytes());
+    } else {
+        TRANSFER_RULES.save(deps.storage, denom.as_bytes(), &rules)?;
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.update_transfer_rules",
+        )
+        .add_attribute("rules", format!("{:?}", rules));
+
+    Ok(res)
+}
+
+/// Set or unset the investor categories, i.e. accreditation tiers, of addresses, used by the
+/// tier policies and the `investor_categories` transfer rule. The verifier contract of the
+/// denom sets them as attestations.
+/// This function supports batch operations, i.e. multiple addresses can be updated simultaneously.
+///
+/// Fails when:-
+///     - caller is not sub_admin, issuer, transfer agent or verifier of the denom
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_investor_category
+///     - set
+///     - unset
+fn try_update_investor_category(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    params: Vec<InvestorCategoryParams>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Ensuring authorized sender
+    let verifier = TIER_VERIFIERS.may_load(deps.storage, denom.as_bytes())?;
+    if is_issuer(&deps, denom.clone(), sender.clone()).is_err()
+        && is_transfer_agent(&deps, denom.clone(), sender.clone()).is_err()
+        && is_subadmin(&deps, sender.clone()).is_err()
+        && verifier.as_ref() != Some(&sender)
+    {
+        let err = format!(
+            "Address `{}`: Don't have Issuer, Transfer, Sub Admin or Verifier rights!",
+            &sender
+        );
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    let mut set: Vec<Addr> = Vec::new();
+    let mut unset: Vec<Addr> = Vec::new();
+
+    for param in params {
+        let key = Key::new(denom.clone(), param.address.clone()).as_bytes()?;
+
+        match param.category {
+            UpdateKind::Set(category) => {
+                INVESTOR_CATEGORIES.save(deps.storage, &key, &category)?;
+                set.push(param.address);
+            }
+            UpdateKind::Unset {} => {
+                INVESTOR_CATEGORIES.remove(deps.storage, &key);
+                unset.push(param.address);
+            }
+        }
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.update_investor_category",
+        )
+        .add_attribute("set", format!("{:?}", set))
+        .add_attribute("unset", format!("{:?}", unset));
+
+    Ok(res)
+}
+
+/// Replace the investor tier policies of a denom. Holders of a tier with a policy hold up to
+/// its token_limit instead of the denom's, and only take part in its actions; holders without
+/// a tier keep the denom's token_limit. An empty list removes every policy.
+///
+/// Fails when:-
+///     - caller is not sub_admin
+///     - denom config (also known as token config) is not available for particular denom
+///     - a tier has more than one policy
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_tier_policies
+///     - policies
+fn try_update_tier_policies(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    policies: Vec<TierPolicy>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update tier policies
+    is_subadmin(&deps, sender)?;
+
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    // ensuring one policy per tier
+    for (index, policy) in policies.iter().enumerate() {
+        if policies[..index]
+            .iter()
+            .any(|other| other.category == policy.category)
+        {
+            return Err(ContractError::DuplicateTierPolicy {
+                category: policy.category.clone(),
+            });
+        }
+    }
+
+    if policies.is_empty() {
+        TIER_POLICIES.remove(deps.storage, denom.as_bytes());
+    } else {
+        TIER_POLICIES.save(deps.storage, denom.as_bytes(), &policies)?;
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.update_tier_policies",
+        )
+        .add_attribute("policies", format!("{:?}", policies));
+
+    Ok(res)
+}
+
+/// Set or unset the verifier contract of a denom, which attests investor tiers through
+/// `update_investor_category`.
+///
+/// Fails when:-
+///     - caller is not sub_admin
+///     - denom config (also known as token config) is not available for particular denom
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.update_tier_verifier
+///     - verifier
+fn try_update_tier_verifier(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    verifier: UpdateKind<Addr>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin update the verifier
+    is_subadmin(&deps, sender)?;
+
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    let verifier = match verifier {
+        UpdateKind::Set(verifier) => {
+            TIER_VERIFIERS.save(deps.storage, denom.as_bytes(), &verifier)?;
+            verifier.to_string()
+        }
+        UpdateKind::Unset {} => {
+            TIER_VERIFIERS.remove(deps.storage, denom.as_bytes());
+            String::default()
+        }
+    };
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.update_tier_verifier",
+        )
+        .add_attribute("verifier", verifier);
+
+    Ok(res)
+}
+
+/// Add or remove addresses in the holder registry of a denom by their current balances. Mint,
+/// burn_from, send, force_transfer and DvP keep the registry current; this covers balances
+/// moved before the registry existed or outside the contract.
+/// This function supports batch operations, i.e. multiple addresses can be synced simultaneously.
+///
+/// Fails when:-
+///     - caller is not sub_admin
+///     - denom config (also known as token config) is not available for particular denom
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.sync_holders
+///     - holders
+///     - removed
+fn try_sync_holders(
+    mut deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    addresses: Vec<Addr>,
+    sender: Addr,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Only Subadmin sync holders
+    is_subadmin(&deps, sender)?;
+
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    let mut holders: Vec<Addr> = Vec::new();
+    let mut removed: Vec<Addr> = Vec::new();
+
+    for address in addresses {
+        if sync_holder(&mut deps, &denom, &address)? {
+            holders.push(address);
+        } else {
+            removed.push(address);
+        }
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute("action", "provwasm.contracts.custom_marker.sync_holders")
+        .add_attribute("holders", format!("{:?}", holders))
+        .add_attribute("removed", format!("{:?}", removed));
+
+    Ok(res)
+}
+
+/// Start a corporate action of a denom. Its holders are processed a batch at a time by
+/// `process_corporate_action`; until the last batch, transfers, mints and burns of the denom
+/// are refused.
+///
+/// Fails when:-
+///     - caller is not sub_admin or issuer
+///     - action id is empty or already taken
+///     - a ratio term is zero, a split keeps balances or a redenomination keeps the denom
+///     - denom config is not available for the denom, or the new denom of a redenomination
+///     - a corporate action of the denom is in progress
+///     - a DvP order of the denom is open, as the action doesn't scale orders or their escrow
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.start_corporate_action
+///     - action_id
+///     - denom
+///     - corporate_action
+fn try_start_corporate_action(
+    deps: DepsMut<ProvenanceQuery>,
+    params: CorporateActionParams,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Ensuring authorized sender
+    if is_issuer(&deps, params.denom.clone(), sender.clone()).is_err()
+        && is_subadmin(&deps, sender.clone()).is_err()
+    {
+        let err = format!(
+            "Address `{}`: Don't have Issuer or Sub Admin rights!",
+            &sender
+        );
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    let action_id = validate_string(params.action_id.clone(), "action_id")?;
+    if CORPORATE_ACTIONS.has(deps.storage, action_id.as_bytes()) {
+        return Err(ContractError::CorporateActionExists { action_id });
+    }
+
+    let (numerator, denominator) = params.action.ratio();
+    let err = match &params.action {
+        _ if numerator == 0 || denominator == 0 => Some("ratio terms must be greater than zero"),
+        CorporateAction::Redenominate { new_denom, .. } if *new_denom == params.denom => {
+            Some("new_denom must differ from denom")
+        }
+        CorporateAction::Split { .. } | CorporateAction::ReverseSplit { .. }
+            if numerator == denominator =>
+        {
+            Some("ratio must differ from one")
+        }
+        _ => None,
+    };
+    if let Some(err) = err {
+        return Err(ContractError::InvalidCorporateAction { err: err.into() });
+    }
+
+    let cash_in_lieu = match params.cash_in_lieu.clone() {
+        Some(CashInLieuPrice {
+            payment_asset,
+            price,
+        }) => Some(CashInLieuPrice {
+            payment_asset: match payment_asset {
+                PaymentAsset::Native { denom } => PaymentAsset::Native {
+                    denom: validate_string(denom, "payment denom")?,
+                },
+                PaymentAsset::Cw20 { address } => PaymentAsset::Cw20 {
+                    address: deps.api.addr_validate(address.as_str())?,
+                },
+            },
+            price,
+        }),
+        None => None,
+    };
+
+    for denom in [
+        params.denom.as_str(),
+        params.action.target_denom(&params.denom),
+    ] {
+        if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+            return Err(ContractError::MissingDenomConfig {
+                denom: denom.to_string(),
+            });
+        }
+    }
+
+    ensure_no_corporate_action(deps.storage, params.denom.clone())?;
+    ensure_no_open_dvp_orders(deps.storage, params.denom.clone())?;
+
+    let record = CorporateActionRecord::new(
+        CorporateActionParams {
+            action_id,
+            cash_in_lieu,
+            ..params
+        },
+        Uint64::new(env.block.time.seconds()),
+    );
+    CORPORATE_ACTIONS.save(deps.storage, record.action_id.as_bytes(), &record)?;
+    ACTIVE_CORPORATE_ACTION.save(deps.storage, record.denom.as_bytes(), &record.action_id)?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.start_corporate_action",
+        )
+        .add_attribute("action_id", &record.action_id)
+        .add_attribute("denom", &record.denom)
+        .add_attribute("corporate_action", format!("{:?}", record.action));
+
+    Ok(res)
+}
+
+/// Apply a corporate action to the next batch of holders of its denom, in address order.
+/// Each balance is multiplied by the ratio of the action and rounded down, minting or burning
+/// the difference; a redenomination burns the whole balance and mints the new denom. The
+/// fractional token rounded off is recorded as cash in lieu owed to the holder, at the price
+/// of the action. Partial freezes, lock-up lots and vesting of the holder scale alike. The
+/// batch that reaches the last holder completes the action.
+///
+/// Fails when:-
+///     - caller is not sub_admin or issuer
+///     - corporate action doesn't exist, is completed or is cancelled
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.process_corporate_action
+///     - action_id
+///     - holders
+///     - status
+fn try_process_corporate_action(
+    deps: DepsMut<ProvenanceQuery>,
+    action_id: String,
+    limit: Option<u32>,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let mut record = load_corporate_action(deps.storage, &action_id)?;
+
+    // Ensuring authorized sender
+    if is_issuer(&deps, record.denom.clone(), sender.clone()).is_err()
+        && is_subadmin(&deps, sender.clone()).is_err()
+    {
+        let err = format!(
+            "Address `{}`: Don't have Issuer or Sub Admin rights!",
+            &sender
+        );
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    ensure_corporate_action_in_progress(&record)?;
+
+    let denom = record.denom.clone();
+    let target_denom = record.action.target_denom(&denom).to_string();
+    let (numerator, denominator) = record.action.ratio();
+    let limit = limit.unwrap_or(DEFAULT_BATCH_SIZE).min(MAX_BATCH_SIZE) as usize;
+
+    // Loading one holder more than the batch tells whether the batch is the last
+    let mut holders = holders_after(deps.storage, &denom, record.last_holder.as_ref(), limit + 1)?;
+    let completed = holders.len() <= limit;
+    holders.truncate(limit);
+
+    let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
+    for holder in &holders {
+        let bal = get_consolidated_balance(deps.as_ref(), holder.clone(), denom.clone())?;
+        let scaled = bal.multiply_ratio(numerator, denominator);
+        let (burned, minted) = if target_denom != denom {
+            (bal, scaled)
+        } else if scaled > bal {
+            (Uint128::zero(), scaled - bal)
+        } else {
+            (bal - scaled, Uint128::zero())
+        };
+
+        // Accounting the fractional token rounded off as cash in lieu
+        let remainder =
+            bal.u128() % u128::from(denominator) * u128::from(numerator) % u128::from(denominator);
+        if remainder > 0 {
+            let cash = CashInLieu {
+                address: holder.clone(),
+                fraction: Decimal::from_ratio(remainder, denominator),
+                amount: record
+                    .cash_in_lieu
+                    .as_ref()
+                    .map(|cash_in_lieu| cash_in_lieu.price.multiply_ratio(remainder, denominator))
+                    .unwrap_or_default(),
+            };
+            record.fractions += cash.fraction;
+            record.cash_in_lieu_total += cash.amount;
+            CASH_IN_LIEU.save(deps.storage, (&action_id, holder), &cash)?;
+        }
+
+        if !burned.is_zero() {
+            update_minted_tokens(deps.storage, denom.clone(), UpdateType::Remove(burned))?;
+            let querier = ProvenanceQuerier::new(&deps.querier);
+            msgs.extend(burn_from(
+                denom.clone(),
+                MintBurnData {
+                    address: holder.clone(),
+                    amount: burned,
+                },
+                env.contract.address.clone(),
+                &querier,
+            )?);
+        }
+        if !minted.is_zero() {
+            update_minted_tokens(deps.storage, target_denom.clone(), UpdateType::Add(minted))?;
+            msgs.extend(mint_to(
+                target_denom.clone(),
+                MintBurnData {
+                    address: holder.clone(),
+                    amount: minted,
+                },
+                env.contract.address.clone(),
+            )?);
+        }
+        record.burned += burned;
+        record.minted += minted;
+
+        scale_holder_state(
+            deps.storage,
+            &denom,
+            &target_denom,
+            holder,
+            (numerator, denominator),
+        )?;
+        record_corporate_action(deps.storage, &denom, &target_denom, holder, scaled)?;
+    }
+
+    record.holders_processed += holders.len() as u64;
+    if let Some(holder) = holders.last() {
+        record.last_holder = Some(holder.clone());
+    }
+    if completed {
+        record.status = CorporateActionStatus::Completed;
+        record.completed_at = Some(Uint64::new(env.block.time.seconds()));
+        ACTIVE_CORPORATE_ACTION.remove(deps.storage, denom.as_bytes());
+    }
+    CORPORATE_ACTIONS.save(deps.storage, action_id.as_bytes(), &record)?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_messages(msgs)
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.process_corporate_action",
+        )
+        .add_attribute("action_id", &action_id)
+        .add_attribute("holders", format!("{:?}", holders))
+        .add_attribute("status", record.status.to_string());
+
+    Ok(res)
+}
+
+/// Cancel a corporate action in progress, e.g. one stuck on a failing batch. Transfers, mints
+/// and burns of the denom are allowed again; holders already processed keep their scaled
+/// balances and cash in lieu.
+///
+/// Fails when:-
+///     - caller is not sub_admin or issuer
+///     - corporate action doesn't exist, is completed or is cancelled
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.cancel_corporate_action
+///     - action_id
+///     - holders_processed
+fn try_cancel_corporate_action(
+    deps: DepsMut<ProvenanceQuery>,
+    action_id: String,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let mut record = load_corporate_action(deps.storage, &action_id)?;
+
+    // Ensuring authorized sender
+    if is_issuer(&deps, record.denom.clone(), sender.clone()).is_err()
+        && is_subadmin(&deps, sender.clone()).is_err()
+    {
+        let err = format!(
+            "Address `{}`: Don't have Issuer or Sub Admin rights!",
+            &sender
+        );
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    ensure_corporate_action_in_progress(&record)?;
+
+    record.status = CorporateActionStatus::Cancelled;
+    record.completed_at = Some(Uint64::new(env.block.time.seconds()));
+    ACTIVE_CORPORATE_ACTION.remove(deps.storage, record.denom.as_bytes());
+    CORPORATE_ACTIONS.save(deps.storage, action_id.as_bytes(), &record)?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.cancel_corporate_action",
+        )
+        .add_attribute("action_id", &action_id)
+        .add_attribute("holders_processed", record.holders_processed.to_string());
+
+    Ok(res)
+}
+
+/// Propose the recovery of a lost address to a new address, counting as the first approval.
+/// Once the recovery threshold of the denom is reached by `approve_recovery`, the balance,
+/// partial freezes, lock-up lots, vesting, whitelisted country code, investor category and
+/// open DvP orders of the lost address move to the new address, and the lost address is
+/// freezed permanently.
+///
+/// Fails when:-
+///     - caller is not sub_admin, issuer, transfer agent or having force_transfer access
+///     - evidence hash is empty, or the lost and new addresses are the same
+///     - denom config (also known as token config) is not available for particular denom
+///     - new address is freezed
+///     - new address has a balance, whitelisted country code, investor category, vesting
+///       schedule, partial freeze or lock-up lot of the denom
+///     - lost or new address is recovered already
+///     - a recovery of the lost address is pending
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.recover_account
+///     - lost
+///     - new
+///     - evidence_hash
+fn try_recover_account(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    lost: Addr,
+    new: Addr,
+    evidence_hash: String,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Ensuring authorized sender
+    is_recovery_agent(&deps, denom.clone(), sender.clone())?;
+
+    let evidence_hash = validate_string(evidence_hash, "evidence_hash")?;
+    if lost.eq(&new) {
+        return Err(ContractError::InvalidRecovery {
+            err: "lost and new addresses must differ".into(),
+        });
+    }
+
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    // ensure new address is not freezed
+    ensure_not_freezed(deps.storage, vec![new.clone()], denom.as_bytes())?;
+    ensure_recovery_target(deps.as_ref(), &denom, &new)?;
+
+    for address in [&lost, &new] {
+        let key = Key::new(denom.clone(), address.clone()).as_bytes()?;
+        if RECOVERED_ACCOUNTS.has(deps.storage, &key) {
+            return Err(ContractError::AccountRecovered {
+                address: address.clone(),
+            });
+        }
+    }
+
+    let key = Key::new(denom.clone(), lost.clone()).as_bytes()?;
+    if RECOVERIES.has(deps.storage, &key) {
+        return Err(ContractError::RecoveryPending {
+            denom,
+            address: lost,
+        });
+    }
+
+    let recovery = AccountRecovery {
+        denom,
+        lost,
+        new,
+        evidence_hash,
+        approvals: vec![sender],
+        created_at: Uint64::new(env.block.time.seconds()),
+    };
+    RECOVERIES.save(deps.storage, &key, &recovery)?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute("action", "provwasm.contracts.custom_marker.recover_account")
+        .add_attribute("lost", &recovery.lost)
+        .add_attribute("new", &recovery.new)
+        .add_attribute("evidence_hash", &recovery.evidence_hash);
+
+    Ok(res)
+}
+
+/// Approve the pending recovery of a lost address. Approvals of callers who have lost their
+/// roles since are dropped, and the approval that reaches the recovery threshold of the denom
+/// carries out the recovery.
+///
+/// Fails when:-
+///     - caller is not sub_admin, issuer, transfer agent or having force_transfer access
+///     - no recovery of the lost address is pending
+///     - caller has approved the recovery already
+///     - on recovery: a corporate action of the denom is in progress, the new address has
+///       gained state of the denom since the proposal, or it is the other party of an open
+///       DvP order of the lost address
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.approve_recovery
+///     - lost
+///     - approvals
+///     - recovered
+fn try_approve_recovery(
+    deps: DepsMut<ProvenanceQuery>,
+    denom: String,
+    lost: Addr,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Ensuring authorized sender
+    is_recovery_agent(&deps, denom.clone(), sender.clone())?;
+
+    let key = Key::new(denom.clone(), lost.clone()).as_bytes()?;
+    let mut recovery = RECOVERIES.may_load(deps.storage, &key)?.ok_or_else(|| {
+        ContractError::RecoveryNotFound {
+            denom: denom.clone(),
+            address: lost.clone(),
+        }
+    })?;
+
+    // Counting only the approvals of callers still holding a recovery role
+    recovery
+        .approvals
+        .retain(|approver| is_recovery_agent(&deps, denom.clone(), approver.clone()).is_ok());
+    if recovery.approvals.contains(&sender) {
+        return Err(ContractError::AlreadyApproved { address: sender });
+    }
+    recovery.approvals.push(sender);
+
+    let threshold = get_recovery_threshold(deps.storage, &denom)?;
+    let recovered = recovery.approvals.len() >= usize::from(threshold);
+
+    let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
+    if recovered {
+        ensure_no_corporate_action(deps.storage, denom.clone())?;
+
+        let bal = get_consolidated_balance(deps.as_ref(), lost.clone(), denom.clone())?;
+        ensure_recovery_target(deps.as_ref(), &denom, &recovery.new)?;
+        move_holder_state(deps.storage, &denom, &lost, &recovery.new)?;
+        move_dvp_orders(deps.storage, &denom, &lost, &recovery.new)?;
+        record_recovery(deps.storage, &denom, &lost, &recovery.new, bal)?;
+
+        // Freezing the lost address permanently
+        FREEZE_LIST.update(
+            deps.storage,
+            denom.as_bytes(),
+            |addresses| -> StdResult<_> {
+                let mut addresses = addresses.unwrap_or_default();
+                if !addresses.contains(&lost) {
+                    addresses.push(lost.clone());
+                    addresses.sort();
+                }
+                Ok(addresses)
+            },
+        )?;
+        RECOVERED_ACCOUNTS.save(deps.storage, &key, &recovery.new)?;
+        RECOVERIES.remove(deps.storage, &key);
+
+        if !bal.is_zero() {
+            msgs.push(cm_transfer_marker_coins(
+                bal.u128(),
+                &denom,
+                recovery.new.clone(),
+                lost.clone(),
+                env.contract.address,
+            )?);
+        }
+    } else {
+        RECOVERIES.save(deps.storage, &key, &recovery)?;
+    }
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_messages(msgs)
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.approve_recovery",
+        )
+        .add_attribute("lost", lost)
+        .add_attribute("approvals", format!("{:?}", recovery.approvals))
+        .add_attribute("recovered", recovered.to_string());
+
+    Ok(res)
+}
+
+/// Cancel the pending recovery of a lost address, e.g. one proposed with a wrong new address.
+///
+/// Fails when:-
+///     - caller is not sub_admin, issuer, transfer agent or having force_transfer access
+///     - no recovery of the lost address is pending
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.cancel_recovery
+///     - lost
+fn try_cancel_recovery(
+    deps: DepsMut<ProvenanceQuery>,
     denom: String,
+    lost: Addr,
     sender: Addr,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
-    // Only Subadmin update country code
-    is_subadmin(&deps, sender)?;
-
-    let attrs = match update_type {
-        UpdateType::Add(code) => {
-            // Adding new country code to the list
-            DENOM_CONFIG.update(
-                deps.storage,
-                denom.clone().as_bytes(),
-                |denom_config_opt: Option<DenomConfig>| -> Result<_, ContractError> {
-                    match denom_config_opt {
-                        Some(mut denom_config) => {
-                            add_country_codes(&mut denom_config.country_codes, code)?;
-                            Ok(denom_config)
-                        }
-                        None => Err(ContractError::MissingDenomConfig { denom }),
-                    }
-                },
-            )?;
-
-            vec![
-                attr(
-                    "action",
-                    "provwasm.contracts.custom_marker.add_country_code",
-                ),
-                attr("country_code", code.to_string()),
-            ]
-        }
-        UpdateType::Remove(code) => {
-            // Removing country code from the list
-            DENOM_CONFIG.update(
-                deps.storage,
-                denom.clone().as_bytes(),
-                |denom_config_opt: Option<DenomConfig>| -> Result<_, ContractError> {
-                    match denom_config_opt {
-                        Some(mut denom_config) => {
-                            remove_country_codes(&mut denom_config.country_codes, code)?;
-                            Ok(denom_config)
-                        }
-                        None => Err(ContractError::MissingDenomConfig { denom }),
-                    }
-                },
-            )?;
-
-            vec![
-                attr(
-                    "action",
-                    "provwasm.contracts.custom_marker.remove_country_code",
-                ),
-                attr("country_code", code.to_string()),
-            ]
-        }
-    };
+    // Ensuring authorized sender
+    is_recovery_agent(&deps, denom.clone(), sender)?;
+
+    let key = Key::new(denom.clone(), lost.clone()).as_bytes()?;
+    if !RECOVERIES.has(deps.storage, &key) {
+        return Err(ContractError::RecoveryNotFound {
+            denom,
+            address: lost,
+        });
+    }
+    RECOVERIES.remove(deps.storage, &key);
 
-    let res = Response::<ProvenanceMsg>::new().add_attributes(attrs);
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute("action", "provwasm.contracts.custom_marker.cancel_recovery")
+        .add_attribute("lost", lost);
 
     Ok(res)
 }
 
-/// Update token limit.
-/// This list contains token limit, i.e., the maximum permissible token that an account can hold.
-///  
+/// Update the approvals an account recovery of a denom needs, 2 by default.
+///
 /// Fails when:-
 ///     - caller is not sub_admin
 ///     - denom config (also known as token config) is not available for particular denom
+///     - threshold is lesser than 2
 ///
 /// Emits event:-
-///     - update_token_limit
-///     - new_limit
-fn try_update_token_limit(
+///     - provwasm.contracts.custom_marker.update_recovery_threshold
+///     - threshold
+fn try_update_recovery_threshold(
     deps: DepsMut<ProvenanceQuery>,
     denom: String,
-    limit: Uint128,
+    threshold: u8,
     sender: Addr,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
-    // Only Subadmin update token limit
+    // Only Subadmin update recovery threshold
     is_subadmin(&deps, sender)?;
 
-    // Setting new token_limit
-    DENOM_CONFIG.update(
-        deps.storage,
-        denom.clone().as_bytes(),
-        |denom_config_opt: Option<DenomConfig>| -> Result<_, ContractError> {
-            match denom_config_opt {
-                Some(mut denom_config) => {
-                    denom_config.token_limit = limit;
-                    Ok(denom_config)
-                }
-                None => Err(ContractError::MissingDenomConfig { denom }),
-            }
-        },
-    )?;
+    if !DENOM_CONFIG.has(deps.storage, denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig { denom });
+    }
+
+    if threshold < DEFAULT_RECOVERY_THRESHOLD {
+        return Err(ContractError::InvalidRecoveryThreshold { threshold });
+    }
+    RECOVERY_THRESHOLD.save(deps.storage, denom.as_bytes(), &threshold)?;
 
     let res = Response::<ProvenanceMsg>::new()
         .add_attribute(
             "action",
-            "provwasm.contracts.custom_marker.update_token_limit",
+            "provwasm.contracts.custom_marker.update_recovery_threshold",
         )
-        .add_attribute("new_limit", limit);
+        .add_attribute("threshold", threshold.to_string());
 
     Ok(res)
 }
@@ -782,10 +1801,13 @@ fn try_update_token_limit(
 /// Fails when:-
 ///     - sender doesn't have transfer access
 ///     - recipient is not whitelisted
-///     - recipient is freezed
+///     - sender or recipient is freezed
 ///     - amount is zero
 ///     - amount exceeds the alloted token_limit
-///     - unfreezed balance is lesser than requested amount
+///     - investor tier of the sender or recipient doesn't allow the action
+///     - amount exceeds the balance neither partially frozen nor held by lock-up lots or vesting
+///     - a transfer rule of the denom fails
+///     - a corporate action of the denom is in progress
 ///
 /// Emits event:-
 ///     - provwasm.contracts.custom_marker.send
@@ -793,35 +1815,25 @@ fn try_update_token_limit(
 ///     - to
 ///     - from
 fn try_send(
-    deps: DepsMut<ProvenanceQuery>,
+    mut deps: DepsMut<ProvenanceQuery>,
     amount: Uint128,
     denom: String,
     to: Addr,
     from: Addr,
     env: Env,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
-    // Ensuring authorized sender
-    has_transfer_access(&deps, denom.clone(), from.clone())?;
-
-    // Ensuring holding period is passed
-    ensure_holding_period_passed(deps.storage, denom.as_bytes(), env.block.time.seconds())?;
-
-    if env.contract.address.ne(&to) {
-        // ensuring country is authorized
-        ensure_authorized_country(deps.storage, denom.clone(), to.clone())?;
-    }
-
-    // ensure not freezed
-    ensure_not_freezed(deps.storage, vec![to.clone()], denom.as_bytes())?;
-
-    // ensure frozen balance and balance capital maintained
-    ensure_bal_maintained(
-        deps.as_ref(),
-        to.clone(),
-        from.clone(),
-        denom.clone(),
+    // Ensuring authorized sender and the transfer pipeline of the denom allows the transfer
+    let pipeline_transfer = Transfer {
+        denom: denom.clone(),
+        from: Some(from.clone()),
+        to: to.clone(),
         amount,
-    )?;
+        from_pending: Pending::default(),
+        to_pending: Pending::default(),
+    };
+    evaluate_send(deps.as_ref(), &env, &pipeline_transfer)
+        .map_err(|restriction| restriction.error)?;
+    record_transfer(&mut deps, &env, &pipeline_transfer)?;
 
     let transfer = cm_transfer_marker_coins(
         amount.u128(),
@@ -843,23 +1855,29 @@ fn try_send(
 
 /// Create and dispatch a message that will mint coins into address.
 /// This function supports batch operations, i.e. multiple addresses can be minted simultaneously.
+/// When the denom has a lock-up period, each mint is locked as a lot for that period.
 ///  
 /// Fails when:-
 ///     - caller is not sub_admin, issuer, tokenization agent or having mint access
 ///     - recipient is not whitelisted
 ///     - recipient is freezed
 ///     - amount is zero
-///     - amount exceeds the alloted token_limit
+///     - amount exceeds the alloted token_limit, net of earlier mints of the batch to the same
+///       address
+///     - investor tier of the recipient doesn't allow mint
+///     - a transfer rule of the denom fails
+///     - a corporate action of the denom is in progress
 ///
 /// Emits event:-
 ///     - provwasm.contracts.custom_marker.mint_to
 fn try_mint_to(
-    deps: DepsMut<ProvenanceQuery>,
+    mut deps: DepsMut<ProvenanceQuery>,
     mint_to_params: Vec<MintBurnParams>,
     sender: Addr,
-    contract_address: Addr,
+    env: Env,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
     let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
+    let mut pending = PendingBalances::default();
 
     for params in mint_to_params {
         // Ensuring authorized sender
@@ -876,39 +1894,40 @@ fn try_mint_to(
         }
 
         for mint_data in params.mint_burn_data {
-            // ensure token limit maintained.
-            ensure_token_limit(
-                deps.as_ref(),
-                mint_data.address.clone(),
-                params.denom.clone(),
-                mint_data.amount,
-            )?;
+            // Ensuring the transfer pipeline of the denom allows the mint, net of earlier mints
+            // of the batch to the same address
+            let pipeline_transfer = Transfer {
+                denom: params.denom.clone(),
+                from: None,
+                to: mint_data.address.clone(),
+                amount: mint_data.amount,
+                from_pending: Pending::default(),
+                to_pending: pending.get(&params.denom, &mint_data.address),
+            };
+            ensure_transfer_allowed(deps.as_ref(), &env, &pipeline_transfer)?;
+            record_transfer(&mut deps, &env, &pipeline_transfer)?;
+            pending.credit(&params.denom, &mint_data.address, mint_data.amount);
 
-            // ensure not freezed
-            ensure_not_freezed(
-                deps.storage,
-                vec![mint_data.address.clone()],
-                params.denom.as_bytes(),
-            )?;
-
-            // ensuring country is authorized
-            ensure_authorized_country(
+            // Add mint amount to pool
+            update_minted_tokens(
                 deps.storage,
                 params.denom.clone(),
-                mint_data.address.clone(),
+                UpdateType::Add(mint_data.amount),
             )?;
 
-            // Add mint amount to pool
-            update_minted_tokens(
+            // Locking the minted tokens
+            add_lockup_lot(
                 deps.storage,
                 params.denom.clone(),
-                UpdateType::Add(mint_data.amount),
+                mint_data.address.clone(),
+                mint_data.amount,
+                env.block.time.seconds(),
             )?;
 
             msgs.extend(mint_to(
                 params.denom.to_string(),
                 mint_data,
-                contract_address.clone(),
+                env.contract.address.clone(),
             )?);
         }
     }
@@ -920,136 +1939,440 @@ fn try_mint_to(
     Ok(res)
 }
 
-/// Create and dispatch a message that will mint coins into address and freeze that amount.
-/// This function supports batch operations, i.e. multiple addresses can be minted simultaneously.
-///  
+/// Open a delivery-versus-payment order between a seller and a buyer of marker tokens.
+/// Neither leg is escrowed yet; the seller delivers with `deliver_dvp` and the buyer pays
+/// with `pay_dvp` (or a cw20 `send`), in either order.
+///
 /// Fails when:-
-///     - caller is not sub_admin, issuer, tokenization agent or having mint access
-///     - recipient is not whitelisted
-///     - recipient is freezed
-///     - amount is zero
-///     - amount exceeds the alloted token_limit
+///     - caller is neither the buyer nor the seller
+///     - order id already exists
+///     - amount or price is zero, buyer is the seller or expiry has passed
+///     - denom config (also known as token config) is not available for particular denom
+///     - buyer is not whitelisted
+///     - buyer or seller is freezed
+///     - a corporate action of the denom is in progress
 ///
 /// Emits event:-
-///     - provwasm.contracts.custom_marker.delivery_vs_payment
-fn try_dvp(
+///     - provwasm.contracts.custom_marker.create_dvp_order
+///     - order_id
+///     - denom
+///     - amount
+///     - price
+///     - seller
+///     - buyer
+fn try_create_dvp_order(
     deps: DepsMut<ProvenanceQuery>,
-    denom: String,
-    mint_data: Vec<MintBurnData>,
+    params: DvpOrderParams,
     sender: Addr,
-    contract_address: Addr,
+    env: Env,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
-    let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
+    // Ensuring caller is a party of the order
+    if sender.ne(&params.buyer) && sender.ne(&params.seller) {
+        let err = format!("Address `{}`: Not a party of the DvP order!", &sender);
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    let order_id = validate_string(params.order_id.clone(), "order_id")?;
+    if DVP_ORDERS.has(deps.storage, order_id.as_bytes()) {
+        return Err(ContractError::DvpOrderExists { order_id });
+    }
+
+    let err = if params.amount.is_zero() || params.price.is_zero() {
+        Some("amount and price must be greater than zero")
+    } else if params.buyer.eq(&params.seller) {
+        Some("buyer and seller must differ")
+    } else if params.expiry.u64() <= env.block.time.seconds() {
+        Some("expiry must be in the future")
+    } else {
+        None
+    };
+    if let Some(err) = err {
+        return Err(ContractError::InvalidDvpOrder { err: err.into() });
+    }
+
+    let payment_asset = match params.payment_asset.clone() {
+        PaymentAsset::Native { denom } => PaymentAsset::Native {
+            denom: validate_string(denom, "payment denom")?,
+        },
+        PaymentAsset::Cw20 { address } => PaymentAsset::Cw20 {
+            address: deps.api.addr_validate(address.as_str())?,
+        },
+    };
+
+    if !DENOM_CONFIG.has(deps.storage, params.denom.as_bytes()) {
+        return Err(ContractError::MissingDenomConfig {
+            denom: params.denom,
+        });
+    }
+
+    ensure_no_corporate_action(deps.storage, params.denom.clone())?;
+
+    // ensuring buyer's country is authorized
+    ensure_authorized_country(deps.storage, params.denom.clone(), params.buyer.clone())?;
+
+    // ensure not freezed
+    ensure_not_freezed(
+        deps.storage,
+        vec![params.buyer.clone(), params.seller.clone()],
+        params.denom.as_bytes(),
+    )?;
+
+    let order = DvpOrder::new(DvpOrderParams {
+        order_id,
+        payment_asset,
+        ..params
+    });
+    DVP_ORDERS.save(deps.storage, order.order_id.as_bytes(), &order)?;
+    DVP_ORDERS_BY_PARTY.save(deps.storage, (&order.buyer, &order.order_id), &true)?;
+    DVP_ORDERS_BY_PARTY.save(deps.storage, (&order.seller, &order.order_id), &true)?;
+    OPEN_DVP_ORDERS.save(deps.storage, (&order.denom, &order.order_id), &true)?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_attribute(
+            "action",
+            "provwasm.contracts.custom_marker.create_dvp_order",
+        )
+        .add_attribute("order_id", &order.order_id)
+        .add_attribute("denom", &order.denom)
+        .add_attribute("amount", order.amount)
+        .add_attribute("price", format!("{}{}", order.price, order.payment_asset))
+        .add_attribute("seller", &order.seller)
+        .add_attribute("buyer", &order.buyer);
+
+    Ok(res)
+}
+
+/// Escrow the marker tokens of a DvP order with the contract.
+/// When the buyer has already paid, both legs are swapped in the same transaction.
+///
+/// Fails when:-
+///     - order doesn't exist, is no longer open or has expired
+///     - caller is not the seller of the order
+///     - seller is not an issuer and doesn't have transfer access
+///     - tokens are already delivered
+///     - holding period is not passed
+///     - seller or buyer is freezed
+///     - amount exceeds the seller's balance neither partially frozen nor held by lock-up lots
+///       or vesting
+///     - buyer is not whitelisted or amount exceeds the buyer's alloted token_limit
+///     - a transfer rule of the denom fails for the seller to buyer transfer
+///     - settlement fails, see `settle_dvp`
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.deliver_dvp
+///     - order_id
+///     - status
+fn try_deliver_dvp(
+    mut deps: DepsMut<ProvenanceQuery>,
+    order_id: String,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let mut order = load_dvp_order(deps.storage, &order_id)?;
+    ensure_dvp_open(&order, env.block.time.seconds())?;
 
     // Ensuring authorized sender
-    if is_issuer(&deps, denom.clone(), sender.clone()).is_err()
-        && (has_mint_access(&deps, denom.clone(), sender.clone()).is_err()
-            || has_freeze_access(&deps, denom.clone(), sender.clone()).is_err())
-        && is_subadmin(&deps, sender.clone()).is_err()
+    if sender.ne(&order.seller) {
+        let err = format!("Address `{}`: Not the seller of the DvP order!", &sender);
+        return Err(ContractError::Unauthorized { err });
+    }
+    if is_issuer(&deps, order.denom.clone(), sender.clone()).is_err()
+        && has_transfer_access(deps.storage, order.denom.clone(), sender.clone()).is_err()
     {
         let err = format!(
-            "Address `{}`: Don't have Issuer, Sub Admin, Freeze or Mint rights!",
+            "Address `{}`: Don't have Issuer or Transfer rights!",
             &sender
         );
         return Err(ContractError::Unauthorized { err });
     }
 
-    for data in mint_data {
-        // ensure token limit maintained.
-        ensure_token_limit(
-            deps.as_ref(),
-            data.address.clone(),
-            denom.clone(),
-            data.amount,
-        )?;
+    if order.delivered {
+        return Err(ContractError::DvpLegEscrowed {
+            order_id,
+            leg: "delivered".into(),
+        });
+    }
+
+    // Ensuring the transfer pipeline of the denom allows the seller to buyer transfer
+    ensure_transfer_allowed(
+        deps.as_ref(),
+        &env,
+        &Transfer {
+            denom: order.denom.clone(),
+            from: Some(sender.clone()),
+            to: order.buyer.clone(),
+            amount: order.amount,
+            from_pending: Pending::default(),
+            to_pending: Pending::default(),
+        },
+    )?;
+    record_transfer(
+        &mut deps,
+        &env,
+        &Transfer {
+            denom: order.denom.clone(),
+            from: Some(sender.clone()),
+            to: env.contract.address.clone(),
+            amount: order.amount,
+            from_pending: Pending::default(),
+            to_pending: Pending::default(),
+        },
+    )?;
+
+    let mut msgs = vec![cm_transfer_marker_coins(
+        order.amount.u128(),
+        &order.denom,
+        env.contract.address.clone(),
+        sender,
+        env.contract.address.clone(),
+    )?];
+    order.delivered = true;
+
+    if order.paid {
+        msgs.extend(settle_dvp(&mut deps, &mut order, &env)?);
+    }
+    DVP_ORDERS.save(deps.storage, order.order_id.as_bytes(), &order)?;
+
+    let res = Response::<ProvenanceMsg>::new()
+        .add_messages(msgs)
+        .add_attribute("action", "provwasm.contracts.custom_marker.deliver_dvp")
+        .add_attribute("order_id", order.order_id)
+        .add_attribute("status", order.status.to_string());
+
+    Ok(res)
+}
+
+/// Handle cw20 tokens sent to the contract with `send`.
+///
+/// Fails when:-
+///     - message is not a `ReceiveMsg`
+///     - handled message fails
+fn try_receive(
+    deps: DepsMut<ProvenanceQuery>,
+    msg: Cw20ReceiveMsg,
+    token: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let sender = deps.api.addr_validate(&msg.sender)?;
+
+    match from_json(&msg.msg)? {
+        ReceiveMsg::PayDvp { order_id } => try_pay_dvp(
+            deps,
+            order_id,
+            sender,
+            Some((PaymentAsset::Cw20 { address: token }, msg.amount)),
+            env,
+        ),
+    }
+}
+
+/// Escrow the payment of a DvP order with the contract; `payment` is the single coin or cw20
+/// amount sent along, if any.
+/// When the seller has already delivered, both legs are swapped in the same transaction.
+///
+/// Fails when:-
+///     - order doesn't exist, is no longer open or has expired
+///     - caller is not the buyer of the order
+///     - payment is already made
+///     - payment is not exactly the order's price in the order's payment asset
+///     - settlement fails, see `settle_dvp`
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.pay_dvp
+///     - order_id
+///     - status
+fn try_pay_dvp(
+    mut deps: DepsMut<ProvenanceQuery>,
+    order_id: String,
+    sender: Addr,
+    payment: Option<(PaymentAsset, Uint128)>,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let mut order = load_dvp_order(deps.storage, &order_id)?;
+    ensure_dvp_open(&order, env.block.time.seconds())?;
+
+    // Ensuring authorized sender
+    if sender.ne(&order.buyer) {
+        let err = format!("Address `{}`: Not the buyer of the DvP order!", &sender);
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    if order.paid {
+        return Err(ContractError::DvpLegEscrowed {
+            order_id,
+            leg: "paid".into(),
+        });
+    }
+
+    if payment != Some((order.payment_asset.clone(), order.price)) {
+        return Err(ContractError::InvalidDvpPayment {
+            expected: format!("{}{}", order.price, order.payment_asset),
+        });
+    }
+    order.paid = true;
 
-        // ensure not freezed
-        ensure_not_freezed(deps.storage, vec![data.address.clone()], denom.as_bytes())?;
+    let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
+    if order.delivered {
+        msgs.extend(settle_dvp(&mut deps, &mut order, &env)?);
+    }
+    DVP_ORDERS.save(deps.storage, order.order_id.as_bytes(), &order)?;
 
-        // ensuring country is authorized
-        ensure_authorized_country(deps.storage, denom.clone(), data.address.clone())?;
+    let res = Response::<ProvenanceMsg>::new()
+        .add_messages(msgs)
+        .add_attribute("action", "provwasm.contracts.custom_marker.pay_dvp")
+        .add_attribute("order_id", order.order_id)
+        .add_attribute("status", order.status.to_string());
 
-        // Add mint amount to pool
-        update_minted_tokens(deps.storage, denom.clone(), UpdateType::Add(data.amount))?;
+    Ok(res)
+}
 
-        msgs.extend(mint_to(
-            denom.to_string(),
-            data.clone(),
+/// Create the messages that swap both escrowed legs of a DvP order: marker tokens to the
+/// buyer and payment to the seller. Marks the order settled.
+///
+/// Fails when:-
+///     - buyer is not whitelisted
+///     - buyer or seller is freezed
+///     - amount exceeds the buyer's alloted token_limit
+///     - a transfer rule of the denom fails for the escrow to buyer transfer
+fn settle_dvp(
+    deps: &mut DepsMut<ProvenanceQuery>,
+    order: &mut DvpOrder,
+    env: &Env,
+) -> Result<Vec<CosmosMsg<ProvenanceMsg>>, ContractError> {
+    let contract_address = env.contract.address.clone();
+
+    // Compliance is checked again, the whitelist or freezes may have changed since creation
+    ensure_not_freezed(
+        deps.storage,
+        vec![order.seller.clone()],
+        order.denom.as_bytes(),
+    )?;
+    let pipeline_transfer = Transfer {
+        denom: order.denom.clone(),
+        from: Some(contract_address.clone()),
+        to: order.buyer.clone(),
+        amount: order.amount,
+        from_pending: Pending::default(),
+        to_pending: Pending::default(),
+    };
+    ensure_transfer_allowed(deps.as_ref(), env, &pipeline_transfer)?;
+    record_transfer(deps, env, &pipeline_transfer)?;
+
+    let msgs = vec![
+        cm_transfer_marker_coins(
+            order.amount.u128(),
+            &order.denom,
+            order.buyer.clone(),
             contract_address.clone(),
-        )?);
+            contract_address,
+        )?,
+        dvp_payment_msg(&order.payment_asset, order.price, order.seller.clone())?,
+    ];
+    order.status = DvpStatus::Settled;
+    OPEN_DVP_ORDERS.remove(deps.storage, (&order.denom, &order.order_id));
+
+    Ok(msgs)
+}
 
-        let key = Key::new(denom.clone(), data.address.clone()).as_bytes()?;
-
-        // Updating balance for the account that already exists and adding the address and balance pairs that
-        // do not exist
-        if PARTIAL_FREEZE
-            .update(deps.storage, &key, |bals_opt| -> Result<_, ContractError> {
-                match bals_opt {
-                    Some(mut bals) => Ok({
-                        bals += data.amount;
-                        bals
-                    }),
-                    None => Ok(data.amount),
-                }
-            })
-            .is_err()
-        {
-            // Adding address and balance pair that doesn't exist
-            PARTIAL_FREEZE.save(deps.storage, &key, &data.amount)?;
-        }
+/// Cancel an open DvP order and refund whichever legs are escrowed: marker tokens to the
+/// seller and payment to the buyer.
+/// Before expiry only the buyer or the seller can cancel; after it anyone can.
+///
+/// Fails when:-
+///     - order doesn't exist or is no longer open
+///     - order has not expired and caller is neither the buyer nor the seller
+///
+/// Emits event:-
+///     - provwasm.contracts.custom_marker.cancel_dvp
+///     - order_id
+///     - status
+fn try_cancel_dvp(
+    mut deps: DepsMut<ProvenanceQuery>,
+    order_id: String,
+    sender: Addr,
+    env: Env,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    let mut order = load_dvp_order(deps.storage, &order_id)?;
+    if order.status != DvpStatus::Open {
+        return Err(ContractError::DvpOrderClosed {
+            order_id,
+            status: order.status,
+        });
+    }
 
-        // Updating frozen tokens balance
-        if FROZEN_TOKENS
-            .update(
-                deps.storage,
-                denom.as_bytes(),
-                |bals_opt| -> Result<_, ContractError> {
-                    match bals_opt {
-                        Some(mut bals) => Ok({
-                            bals += data.amount;
-                            bals
-                        }),
-                        None => Ok(data.amount),
-                    }
-                },
-            )
-            .is_err()
-        {
-            // Adding balance as new entry
-            FROZEN_TOKENS.save(deps.storage, denom.as_bytes(), &data.amount)?;
-        }
+    if order.is_expired(env.block.time.seconds()) {
+        order.status = DvpStatus::Expired;
+    } else if sender.eq(&order.buyer) || sender.eq(&order.seller) {
+        order.status = DvpStatus::Cancelled;
+    } else {
+        let err = format!("Address `{}`: Not a party of the DvP order!", &sender);
+        return Err(ContractError::Unauthorized { err });
     }
+    OPEN_DVP_ORDERS.remove(deps.storage, (&order.denom, &order.order_id));
+
+    let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
+    if order.delivered {
+        record_transfer(
+            &mut deps,
+            &env,
+            &Transfer {
+                denom: order.denom.clone(),
+                from: Some(env.contract.address.clone()),
+                to: order.seller.clone(),
+                amount: order.amount,
+                from_pending: Pending::default(),
+                to_pending: Pending::default(),
+            },
+        )?;
+        msgs.push(cm_transfer_marker_coins(
+            order.amount.u128(),
+            &order.denom,
+            order.seller.clone(),
+            env.contract.address.clone(),
+            env.contract.address,
+        )?);
+    }
+    if order.paid {
+        msgs.push(dvp_payment_msg(
+            &order.payment_asset,
+            order.price,
+            order.buyer.clone(),
+        )?);
+    }
+    DVP_ORDERS.save(deps.storage, order.order_id.as_bytes(), &order)?;
 
     let res = Response::<ProvenanceMsg>::new()
         .add_messages(msgs)
-        .add_attribute(
-            "action",
-            "provwasm.contracts.custom_marker.delivery_vs_payment",
-        );
+        .add_attribute("action", "provwasm.contracts.custom_marker.cancel_dvp")
+        .add_attribute("order_id", order.order_id)
+        .add_attribute("status", order.status.to_string());
 
     Ok(res)
 }
 
 /// Create and dispatch a message that will burn coins from address.
 /// This function supports batch operations, i.e. multiple addresses can be burned simultaneously.
-/// Decreases minted token value.
+/// Decreases minted token value; an address whose balance is burned stops being a holder.
+/// Unlocked tokens are burned first, then locked tokens, consuming their lock-up lots.
 ///  
 /// Fails when:-
 ///     - caller is not sub_admin, issuer, tokenization agent or having burn access
 ///     - recipient is not whitelisted
-///     - recipient is freezed
 ///     - amount is zero
+///     - amount exceeds the spendable balance, i.e. not partially frozen, net of earlier burns
+///       of the batch from the same address
+///     - a corporate action of the denom is in progress
 ///
 /// Emits event:-
 ///     - provwasm.contracts.custom_marker.burn_from
 fn try_burn_from(
-    deps: DepsMut<ProvenanceQuery>,
+    mut deps: DepsMut<ProvenanceQuery>,
     burn_from_params: Vec<MintBurnParams>,
-    contract_address: Addr,
     sender: Addr,
+    env: Env,
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
     let mut msgs: Vec<CosmosMsg<ProvenanceMsg>> = Vec::new();
-    let querier = ProvenanceQuerier::new(&deps.querier);
+    let mut pending = PendingBalances::default();
 
     for params in burn_from_params {
         // Ensuring authorized sender
@@ -1065,12 +2388,18 @@ fn try_burn_from(
             return Err(ContractError::Unauthorized { err });
         }
 
+        ensure_no_corporate_action(deps.storage, params.denom.clone())?;
+
         for burn_data in params.mint_burn_data {
-            // ensure token balance no frozen.
-            ensure_bal_not_frozen(
+            // ensuring only the spendable balance is burned, net of earlier burns of the batch
+            let burned = pending.get(&params.denom, &burn_data.address);
+            ensure_spendable(
                 deps.as_ref(),
                 burn_data.address.clone(),
                 params.denom.clone(),
+                burn_data.amount,
+                burned,
+                env.block.time.seconds(),
             )?;
 
             // ensuring country is authorized
@@ -1087,10 +2416,21 @@ fn try_burn_from(
                 UpdateType::Remove(burn_data.amount),
             )?;
 
+            record_burn(
+                &mut deps,
+                &env,
+                &params.denom,
+                &burn_data.address,
+                burn_data.amount,
+                burned,
+            )?;
+            pending.debit(&params.denom, &burn_data.address, burn_data.amount);
+
+            let querier = ProvenanceQuerier::new(&deps.querier);
             msgs.extend(burn_from(
                 params.denom.clone(),
                 burn_data,
-                contract_address.clone(),
+                env.contract.address.clone(),
                 &querier,
             )?);
         }
@@ -1546,12 +2886,16 @@ pub fn try_manage_roles(
 ///  
 /// Fails when:-
 ///     - caller is not sub_admin, issuer, transfer agent or having force_transfer access
-///     - recipient is not whitelisted
-///     - caller is not whitelisted
-///     - recipient is freezed
-///     - caller is freezed
+///     - sender or recipient is not whitelisted
+///     - sender or recipient is freezed
+///     - holding period is not passed
 ///     - amount is zero
-///     - unfreezed balance is lesser than requested amount
+///     - amount exceeds the recipient's alloted token_limit, net of earlier transfers of the
+///       batch to and from the same address
+///     - amount exceeds the balance neither partially frozen nor held by lock-up lots or
+///       vesting, net of earlier transfers of the batch to and from the same address
+///     - a transfer rule of the denom fails
+///     - a corporate action of the denom is in progress
 ///
 /// Emits event:-
 ///     - provwasm.contracts.custom_marker.force_transfer
@@ -1559,7 +2903,7 @@ pub fn try_manage_roles(
 ///     - to
 ///     - from
 fn try_force_transfer(
-    deps: DepsMut<ProvenanceQuery>,
+    mut deps: DepsMut<ProvenanceQuery>,
     denom: String,
     params: Vec<ForceTransferParams>,
     sender: Addr,
@@ -1578,9 +2922,6 @@ fn try_force_transfer(
         return Err(ContractError::Unauthorized { err });
     }
 
-    // Ensuring holding period is passed
-    ensure_holding_period_passed(deps.storage, denom.as_bytes(), env.block.time.seconds())?;
-
     let mut msgs = Vec::new();
     let mut attrs = Vec::new();
     attrs.push(attr(
@@ -1588,26 +2929,23 @@ fn try_force_transfer(
         "provwasm.contracts.custom_marker.force_transfer",
     ));
 
+    let mut pending = PendingBalances::default();
     for param in params {
-        // ensuring country is authorized
-        ensure_authorized_country(deps.storage, denom.clone(), param.from.clone())?;
-        ensure_authorized_country(deps.storage, denom.clone(), param.to.clone())?;
-
-        // ensure not freezed
-        ensure_not_freezed(
-            deps.storage,
-            vec![param.from.clone(), param.to.clone()],
-            denom.as_bytes(),
-        )?;
-
-        // ensure frozen balance and balance capital maintained
-        ensure_bal_maintained(
-            deps.as_ref(),
-            param.to.clone(),
-            param.from.clone(),
-            denom.clone(),
-            param.amount,
-        )?;
+        // Ensuring the sender is whitelisted and the transfer pipeline of the denom allows the
+        // transfer, net of earlier transfers of the batch to and from the same addresses
+        let pipeline_transfer = Transfer {
+            denom: denom.clone(),
+            from: Some(param.from.clone()),
+            to: param.to.clone(),
+            amount: param.amount,
+            from_pending: pending.get(&denom, &param.from),
+            to_pending: pending.get(&denom, &param.to),
+        };
+        evaluate_force_transfer(deps.as_ref(), &env, &pipeline_transfer)
+            .map_err(|restriction| restriction.error)?;
+        record_transfer(&mut deps, &env, &pipeline_transfer)?;
+        pending.debit(&denom, &param.from, param.amount);
+        pending.credit(&denom, &param.to, param.amount);
 
         msgs.push(cm_transfer_marker_coins(
             param.amount.u128(),
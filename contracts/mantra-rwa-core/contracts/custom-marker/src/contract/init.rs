@@ -1,5 +1,7 @@
 #[cfg(not(feature = "library"))]
 use super::*;
+#[cfg(not(feature = "library"))]
+use cw2::set_contract_version;
 
 /// Initialize the smart contract config state.
 ///
@@ -15,6 +17,10 @@ pub fn instantiate(
     // admin info, granting sender the admin role
     let admin = info.sender;
     ADMIN.save(deps.storage, &admin)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // default region groups of country codes
+    save_default_regions(deps.storage)?;
 
     // Dispatch messages to the name module handler and emit an event.
     Ok(Response::new().add_attribute("action", "provwasm.contracts.custom_marker.init"))
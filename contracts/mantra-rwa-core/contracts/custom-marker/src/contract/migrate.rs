@@ -1,11 +1,96 @@
 #[cfg(not(feature = "library"))]
 use super::*;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::Order;
+#[cfg(not(feature = "library"))]
+use cw2::{set_contract_version, CONTRACT};
 
 /// Called when migrating a contract instance to a new code ID.
+/// An instance without a contract version predates it: its country codes stored as `u8` before
+/// ISO 3166-1 codes were required are replaced by the countries given for them in
+/// `country_codes`, in denom configs, whitelists and country pairs of transfer rules, and its
+/// partial freezes stored as one amount per address become freeze lots without a reason or
+/// expiry. Default region groups that don't exist yet are stored, then the contract version.
+///
+/// Fails when:-
+///     - the instance is of another contract
+///     - `country_codes` is given for an instance with a contract version
+///     - a given country is not an ISO 3166-1 alpha-2 or numeric code
+///     - a stored code has no given country
 ///
 /// Event:-
 ///     provwasm.contracts.custom_marker.migrate
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match CONTRACT.may_load(deps.storage)? {
+        None => migrate_legacy_state(deps.storage, &msg)?,
+        Some(version) => {
+            if version.contract != CONTRACT_NAME {
+                return Err(ContractError::InvalidMigration {
+                    err: format!("can't migrate contract `{}`", version.contract),
+                });
+            }
+            if !msg.country_codes.is_empty() {
+                return Err(ContractError::InvalidMigration {
+                    err: "legacy country codes are already migrated".into(),
+                });
+            }
+        }
+    }
+
+    save_default_regions(deps.storage)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::new().add_attribute("action", "provwasm.contracts.custom_marker.migrate"))
 }
+
+#[cfg(not(feature = "library"))]
+fn migrate_legacy_state(storage: &mut dyn Storage, msg: &MigrateMsg) -> Result<(), ContractError> {
+    let legacy = LegacyCountryCodes::new(&msg.country_codes)?;
+
+    let denom_configs = DENOM_CONFIG
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (denom, mut denom_config) in denom_configs {
+        denom_config.country_codes = legacy.migrate_all(&denom_config.country_codes)?;
+        DENOM_CONFIG.save(storage, &denom, &denom_config)?;
+    }
+
+    let whitelist = WHITELIST
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, code) in whitelist {
+        WHITELIST.save(storage, &key, &legacy.migrate(code)?)?;
+    }
+
+    let transfer_rules = TRANSFER_RULES
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (denom, mut rules) in transfer_rules {
+        for rule in rules.iter_mut() {
+            if let TransferRule::BlockedCountryPairs { pairs } = rule {
+                for pair in pairs.iter_mut() {
+                    pair.from = legacy.migrate(pair.from)?;
+                    pair.to = legacy.migrate(pair.to)?;
+                }
+            }
+        }
+        TRANSFER_RULES.save(storage, &denom, &rules)?;
+    }
+
+    let partial_freezes = PARTIAL_FREEZE
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, amount) in partial_freezes {
+        let mut lots = FREEZE_LOTS.may_load(storage, &key)?.unwrap_or_default();
+        lots.push(FreezeLot {
+            amount,
+            reason: None,
+            expires_at: None,
+        });
+        FREEZE_LOTS.save(storage, &key, &lots)?;
+        PARTIAL_FREEZE.remove(storage, &key);
+    }
+
+    Ok(())
+}
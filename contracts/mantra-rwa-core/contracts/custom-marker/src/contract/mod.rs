@@ -1,5 +1,11 @@
 use super::*;
 
+// Contract version stored on instantiate and migrate, with `cw2`
+#[cfg(not(feature = "library"))]
+const CONTRACT_NAME: &str = "crates.io:custom-marker";
+#[cfg(not(feature = "library"))]
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg(not(feature = "library"))]
 mod execute;
 #[cfg(not(feature = "library"))]
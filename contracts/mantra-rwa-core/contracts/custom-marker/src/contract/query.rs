@@ -1,17 +1,29 @@
 #![cfg(not(feature = "library"))]
 use super::*;
 use crate::{
-    get_marker_by_address, get_marker_by_denom, Key, DENOM_CONFIG, FREEZE_LIST, HOLDING_PERIOD,
-    MINTED_TOKENS, PARTIAL_FREEZE,
+    evaluate_send, get_frozen_balance, get_locked_balance, get_marker_by_address,
+    get_marker_by_denom, get_recovery_threshold, get_spendable_balance, CanTransferResponse,
+    DvpOrder, Key, RestrictionCode, Transfer, CASH_IN_LIEU, CORPORATE_ACTIONS, DENOM_CONFIG,
+    DVP_ORDERS, DVP_ORDERS_BY_PARTY, FREEZE_LIST, FREEZE_LOTS, HOLDERS, HOLDER_COUNT,
+    HOLDING_PERIOD, INVESTOR_CATEGORIES, LOCKUP_LOTS, LOCKUP_PERIOD, MINTED_TOKENS,
+    RECOVERED_ACCOUNTS, RECOVERIES, REGIONS, TIER_POLICIES, TIER_VERIFIERS, TRANSFER_RULES,
+    VESTING,
 };
-use cosmwasm_std::{to_json_binary, Addr, Deps, Env, QueryResponse, StdError, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Deps, Env, Order, QueryResponse, StdError, StdResult, Uint128,
+};
+use cw_storage_plus::Bound;
 use provwasm_std::{ProvenanceQuerier, ProvenanceQuery};
 
+// Page size of paginated queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 /// Handle query requests for the provenance marker module.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(
     deps: Deps<ProvenanceQuery>,
-    _env: Env,
+    env: Env,
     msg: QueryMsg,
 ) -> Result<QueryResponse, StdError> {
     match msg {
@@ -20,7 +32,7 @@ pub fn query(
         QueryMsg::GetAuthorizedCountries { denom } => try_get_auth_countries(deps, denom),
         QueryMsg::GetFreezedAccounts { denom } => try_get_freezed_accounts(deps, denom),
         QueryMsg::GetFrozenBalance { denom, address } => {
-            try_get_frozen_balance(deps, denom, address)
+            try_get_frozen_balance(deps, denom, address, env)
         }
         QueryMsg::GetDenomConfig { denom } => try_get_denom_config(deps, denom),
         QueryMsg::GetCountryCodeByAddress { denom, address } => {
@@ -32,6 +44,60 @@ pub fn query(
         QueryMsg::GetFrozenTokens { denom } => try_get_frozen_tokens(deps, denom),
         QueryMsg::GetCiculatingSupply { denom } => try_get_circultating_tokens(deps, denom),
         QueryMsg::GetHoldPeriod { denom } => try_get_hold_period(deps, denom),
+        QueryMsg::GetDvpOrder { order_id } => try_get_dvp_order(deps, order_id),
+        QueryMsg::GetDvpOrders {
+            address,
+            start_after,
+            limit,
+        } => try_get_dvp_orders(deps, address, start_after, limit),
+        QueryMsg::GetLockupPeriod { denom } => try_get_lockup_period(deps, denom),
+        QueryMsg::GetLockupLots { denom, address } => try_get_lockup_lots(deps, denom, address),
+        QueryMsg::GetVesting { denom, address } => try_get_vesting(deps, denom, address),
+        QueryMsg::GetLockedBalance { denom, address } => {
+            try_get_locked_balance(deps, denom, address, env)
+        }
+        QueryMsg::GetTransferRules { denom } => try_get_transfer_rules(deps, denom),
+        QueryMsg::GetInvestorCategory { denom, address } => {
+            try_get_investor_category(deps, denom, address)
+        }
+        QueryMsg::GetTierPolicies { denom } => try_get_tier_policies(deps, denom),
+        QueryMsg::GetTierVerifier { denom } => try_get_tier_verifier(deps, denom),
+        QueryMsg::GetHolders {
+            denom,
+            start_after,
+            limit,
+        } => try_get_holders(deps, denom, start_after, limit),
+        QueryMsg::GetHolderCount { denom } => try_get_holder_count(deps, denom),
+        QueryMsg::GetFreezeLots { denom, address } => try_get_freeze_lots(deps, denom, address),
+        QueryMsg::GetSpendableBalance { denom, address } => {
+            try_get_spendable_balance(deps, denom, address, env)
+        }
+        QueryMsg::GetCorporateAction { action_id } => try_get_corporate_action(deps, action_id),
+        QueryMsg::GetRecovery { denom, lost } => try_get_recovery(deps, denom, lost),
+        QueryMsg::GetRecoveredAccount { denom, address } => {
+            try_get_recovered_account(deps, denom, address)
+        }
+        QueryMsg::GetRecoveryThreshold { denom } => try_get_recovery_threshold(deps, denom),
+        QueryMsg::GetCashInLieu {
+            action_id,
+            start_after,
+            limit,
+        } => try_get_cash_in_lieu(deps, action_id, start_after, limit),
+        QueryMsg::CanTransfer {
+            denom,
+            from,
+            to,
+            amount,
+        } => try_can_transfer(deps, denom, from, to, amount, env),
+        QueryMsg::DetectTransferRestriction {
+            denom,
+            from,
+            to,
+            amount,
+        } => try_detect_transfer_restriction(deps, denom, from, to, amount, env),
+        QueryMsg::MessageForRestriction { code } => try_message_for_restriction(code),
+        QueryMsg::GetRegion { name } => try_get_region(deps, name),
+        QueryMsg::GetRegions {} => try_get_regions(deps),
     }
 }
 
@@ -92,7 +158,7 @@ fn try_get_freezed_accounts(
     to_json_binary(&accounts)
 }
 
-/// Query frozen balances by address.
+/// Query frozen balances by address, i.e. the amount of its unexpired partial freezes.
 ///
 /// Returns:-
 ///     Frozen Balance
@@ -100,9 +166,9 @@ fn try_get_frozen_balance(
     deps: Deps<ProvenanceQuery>,
     denom: String,
     address: Addr,
+    env: Env,
 ) -> Result<QueryResponse, StdError> {
-    let key = Key::new(denom, address).as_bytes_std()?;
-    let bal = PARTIAL_FREEZE.load(deps.storage, &key).unwrap_or_default();
+    let bal = get_frozen_balance(deps.storage, address, denom, env.block.time.seconds())?;
     to_json_binary(&bal)
 }
 
@@ -207,3 +273,402 @@ fn try_get_hold_period(
 
     to_json_binary(&holding_period)
 }
+
+/// Query a DvP order by id.
+///
+/// Fails when:-
+///     order doesn't exist
+///
+/// Returns:-
+///     DvP Order
+fn try_get_dvp_order(
+    deps: Deps<ProvenanceQuery>,
+    order_id: String,
+) -> Result<QueryResponse, StdError> {
+    let order = DVP_ORDERS.load(deps.storage, order_id.as_bytes())?;
+    to_json_binary(&order)
+}
+
+/// Query DvP orders in which the address is the buyer or the seller in order id order, a page
+/// at a time; pass the last order id of a page as `start_after` for the next one.
+///
+/// Returns:-
+///     List of DvP Orders
+fn try_get_dvp_orders(
+    deps: Deps<ProvenanceQuery>,
+    address: Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<QueryResponse, StdError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let orders = DVP_ORDERS_BY_PARTY
+        .prefix(&address)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|order_id| DVP_ORDERS.load(deps.storage, order_id?.as_bytes()))
+        .collect::<StdResult<Vec<DvpOrder>>>()?;
+
+    to_json_binary(&orders)
+}
+
+/// Query for lockup period
+///
+/// Returns:-
+///     Seconds each mint stays locked
+fn try_get_lockup_period(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+) -> Result<QueryResponse, StdError> {
+    let period = LOCKUP_PERIOD
+        .load(deps.storage, denom.as_bytes())
+        .unwrap_or_default();
+
+    to_json_binary(&period)
+}
+
+/// Query lock-up lots of an address. Lots that have unlocked may still be listed.
+///
+/// Returns:-
+///     List of Lock-up Lots
+fn try_get_lockup_lots(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+) -> Result<QueryResponse, StdError> {
+    let key = Key::new(denom, address).as_bytes_std()?;
+    let lots = LOCKUP_LOTS.load(deps.storage, &key).unwrap_or_default();
+    to_json_binary(&lots)
+}
+
+/// Query vesting schedule of an address.
+///
+/// Returns:-
+///     Vesting Schedule, if any
+fn try_get_vesting(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+) -> Result<QueryResponse, StdError> {
+    let key = Key::new(denom, address).as_bytes_std()?;
+    let schedule = VESTING.may_load(deps.storage, &key)?;
+    to_json_binary(&schedule)
+}
+
+/// Query balance of an address split into locked and transferable parts.
+///
+/// Returns:-
+///     Locked Balance
+fn try_get_locked_balance(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+    env: Env,
+) -> Result<QueryResponse, StdError> {
+    let balance = get_locked_balance(deps, address, denom, env.block.time.seconds())?;
+    to_json_binary(&balance)
+}
+
+/// Query for transfer rules
+///
+/// Returns:-
+///     List of Transfer Rules
+fn try_get_transfer_rules(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+) -> Result<QueryResponse, StdError> {
+    let rules = TRANSFER_RULES
+        .load(deps.storage, denom.as_bytes())
+        .unwrap_or_default();
+
+    to_json_binary(&rules)
+}
+
+/// Query investor category of an address.
+///
+/// Returns:-
+///     Investor Category, if any
+fn try_get_investor_category(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+) -> Result<QueryResponse, StdError> {
+    let key = Key::new(denom, address).as_bytes_std()?;
+    let category = INVESTOR_CATEGORIES.may_load(deps.storage, &key)?;
+    to_json_binary(&category)
+}
+
+/// Query investor tier policies of a denom.
+///
+/// Returns:-
+///     List of Tier Policies
+fn try_get_tier_policies(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+) -> Result<QueryResponse, StdError> {
+    let policies = TIER_POLICIES
+        .load(deps.storage, denom.as_bytes())
+        .unwrap_or_default();
+
+    to_json_binary(&policies)
+}
+
+/// Query verifier contract of a denom, which attests investor tiers.
+///
+/// Returns:-
+///     Verifier address, if any
+fn try_get_tier_verifier(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+) -> Result<QueryResponse, StdError> {
+    let verifier = TIER_VERIFIERS.may_load(deps.storage, denom.as_bytes())?;
+    to_json_binary(&verifier)
+}
+
+/// Query holders of a denom in address order, a page at a time; pass the last address of a
+/// page as `start_after` for the next one.
+///
+/// Returns:-
+///     List of holder addresses
+fn try_get_holders(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<QueryResponse, StdError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let holders = HOLDERS
+        .prefix(&denom)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    to_json_binary(&holders)
+}
+
+/// Query partial freezes of an address. Freezes that have expired may still be listed.
+///
+/// Returns:-
+///     List of Freeze Lots
+fn try_get_freeze_lots(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+) -> Result<QueryResponse, StdError> {
+    let key = Key::new(denom, address).as_bytes_std()?;
+    let lots = FREEZE_LOTS.load(deps.storage, &key).unwrap_or_default();
+    to_json_binary(&lots)
+}
+
+/// Query balance of an address split by its partial freezes.
+///
+/// Returns:-
+///     Spendable Balance
+fn try_get_spendable_balance(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+    env: Env,
+) -> Result<QueryResponse, StdError> {
+    let balance = get_spendable_balance(deps, address, denom, env.block.time.seconds())?;
+    to_json_binary(&balance)
+}
+
+/// Query a corporate action by action id.
+///
+/// Returns:-
+///     Corporate Action Record
+fn try_get_corporate_action(
+    deps: Deps<ProvenanceQuery>,
+    action_id: String,
+) -> Result<QueryResponse, StdError> {
+    let record = CORPORATE_ACTIONS.load(deps.storage, action_id.as_bytes())?;
+    to_json_binary(&record)
+}
+
+/// Query cash in lieu owed by a corporate action in address order, a page at a time; pass the
+/// last address of a page as `start_after` for the next one.
+///
+/// Returns:-
+///     List of Cash In Lieu
+fn try_get_cash_in_lieu(
+    deps: Deps<ProvenanceQuery>,
+    action_id: String,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<QueryResponse, StdError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let cash_in_lieu = CASH_IN_LIEU
+        .prefix(&action_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, cash)| cash))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&cash_in_lieu)
+}
+
+/// Query the pending recovery of a lost address.
+///
+/// Returns:-
+///     Account Recovery, if any
+fn try_get_recovery(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    lost: Addr,
+) -> Result<QueryResponse, StdError> {
+    let key = Key::new(denom, lost).as_bytes_std()?;
+    let recovery = RECOVERIES.may_load(deps.storage, &key)?;
+    to_json_binary(&recovery)
+}
+
+/// Query the new address of a recovered address.
+///
+/// Returns:-
+///     New address, if the address is recovered
+fn try_get_recovered_account(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    address: Addr,
+) -> Result<QueryResponse, StdError> {
+    let key = Key::new(denom, address).as_bytes_std()?;
+    let new = RECOVERED_ACCOUNTS.may_load(deps.storage, &key)?;
+    to_json_binary(&new)
+}
+
+/// Query approvals an account recovery of a denom needs.
+///
+/// Returns:-
+///     Recovery threshold
+fn try_get_recovery_threshold(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+) -> Result<QueryResponse, StdError> {
+    let threshold = get_recovery_threshold(deps.storage, &denom)?;
+    to_json_binary(&threshold)
+}
+
+/// Query number of holders of a denom.
+///
+/// Returns:-
+///     Holder count
+fn try_get_holder_count(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+) -> Result<QueryResponse, StdError> {
+    let count = HOLDER_COUNT
+        .may_load(deps.storage, denom.as_bytes())?
+        .unwrap_or_default();
+
+    to_json_binary(&count)
+}
+
+/// Dry-run a `send` of `from` like `detect_transfer_restriction`, with the restriction and the
+/// error it would fail with; `from` is none for a mint.
+///
+/// Returns:-
+///     Can Transfer Response, with the code of the first check that fails
+fn try_can_transfer(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    from: Option<Addr>,
+    to: Addr,
+    amount: Uint128,
+    env: Env,
+) -> Result<QueryResponse, StdError> {
+    let transfer = Transfer {
+        denom,
+        from,
+        to,
+        amount,
+        from_pending: Pending::default(),
+        to_pending: Pending::default(),
+    };
+    let (restriction, reason) = detect_restriction(deps, &env, &transfer);
+
+    to_json_binary(&CanTransferResponse {
+        code: restriction.code(),
+        restriction,
+        reason,
+    })
+}
+
+/// Dry-run a `send` of `from`, with the same checks including transfer access.
+///
+/// Returns:-
+///     Restriction code of the first check that fails, 0 when the send is allowed
+fn try_detect_transfer_restriction(
+    deps: Deps<ProvenanceQuery>,
+    denom: String,
+    from: Addr,
+    to: Addr,
+    amount: Uint128,
+    env: Env,
+) -> Result<QueryResponse, StdError> {
+    let transfer = Transfer {
+        denom,
+        from: Some(from),
+        to,
+        amount,
+        from_pending: Pending::default(),
+        to_pending: Pending::default(),
+    };
+    let (restriction, _) = detect_restriction(deps, &env, &transfer);
+
+    to_json_binary(&restriction.code())
+}
+
+// Restriction of the first check of `send` that fails, with the error it fails with
+fn detect_restriction(
+    deps: Deps<ProvenanceQuery>,
+    env: &Env,
+    transfer: &Transfer,
+) -> (RestrictionCode, Option<String>) {
+    match evaluate_send(deps, env, transfer) {
+        Ok(()) => (RestrictionCode::Success, None),
+        Err(restriction) => (restriction.code, Some(restriction.error.to_string())),
+    }
+}
+
+/// Query the explanation of a restriction code.
+///
+/// Fails when:-
+///     code is unknown
+///
+/// Returns:-
+///     Message of the restriction code
+fn try_message_for_restriction(code: u8) -> Result<QueryResponse, StdError> {
+    let restriction = RestrictionCode::from_code(code)
+        .ok_or_else(|| StdError::generic_err(format!("Unknown restriction code: {}", code)))?;
+
+    to_json_binary(&restriction.message())
+}
+
+/// Query a region group, by case insensitive name.
+///
+/// Fails when:-
+///     region group doesn't exist
+///
+/// Returns:-
+///     List of ISO 3166-1 numeric country codes
+fn try_get_region(deps: Deps<ProvenanceQuery>, name: String) -> Result<QueryResponse, StdError> {
+    let codes = REGIONS.load(deps.storage, &name.to_ascii_lowercase())?;
+    to_json_binary(&codes)
+}
+
+/// Query all region groups.
+///
+/// Returns:-
+///     List of region names with their ISO 3166-1 numeric country codes
+fn try_get_regions(deps: Deps<ProvenanceQuery>) -> Result<QueryResponse, StdError> {
+    let regions = REGIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Vec<u16>)>>>()?;
+
+    to_json_binary(&regions)
+}
@@ -0,0 +1,61 @@
+use super::*;
+pub use country_codes::{
+    is_country_code, CountryCode, InvalidCountryCode, LegacyCountryCode, LegacyCountryCodes,
+    UnmappedCountryCode,
+};
+
+/// Region groups stored on instantiate and migrate, by name and alpha-2 codes. Sub admins
+/// keep them current with `update_region`; the FATF list is the one of June 2025.
+pub const DEFAULT_REGIONS: [(&str, &[&str]); 3] = [
+    (
+        "eu",
+        &[
+            "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU",
+            "IE", "IT", "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+        ],
+    ),
+    ("gcc", &["AE", "BH", "KW", "OM", "QA", "SA"]),
+    (
+        "fatf-greylist",
+        &[
+            "AO", "BF", "BG", "BO", "CD", "CI", "CM", "DZ", "HR", "HT", "KE", "LA", "LB", "MC",
+            "MZ", "NA", "NG", "NP", "SS", "SY", "VE", "VG", "VN", "YE", "ZA",
+        ],
+    ),
+];
+
+/// Function to ensure every code is assigned by ISO 3166-1
+pub fn ensure_country_codes(codes: &[u16]) -> Result<(), ContractError> {
+    match codes.iter().find(|code| !is_country_code(**code)) {
+        Some(code) => Err(ContractError::InvalidCountryCode {
+            code: code.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Function to store the default region groups that don't exist yet
+pub fn save_default_regions(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    for (name, alpha_2_codes) in DEFAULT_REGIONS {
+        if REGIONS.has(storage, name) {
+            continue;
+        }
+
+        let codes = alpha_2_codes
+            .iter()
+            .map(|code| CountryCode::Alpha2(code.to_string()).numeric())
+            .collect::<Result<Vec<u16>, InvalidCountryCode>>()?;
+        REGIONS.save(storage, name, &codes)?;
+    }
+
+    Ok(())
+}
+
+/// Function to get the codes of a region group
+pub fn load_region(storage: &dyn Storage, name: &str) -> Result<Vec<u16>, ContractError> {
+    REGIONS
+        .may_load(storage, &name.to_ascii_lowercase())?
+        .ok_or_else(|| ContractError::RegionNotFound {
+            name: name.to_string(),
+        })
+}
@@ -132,3 +132,242 @@ pub enum Requests {
         params: CancelParams,
     },
 }
+
+// Asset a DvP buyer pays with
+#[cw_serde]
+pub enum PaymentAsset {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+
+impl Display for PaymentAsset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native { denom } => write!(f, "{}", denom),
+            Self::Cw20 { address } => write!(f, "{}", address),
+        }
+    }
+}
+
+// DvP Order Status
+#[cw_serde]
+pub enum DvpStatus {
+    Open,
+    Settled,
+    Cancelled,
+    Expired,
+}
+
+impl Display for DvpStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Settled => write!(f, "settled"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+/// Ratio-based action applied to the balance of every holder of a denom.
+#[cw_serde]
+pub enum CorporateAction {
+    /// `numerator` tokens for every `denominator` held, e.g. 2 for 1
+    Split { numerator: u64, denominator: u64 },
+    /// One token for every `ratio` held
+    ReverseSplit { ratio: u64 },
+    /// `numerator` tokens of `new_denom` for every `denominator` held, which are burned
+    Redenominate {
+        new_denom: String,
+        numerator: u64,
+        denominator: u64,
+    },
+}
+
+impl CorporateAction {
+    /// Numerator and denominator every balance is multiplied by
+    pub fn ratio(&self) -> (u64, u64) {
+        match self {
+            Self::Split {
+                numerator,
+                denominator,
+            } => (*numerator, *denominator),
+            Self::ReverseSplit { ratio } => (1, *ratio),
+            Self::Redenominate {
+                numerator,
+                denominator,
+                ..
+            } => (*numerator, *denominator),
+        }
+    }
+
+    /// Denom the holders of `denom` hold after the action
+    pub fn target_denom<'a>(&'a self, denom: &'a str) -> &'a str {
+        match self {
+            Self::Redenominate { new_denom, .. } => new_denom,
+            _ => denom,
+        }
+    }
+}
+
+// Corporate Action Status
+#[cw_serde]
+pub enum CorporateActionStatus {
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+impl Display for CorporateActionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InProgress => write!(f, "in_progress"),
+            Self::Completed => write!(f, "completed"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+// Investor Category
+#[cw_serde]
+#[derive(Eq)]
+pub enum InvestorCategory {
+    Retail,
+    Professional,
+    Institutional,
+}
+
+/// Action an investor tier may take part in, see `TierPolicy`.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum TierAction {
+    /// Receive newly minted tokens through `mint_to`
+    Mint,
+    /// Receive tokens from another address
+    Receive,
+    /// Send tokens to another address
+    Send,
+}
+
+impl Display for TierAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Mint => write!(f, "mint"),
+            Self::Receive => write!(f, "receive"),
+            Self::Send => write!(f, "send"),
+        }
+    }
+}
+
+/// Configurable rule of a denom's transfer pipeline, see `rules::evaluate_transfer`.
+#[cw_serde]
+pub enum TransferRule {
+    /// Caps the number of addresses holding the denom
+    MaxHolders { limit: u64 },
+    /// A holder's balance after a transfer is zero or at least `amount`
+    MinHolding { amount: Uint128 },
+    /// A recipient's balance after a transfer is at most `amount`
+    MaxHolding { amount: Uint128 },
+    /// Recipients must be registered in one of the categories
+    InvestorCategories { allowed: Vec<InvestorCategory> },
+    /// Transfers between the country codes of each pair, in that direction, are refused
+    BlockedCountryPairs { pairs: Vec<CountryPair> },
+    /// Caps what one address sends per UTC day
+    DailyVolumeCap { amount: Uint128 },
+}
+
+impl TransferRule {
+    pub fn code(&self) -> RestrictionCode {
+        match self {
+            Self::MaxHolders { .. } => RestrictionCode::MaxHolders,
+            Self::MinHolding { .. } => RestrictionCode::MinHolding,
+            Self::MaxHolding { .. } => RestrictionCode::MaxHolding,
+            Self::InvestorCategories { .. } => RestrictionCode::InvestorCategory,
+            Self::BlockedCountryPairs { .. } => RestrictionCode::CountryPair,
+            Self::DailyVolumeCap { .. } => RestrictionCode::DailyVolume,
+        }
+    }
+}
+
+/// Check of the transfer pipeline that refused a transfer. The numeric codes are stable.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum RestrictionCode {
+    Success = 0,
+    AccountFrozen = 1,
+    NotWhitelisted = 2,
+    TokenLimitExceeded = 3,
+    HoldingPeriod = 4,
+    BalanceFrozen = 5,
+    TokensLocked = 6,
+    MaxHolders = 7,
+    MinHolding = 8,
+    MaxHolding = 9,
+    InvestorCategory = 10,
+    CountryPair = 11,
+    DailyVolume = 12,
+    NoTransferAccess = 13,
+    TierAction = 14,
+    CorporateAction = 15,
+}
+
+impl RestrictionCode {
+    pub const ALL: [RestrictionCode; 16] = [
+        Self::Success,
+        Self::AccountFrozen,
+        Self::NotWhitelisted,
+        Self::TokenLimitExceeded,
+        Self::HoldingPeriod,
+        Self::BalanceFrozen,
+        Self::TokensLocked,
+        Self::MaxHolders,
+        Self::MinHolding,
+        Self::MaxHolding,
+        Self::InvestorCategory,
+        Self::CountryPair,
+        Self::DailyVolume,
+        Self::NoTransferAccess,
+        Self::TierAction,
+        Self::CorporateAction,
+    ];
+
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|restriction| restriction.code() == code)
+    }
+
+    /// Human readable explanation of the code, as returned by `message_for_restriction`
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Success => "No restriction",
+            Self::AccountFrozen => "Sender or recipient is freezed",
+            Self::NotWhitelisted => "Recipient is not whitelisted for the denom",
+            Self::TokenLimitExceeded => "Recipient would exceed the token limit",
+            Self::HoldingPeriod => "Holding period of the denom is not passed",
+            Self::BalanceFrozen => "Amount exceeds the sender's balance not partially frozen",
+            Self::TokensLocked => "Amount exceeds the balance not held by lock-up lots or vesting",
+            Self::MaxHolders => "Denom has reached its maximum number of holders",
+            Self::MinHolding => "Balance would fall below the minimum holding",
+            Self::MaxHolding => "Recipient would exceed the maximum holding",
+            Self::InvestorCategory => "Investor category of the recipient is not allowed",
+            Self::CountryPair => "Transfers between these countries are restricted",
+            Self::DailyVolume => "Sender would exceed the daily volume cap",
+            Self::NoTransferAccess => "Sender doesn't have transfer access",
+            Self::TierAction => "Investor tier of the sender or recipient doesn't allow the action",
+            Self::CorporateAction => "A corporate action of the denom is in progress",
+        }
+    }
+}
+
+/// Countries to authorize for a denom: one country, or every country of a region group.
+#[cw_serde]
+pub enum Countries {
+    Code(CountryCode),
+    Region(String),
+}
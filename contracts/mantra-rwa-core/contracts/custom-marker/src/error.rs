@@ -57,10 +57,10 @@ pub enum ContractError {
     CountryCodeAuthorizationFailed { denom: String, address: Addr },
 
     #[error("Country code: `{code}` already exists!")]
-    CountryCodeAlreadyExists { code: u8 },
+    CountryCodeAlreadyExists { code: u16 },
 
     #[error("Country code: `{code}` does not exists!")]
-    CountryCodeNotExists { code: u8 },
+    CountryCodeNotExists { code: u16 },
 
     #[error("Address:`{address}` is not an Issuer!")]
     NotAnIssuer { address: Addr },
@@ -74,8 +74,14 @@ pub enum ContractError {
     #[error("Token limit exceeded for address: `{address}`!")]
     TokenLimitExceeded { address: Addr },
 
-    #[error("Balance is frozen for address: `{address}` and denom: `{denom}`!")]
-    BalanceFrozen { denom: String, address: Addr },
+    #[error(
+        "Only {spendable} tokens are spendable for address: `{address}` and denom: `{denom}`!"
+    )]
+    BalanceFrozen {
+        denom: String,
+        address: Addr,
+        spendable: Uint128,
+    },
 
     #[error("Supply must be greater than token limit!")]
     SupplyUnderFlow {},
@@ -115,4 +121,135 @@ pub enum ContractError {
 
     #[error("Address: `{addr}` doesn't exist!")]
     NotFound { addr: Addr },
+
+    #[error("DvP order: `{order_id}` already exists!")]
+    DvpOrderExists { order_id: String },
+
+    #[error("DvP order: `{order_id}` doesn't exist!")]
+    DvpOrderNotFound { order_id: String },
+
+    #[error("DvP order: `{order_id}` is {status}!")]
+    DvpOrderClosed { order_id: String, status: DvpStatus },
+
+    #[error("DvP order: `{order_id}` expired at {expiry}!")]
+    DvpOrderExpired { order_id: String, expiry: u64 },
+
+    #[error("DvP order: `{order_id}` is already {leg}!")]
+    DvpLegEscrowed { order_id: String, leg: String },
+
+    #[error("Invalid DvP order! `{err}`")]
+    InvalidDvpOrder { err: String },
+
+    #[error("DvP payment must be exactly `{expected}`!")]
+    InvalidDvpPayment { expected: String },
+
+    #[error("Only {transferable} tokens are transferable for address: `{address}` and denom: `{denom}`!")]
+    TokensLocked {
+        denom: String,
+        address: Addr,
+        transferable: Uint128,
+    },
+
+    #[error("Invalid vesting schedule! `{err}`")]
+    InvalidVestingSchedule { err: String },
+
+    #[error("Denom: `{denom}` is limited to {limit} holders!")]
+    MaxHoldersReached { denom: String, limit: u64 },
+
+    #[error("Balance of address: `{address}` must be zero or at least {min}!")]
+    BelowMinHolding { address: Addr, min: Uint128 },
+
+    #[error("Balance of address: `{address}` can't exceed {max}!")]
+    AboveMaxHolding { address: Addr, max: Uint128 },
+
+    #[error("Investor category of address: `{address}` is not allowed for denom: `{denom}`!")]
+    InvestorCategoryNotAllowed { denom: String, address: Addr },
+
+    #[error("Transfers from country code: `{from}` to `{to}` are restricted!")]
+    CountryPairRestricted { from: u16, to: u16 },
+
+    #[error("Daily volume exceeded for address: `{address}`, {remaining} remaining!")]
+    DailyVolumeExceeded { address: Addr, remaining: Uint128 },
+
+    #[error("Country code: `{code}` is not an ISO 3166-1 alpha-2 or numeric code!")]
+    InvalidCountryCode { code: String },
+
+    #[error("Region: `{name}` not found!")]
+    RegionNotFound { name: String },
+
+    #[error("Investor tier of address: `{address}` doesn't allow {action} for denom: `{denom}`!")]
+    TierActionNotAllowed {
+        denom: String,
+        address: Addr,
+        action: TierAction,
+    },
+
+    #[error("Investor tier: `{category:?}` has more than one policy!")]
+    DuplicateTierPolicy { category: InvestorCategory },
+
+    #[error("Freeze expiry: {expires_at} is not in the future!")]
+    InvalidFreezeExpiry { expires_at: Uint64 },
+
+    #[error("Only {frozen} tokens are frozen for address: `{address}`!")]
+    UnfreezeExceedsFrozen { address: Addr, frozen: Uint128 },
+
+    #[error("Corporate action: `{action_id}` already exists!")]
+    CorporateActionExists { action_id: String },
+
+    #[error("Corporate action: `{action_id}` doesn't exist!")]
+    CorporateActionNotFound { action_id: String },
+
+    #[error("Corporate action: `{action_id}` of denom: `{denom}` is in progress!")]
+    CorporateActionInProgress { denom: String, action_id: String },
+
+    #[error("Corporate action: `{action_id}` is completed!")]
+    CorporateActionCompleted { action_id: String },
+
+    #[error("Invalid corporate action! `{err}`")]
+    InvalidCorporateAction { err: String },
+
+    #[error("Corporate action: `{action_id}` is cancelled!")]
+    CorporateActionCancelled { action_id: String },
+
+    #[error("DvP order: `{order_id}` of denom: `{denom}` is open!")]
+    DvpOrdersOpen { denom: String, order_id: String },
+
+    #[error("Burn of {amount} exceeds the minted tokens: {minted} of denom: `{denom}`!")]
+    BurnExceedsMinted {
+        denom: String,
+        minted: Uint128,
+        amount: Uint128,
+    },
+
+    #[error("Recovery of address: `{address}` for denom: `{denom}` is pending!")]
+    RecoveryPending { denom: String, address: Addr },
+
+    #[error("No recovery of address: `{address}` for denom: `{denom}` is pending!")]
+    RecoveryNotFound { denom: String, address: Addr },
+
+    #[error("Address: `{address}` is recovered and permanently freezed!")]
+    AccountRecovered { address: Addr },
+
+    #[error("Invalid account recovery! `{err}`")]
+    InvalidRecovery { err: String },
+
+    #[error("Recovery threshold must be at least 2, got {threshold}!")]
+    InvalidRecoveryThreshold { threshold: u8 },
+
+    #[error("Invalid migration! `{err}`")]
+    InvalidMigration { err: String },
+}
+
+impl From<InvalidCountryCode> for ContractError {
+    fn from(err: InvalidCountryCode) -> Self {
+        ContractError::InvalidCountryCode { code: err.code }
+    }
+}
+
+impl From<UnmappedCountryCode> for ContractError {
+    fn from(err: UnmappedCountryCode) -> Self {
+        ContractError::InvalidMigration {
+            err: err.to_string(),
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use super::*;
-use cosmwasm_std::CosmosMsg;
+use cosmwasm_std::{coin, to_json_binary, BankMsg, CosmosMsg, Order, WasmMsg};
+use cw20::Cw20ExecuteMsg;
 use provwasm_std::ProvenanceQuery;
 use provwasm_std::{ProvenanceMsg, ProvenanceQuerier};
 
@@ -17,7 +18,7 @@ pub fn check_bal_avalaility(
 }
 
 /// Function to get consolidated balance
-fn get_consolidated_balance(
+pub fn get_consolidated_balance(
     deps: Deps<ProvenanceQuery>,
     address: Addr,
     denom: String,
@@ -29,7 +30,7 @@ fn get_consolidated_balance(
 
 /// Function to ensure holding period is passed
 pub fn ensure_holding_period_passed(
-    storage: &mut dyn Storage,
+    storage: &dyn Storage,
     denom: &[u8],
     current_timestamp: u64,
 ) -> Result<(), ContractError> {
@@ -45,61 +46,331 @@ pub fn ensure_holding_period_passed(
     Ok(())
 }
 
-/// Function to ensure token limit is not exceeded
+/// Function to add a lock-up lot for minted tokens when the denom has a lock-up period
+pub fn add_lockup_lot(
+    storage: &mut dyn Storage,
+    denom: String,
+    address: Addr,
+    amount: Uint128,
+    current_timestamp: u64,
+) -> Result<(), ContractError> {
+    let period = LOCKUP_PERIOD
+        .may_load(storage, denom.as_bytes())?
+        .unwrap_or_default();
+    if period.is_zero() {
+        return Ok(());
+    }
+
+    let key = Key::new(denom, address).as_bytes()?;
+    let mut lots = LOCKUP_LOTS.may_load(storage, &key)?.unwrap_or_default();
+
+    // Dropping lots that have unlocked
+    lots.retain(|lot| lot.unlock_at.u64() > current_timestamp);
+    lots.push(LockupLot {
+        amount,
+        unlock_at: Uint64::new(current_timestamp + period.u64()),
+    });
+    LOCKUP_LOTS.save(storage, &key, &lots)?;
+
+    Ok(())
+}
+
+/// Function to get balance split by lock-up lots and vesting
+pub fn get_locked_balance(
+    deps: Deps<ProvenanceQuery>,
+    address: Addr,
+    denom: String,
+    current_timestamp: u64,
+) -> StdResult<LockedBalance> {
+    let key = Key::new(denom.clone(), address.clone()).as_bytes_std()?;
+    let locked_lots = get_locked_lots(deps.storage, &key, current_timestamp)?;
+    let unvested = get_unvested(deps.storage, &key, current_timestamp)?;
+    let balance = get_consolidated_balance(deps, address, denom)?;
+
+    Ok(LockedBalance {
+        balance,
+        locked_lots,
+        unvested,
+        transferable: balance.saturating_sub(locked_lots + unvested),
+    })
+}
+
+fn get_locked_lots(
+    storage: &dyn Storage,
+    key: &[u8],
+    current_timestamp: u64,
+) -> StdResult<Uint128> {
+    Ok(LOCKUP_LOTS
+        .may_load(storage, key)?
+        .unwrap_or_default()
+        .iter()
+        .filter(|lot| lot.unlock_at.u64() > current_timestamp)
+        .fold(Uint128::zero(), |total, lot| total + lot.amount))
+}
+
+fn get_unvested(storage: &dyn Storage, key: &[u8], current_timestamp: u64) -> StdResult<Uint128> {
+    Ok(VESTING
+        .may_load(storage, key)?
+        .map(|schedule| schedule.unvested(current_timestamp))
+        .unwrap_or_default())
+}
+
+/// Function to ensure a sender only moves its balance neither partially frozen nor held by
+/// lock-up lots or vesting, i.e. `amount <= balance - frozen - locked`. `pending` was moved to
+/// and from the address earlier in the same batch. Fails with `BalanceFrozen` when the amount
+/// exceeds the balance not frozen, else with `TokensLocked`.
+pub fn ensure_transferable(
+    deps: Deps<ProvenanceQuery>,
+    address: Addr,
+    denom: String,
+    amount: Uint128,
+    pending: Pending,
+    current_timestamp: u64,
+) -> Result<(), ContractError> {
+    let key = Key::new(denom.clone(), address.clone()).as_bytes()?;
+    let frozen = get_frozen_balance(
+        deps.storage,
+        address.clone(),
+        denom.clone(),
+        current_timestamp,
+    )?;
+    let locked = get_locked_lots(deps.storage, &key, current_timestamp)?
+        + get_unvested(deps.storage, &key, current_timestamp)?;
+    if (frozen + locked).is_zero() {
+        return Ok(());
+    }
+
+    let balance = get_consolidated_balance(deps, address.clone(), denom.clone())?;
+    let spendable = pending.apply(balance).saturating_sub(frozen);
+    let transferable = spendable.saturating_sub(locked);
+    if amount <= transferable {
+        return Ok(());
+    }
+
+    if !frozen.is_zero() && amount > spendable {
+        return Err(ContractError::BalanceFrozen {
+            denom,
+            address,
+            spendable,
+        });
+    }
+
+    Err(ContractError::TokensLocked {
+        denom,
+        address,
+        transferable,
+    })
+}
+
+/// Function to consume the lock-up lots of an address as `amount` leaves its `balance`.
+/// Unlocked tokens leave first; the lots unlocking soonest are consumed by whatever the
+/// remaining balance can't back, so no lot outlives the tokens it locks. Lots that have
+/// unlocked are dropped.
+pub fn consume_lockup_lots(
+    storage: &mut dyn Storage,
+    denom: String,
+    address: Addr,
+    balance: Uint128,
+    amount: Uint128,
+    current_timestamp: u64,
+) -> Result<(), ContractError> {
+    let key = Key::new(denom, address).as_bytes()?;
+    let mut lots = match LOCKUP_LOTS.may_load(storage, &key)? {
+        Some(lots) => lots,
+        None => return Ok(()),
+    };
+    lots.retain(|lot| lot.unlock_at.u64() > current_timestamp);
+    lots.sort_by_key(|lot| lot.unlock_at);
+
+    let locked = lots
+        .iter()
+        .fold(Uint128::zero(), |total, lot| total + lot.amount);
+    let mut excess = locked.saturating_sub(balance.saturating_sub(amount));
+    for lot in lots.iter_mut() {
+        let consumed = lot.amount.min(excess);
+        lot.amount -= consumed;
+        excess -= consumed;
+    }
+    lots.retain(|lot| !lot.amount.is_zero());
+
+    if lots.is_empty() {
+        LOCKUP_LOTS.remove(storage, &key);
+    } else {
+        LOCKUP_LOTS.save(storage, &key, &lots)?;
+    }
+
+    Ok(())
+}
+
+/// Function to ensure token limit is not exceeded; `pending` was moved to and from the address
+/// earlier in the same batch
 pub fn ensure_token_limit(
     deps: Deps<ProvenanceQuery>,
     address: Addr,
     denom: String,
     amount: Uint128,
+    pending: Pending,
 ) -> Result<(), ContractError> {
     let denom_config = DENOM_CONFIG.load(deps.storage, denom.as_bytes())?;
+    let token_limit = get_tier_policy(deps.storage, &denom, &address)?
+        .and_then(|policy| policy.token_limit)
+        .unwrap_or(denom_config.token_limit);
     let bal = get_consolidated_balance(deps, address.clone(), denom)?;
 
     check_bal_avalaility(
-        bal + amount,
-        denom_config.token_limit,
+        pending.apply(bal) + amount,
+        token_limit,
         ContractError::TokenLimitExceeded { address },
     )?;
 
     Ok(())
 }
 
-/// Function to check balance is not frozen
-pub fn ensure_bal_not_frozen(
+/// Function to get the policy of the investor tier of an address, if it has a tier with a policy
+pub fn get_tier_policy(
+    storage: &dyn Storage,
+    denom: &str,
+    address: &Addr,
+) -> Result<Option<TierPolicy>, ContractError> {
+    let key = Key::new(denom.to_string(), address.clone()).as_bytes()?;
+    let category = match INVESTOR_CATEGORIES.may_load(storage, &key)? {
+        Some(category) => category,
+        None => return Ok(None),
+    };
+
+    Ok(TIER_POLICIES
+        .may_load(storage, denom.as_bytes())?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|policy| policy.category == category))
+}
+
+/// Function to ensure the investor tier of an address allows the action. Addresses without a
+/// tier, or whose tier has no policy, are not restricted.
+pub fn ensure_tier_action(
+    storage: &dyn Storage,
+    denom: String,
+    address: Addr,
+    action: TierAction,
+) -> Result<(), ContractError> {
+    if let Some(policy) = get_tier_policy(storage, &denom, &address)? {
+        if !policy.actions.contains(&action) {
+            return Err(ContractError::TierActionNotAllowed {
+                denom,
+                address,
+                action,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Function to get the amount frozen by the unexpired partial freezes of an address
+pub fn get_frozen_balance(
+    storage: &dyn Storage,
+    address: Addr,
+    denom: String,
+    current_timestamp: u64,
+) -> StdResult<Uint128> {
+    let key = Key::new(denom, address).as_bytes_std()?;
+
+    Ok(FREEZE_LOTS
+        .may_load(storage, &key)?
+        .unwrap_or_default()
+        .iter()
+        .filter(|lot| lot.is_active(current_timestamp))
+        .fold(Uint128::zero(), |total, lot| total + lot.amount))
+}
+
+/// Function to get balance split by partial freezes
+pub fn get_spendable_balance(
     deps: Deps<ProvenanceQuery>,
     address: Addr,
     denom: String,
+    current_timestamp: u64,
+) -> StdResult<SpendableBalance> {
+    let frozen = get_frozen_balance(
+        deps.storage,
+        address.clone(),
+        denom.clone(),
+        current_timestamp,
+    )?;
+    let balance = get_consolidated_balance(deps, address, denom)?;
+
+    Ok(SpendableBalance {
+        balance,
+        frozen,
+        spendable: balance.saturating_sub(frozen),
+    })
+}
+
+/// Function to ensure only the balance not partially frozen is moved; `pending` was moved to
+/// and from the address earlier in the same batch
+pub fn ensure_spendable(
+    deps: Deps<ProvenanceQuery>,
+    address: Addr,
+    denom: String,
+    amount: Uint128,
+    pending: Pending,
+    current_timestamp: u64,
 ) -> Result<(), ContractError> {
-    let key = Key::new(denom.to_string(), address.clone()).as_bytes()?;
-    let bal = get_consolidated_balance(deps, address.clone(), denom.clone())?;
-    let frozen_bal = PARTIAL_FREEZE.load(deps.storage, &key).unwrap_or_default();
+    let bal = get_spendable_balance(deps, address.clone(), denom.clone(), current_timestamp)?;
+    if bal.frozen.is_zero() {
+        return Ok(());
+    }
 
+    let spendable = pending.apply(bal.spendable);
     check_bal_avalaility(
-        frozen_bal,
-        bal,
-        ContractError::BalanceFrozen { denom, address },
+        amount,
+        spendable,
+        ContractError::BalanceFrozen {
+            denom,
+            address,
+            spendable,
+        },
     )?;
 
     Ok(())
 }
 
-/// Function to check balance is maintained
-pub fn ensure_bal_maintained(
-    deps: Deps<ProvenanceQuery>,
-    to: Addr,
-    from: Addr,
-    denom: String,
+/// Function to drop the expired partial freezes of an address, releasing them from the frozen
+/// tokens of the denom
+pub fn prune_freeze_lots(
+    storage: &mut dyn Storage,
+    denom: &str,
+    lots: &mut Vec<FreezeLot>,
+    current_timestamp: u64,
+) -> Result<(), ContractError> {
+    let expired = lots
+        .iter()
+        .filter(|lot| !lot.is_active(current_timestamp))
+        .fold(Uint128::zero(), |total, lot| total + lot.amount);
+    lots.retain(|lot| lot.is_active(current_timestamp));
+
+    release_frozen_tokens(storage, denom, expired)
+}
+
+/// Function to remove an amount from the frozen tokens of the denom
+pub fn release_frozen_tokens(
+    storage: &mut dyn Storage,
+    denom: &str,
     amount: Uint128,
 ) -> Result<(), ContractError> {
-    ensure_bal_not_frozen(deps, from, denom.clone())?;
-    ensure_token_limit(deps, to, denom, amount)?;
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    FROZEN_TOKENS.update(storage, denom.as_bytes(), |tokens| -> StdResult<_> {
+        Ok(tokens.unwrap_or_default().saturating_sub(amount))
+    })?;
 
     Ok(())
 }
 
 /// Function to the user is whitelisted
 pub fn ensure_authorized_country(
-    storage: &mut dyn Storage,
+    storage: &dyn Storage,
     denom: String,
     address: Addr,
 ) -> Result<(), ContractError> {
@@ -119,7 +390,7 @@ pub fn ensure_authorized_country(
 
 /// Function to ensure account is not freezed
 pub fn ensure_not_freezed(
-    storage: &mut dyn Storage,
+    storage: &dyn Storage,
     address: Vec<Addr>,
     denom: &[u8],
 ) -> Result<(), ContractError> {
@@ -136,7 +407,7 @@ pub fn ensure_not_freezed(
 }
 
 /// Helper function to add country codes
-pub fn add_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(), ContractError> {
+pub fn add_country_codes(country_codes: &mut Vec<u16>, code: u16) -> Result<(), ContractError> {
     if country_codes.contains(&code) {
         return Err(ContractError::CountryCodeAlreadyExists { code });
     } else {
@@ -147,7 +418,7 @@ pub fn add_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(), Co
 }
 
 /// Helper function to remove country codes
-pub fn remove_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(), ContractError> {
+pub fn remove_country_codes(country_codes: &mut Vec<u16>, code: u16) -> Result<(), ContractError> {
     if !country_codes.contains(&code) {
         return Err(ContractError::CountryCodeNotExists { code });
     } else {
@@ -159,10 +430,10 @@ pub fn remove_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(),
 
 /// Function to get country code from an address
 pub fn get_country_code_from_whitelist(
-    storage: &mut dyn Storage,
+    storage: &dyn Storage,
     denom: String,
     address: Addr,
-) -> Result<u8, ContractError> {
+) -> Result<u16, ContractError> {
     let key = Key::new(denom.to_string(), address.clone()).as_bytes()?;
 
     match WHITELIST.load(storage, &key) {
@@ -283,13 +554,13 @@ pub fn has_deposit_access(
 
 /// Function to check the user has transfer rights or not
 pub fn has_transfer_access(
-    deps: &DepsMut<ProvenanceQuery>,
+    storage: &dyn Storage,
     denom: String,
     sender: Addr,
 ) -> Result<(), ContractError> {
     let key = Key::new(denom, AccessControls::Transfer).as_bytes()?;
 
-    AGENTS.load(deps.storage, &key).map_or(
+    AGENTS.load(storage, &key).map_or(
         Err(ContractError::NoTransferAccess {
             address: sender.clone(),
         }),
@@ -528,10 +799,14 @@ pub fn update_minted_tokens(
                 denom.as_bytes(),
                 |bals_opt| -> Result<_, ContractError> {
                     match bals_opt {
-                        Some(mut bals) => Ok({
-                            bals -= amount;
-                            bals
-                        }),
+                        Some(bals) => {
+                            bals.checked_sub(amount)
+                                .map_err(|_| ContractError::BurnExceedsMinted {
+                                    denom: denom.clone(),
+                                    minted: bals,
+                                    amount,
+                                })
+                        }
                         None => Ok(amount),
                     }
                 },
@@ -792,3 +1067,346 @@ pub fn burn_from(
 
     Ok(msgs)
 }
+
+/// Helper function to load a DvP order
+pub fn load_dvp_order(storage: &dyn Storage, order_id: &str) -> Result<DvpOrder, ContractError> {
+    DVP_ORDERS
+        .may_load(storage, order_id.as_bytes())?
+        .ok_or_else(|| ContractError::DvpOrderNotFound {
+            order_id: order_id.to_string(),
+        })
+}
+
+/// Function to ensure a DvP order can still take a leg
+pub fn ensure_dvp_open(order: &DvpOrder, current_timestamp: u64) -> Result<(), ContractError> {
+    ensure!(
+        order.status == DvpStatus::Open,
+        ContractError::DvpOrderClosed {
+            order_id: order.order_id.clone(),
+            status: order.status.clone(),
+        }
+    );
+    ensure!(
+        !order.is_expired(current_timestamp),
+        ContractError::DvpOrderExpired {
+            order_id: order.order_id.clone(),
+            expiry: order.expiry.u64(),
+        }
+    );
+
+    Ok(())
+}
+
+/// Helper function for paying out escrowed DvP funds
+pub fn dvp_payment_msg(
+    asset: &PaymentAsset,
+    amount: Uint128,
+    recipient: Addr,
+) -> StdResult<CosmosMsg<ProvenanceMsg>> {
+    let msg = match asset {
+        PaymentAsset::Native { denom } => BankMsg::Send {
+            to_address: recipient.into_string(),
+            amount: vec![coin(amount.u128(), denom)],
+        }
+        .into(),
+        PaymentAsset::Cw20 { address } => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(msg)
+}
+
+/// Helper function to load a corporate action
+pub fn load_corporate_action(
+    storage: &dyn Storage,
+    action_id: &str,
+) -> Result<CorporateActionRecord, ContractError> {
+    CORPORATE_ACTIONS
+        .may_load(storage, action_id.as_bytes())?
+        .ok_or_else(|| ContractError::CorporateActionNotFound {
+            action_id: action_id.to_string(),
+        })
+}
+
+/// Function to ensure a corporate action is neither completed nor cancelled
+pub fn ensure_corporate_action_in_progress(
+    record: &CorporateActionRecord,
+) -> Result<(), ContractError> {
+    let action_id = record.action_id.clone();
+    match record.status {
+        CorporateActionStatus::InProgress => Ok(()),
+        CorporateActionStatus::Completed => {
+            Err(ContractError::CorporateActionCompleted { action_id })
+        }
+        CorporateActionStatus::Cancelled => {
+            Err(ContractError::CorporateActionCancelled { action_id })
+        }
+    }
+}
+
+/// Function to ensure no corporate action of the denom is in progress
+pub fn ensure_no_corporate_action(
+    storage: &dyn Storage,
+    denom: String,
+) -> Result<(), ContractError> {
+    if let Some(action_id) = ACTIVE_CORPORATE_ACTION.may_load(storage, denom.as_bytes())? {
+        return Err(ContractError::CorporateActionInProgress { denom, action_id });
+    }
+
+    Ok(())
+}
+
+/// Function to ensure no DvP order of the denom is open, as corporate actions don't scale the
+/// amounts of orders or the tokens they escrow
+pub fn ensure_no_open_dvp_orders(
+    storage: &dyn Storage,
+    denom: String,
+) -> Result<(), ContractError> {
+    let open = OPEN_DVP_ORDERS
+        .prefix(&denom)
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+    if let Some(order_id) = open {
+        return Err(ContractError::DvpOrdersOpen { denom, order_id });
+    }
+
+    Ok(())
+}
+
+/// Function to scale the partial freezes, lock-up lots and vesting of a holder by the ratio of
+/// a corporate action, rounding down. They move to `target_denom` along with the whitelisted
+/// country code and investor category when it differs from `denom`.
+pub fn scale_holder_state(
+    storage: &mut dyn Storage,
+    denom: &str,
+    target_denom: &str,
+    address: &Addr,
+    (numerator, denominator): (u64, u64),
+) -> Result<(), ContractError> {
+    let key = Key::new(denom.to_string(), address.clone()).as_bytes()?;
+    let target_key = Key::new(target_denom.to_string(), address.clone()).as_bytes()?;
+    let scale = |amount: Uint128| amount.multiply_ratio(numerator, denominator);
+
+    if let Some(lots) = FREEZE_LOTS.may_load(storage, &key)? {
+        FREEZE_LOTS.remove(storage, &key);
+        let frozen = lots
+            .iter()
+            .fold(Uint128::zero(), |total, lot| total + lot.amount);
+        release_frozen_tokens(storage, denom, frozen)?;
+
+        let mut target_lots = FREEZE_LOTS
+            .may_load(storage, &target_key)?
+            .unwrap_or_default();
+        let scaled: Vec<FreezeLot> = lots
+            .into_iter()
+            .map(|lot| FreezeLot {
+                amount: scale(lot.amount),
+                ..lot
+            })
+            .filter(|lot| !lot.amount.is_zero())
+            .collect();
+        let frozen = scaled
+            .iter()
+            .fold(Uint128::zero(), |total, lot| total + lot.amount);
+        FROZEN_TOKENS.update(storage, target_denom.as_bytes(), |tokens| -> StdResult<_> {
+            Ok(tokens.unwrap_or_default() + frozen)
+        })?;
+        target_lots.extend(scaled);
+        if !target_lots.is_empty() {
+            FREEZE_LOTS.save(storage, &target_key, &target_lots)?;
+        }
+    }
+
+    if let Some(lots) = LOCKUP_LOTS.may_load(storage, &key)? {
+        LOCKUP_LOTS.remove(storage, &key);
+        let mut target_lots = LOCKUP_LOTS
+            .may_load(storage, &target_key)?
+            .unwrap_or_default();
+        target_lots.extend(
+            lots.into_iter()
+                .map(|lot| LockupLot {
+                    amount: scale(lot.amount),
+                    ..lot
+                })
+                .filter(|lot| !lot.amount.is_zero()),
+        );
+        if !target_lots.is_empty() {
+            LOCKUP_LOTS.save(storage, &target_key, &target_lots)?;
+        }
+    }
+
+    if let Some(schedule) = VESTING.may_load(storage, &key)? {
+        VESTING.remove(storage, &key);
+        let amount = scale(schedule.amount);
+        if !amount.is_zero() {
+            VESTING.save(
+                storage,
+                &target_key,
+                &VestingSchedule { amount, ..schedule },
+            )?;
+        }
+    }
+
+    if target_denom != denom {
+        if let Some(code) = WHITELIST.may_load(storage, &key)? {
+            if !WHITELIST.has(storage, &target_key) {
+                WHITELIST.save(storage, &target_key, &code)?;
+            }
+        }
+        if let Some(category) = INVESTOR_CATEGORIES.may_load(storage, &key)? {
+            if !INVESTOR_CATEGORIES.has(storage, &target_key) {
+                INVESTOR_CATEGORIES.save(storage, &target_key, &category)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Function to check the sender may propose and approve account recoveries of a denom
+pub fn is_recovery_agent(
+    deps: &DepsMut<ProvenanceQuery>,
+    denom: String,
+    sender: Addr,
+) -> Result<(), ContractError> {
+    if is_issuer(deps, denom.clone(), sender.clone()).is_err()
+        && is_transfer_agent(deps, denom.clone(), sender.clone()).is_err()
+        && has_force_transfer_access(deps, denom, sender.clone()).is_err()
+        && is_subadmin(deps, sender.clone()).is_err()
+    {
+        let err = format!(
+            "Address `{}`: Don't have Issuer, Transfer, Sub Admin or Force Transfer rights!",
+            &sender
+        );
+        return Err(ContractError::Unauthorized { err });
+    }
+
+    Ok(())
+}
+
+/// Function to ensure the new address of a recovery has no state of the denom that the state
+/// of the lost address would overwrite or mix with
+pub fn ensure_recovery_target(
+    deps: Deps<ProvenanceQuery>,
+    denom: &str,
+    new: &Addr,
+) -> Result<(), ContractError> {
+    let storage = deps.storage;
+    let bal = get_consolidated_balance(deps, new.clone(), denom.to_string())?;
+    let key = Key::new(denom.to_string(), new.clone()).as_bytes()?;
+    let state = [
+        // Checking the marker balance, with the holder registry as an extra guard
+        (
+            !bal.is_zero() || HOLDERS.has(storage, (denom, new)),
+            "balance",
+        ),
+        (WHITELIST.has(storage, &key), "whitelisted country code"),
+        (INVESTOR_CATEGORIES.has(storage, &key), "investor category"),
+        (VESTING.has(storage, &key), "vesting schedule"),
+        (FREEZE_LOTS.has(storage, &key), "partial freeze"),
+        (LOCKUP_LOTS.has(storage, &key), "lock-up lot"),
+    ];
+    if let Some((_, name)) = state.iter().find(|(has, _)| *has) {
+        return Err(ContractError::InvalidRecovery {
+            err: format!("new address already has a {} of the denom", name),
+        });
+    }
+
+    Ok(())
+}
+
+/// Function to move the partial freezes, lock-up lots, vesting, whitelisted country code and
+/// investor category of a lost address to its new address, which must have passed
+/// `ensure_recovery_target`
+pub fn move_holder_state(
+    storage: &mut dyn Storage,
+    denom: &str,
+    lost: &Addr,
+    new: &Addr,
+) -> Result<(), ContractError> {
+    let key = Key::new(denom.to_string(), lost.clone()).as_bytes()?;
+    let new_key = Key::new(denom.to_string(), new.clone()).as_bytes()?;
+
+    if let Some(schedule) = VESTING.may_load(storage, &key)? {
+        VESTING.remove(storage, &key);
+        VESTING.save(storage, &new_key, &schedule)?;
+    }
+
+    if let Some(lots) = FREEZE_LOTS.may_load(storage, &key)? {
+        FREEZE_LOTS.remove(storage, &key);
+        FREEZE_LOTS.save(storage, &new_key, &lots)?;
+    }
+
+    if let Some(lots) = LOCKUP_LOTS.may_load(storage, &key)? {
+        LOCKUP_LOTS.remove(storage, &key);
+        LOCKUP_LOTS.save(storage, &new_key, &lots)?;
+    }
+
+    if let Some(code) = WHITELIST.may_load(storage, &key)? {
+        WHITELIST.remove(storage, &key);
+        WHITELIST.save(storage, &new_key, &code)?;
+    }
+
+    if let Some(category) = INVESTOR_CATEGORIES.may_load(storage, &key)? {
+        INVESTOR_CATEGORIES.remove(storage, &key);
+        INVESTOR_CATEGORIES.save(storage, &new_key, &category)?;
+    }
+
+    Ok(())
+}
+
+/// Function to move the lost address's side of its open DvP orders of the denom to its new
+/// address, so that refunds and payments don't go to the freezed lost address
+pub fn move_dvp_orders(
+    storage: &mut dyn Storage,
+    denom: &str,
+    lost: &Addr,
+    new: &Addr,
+) -> Result<(), ContractError> {
+    let order_ids = DVP_ORDERS_BY_PARTY
+        .prefix(lost)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    for order_id in order_ids {
+        let mut order = load_dvp_order(storage, &order_id)?;
+        if order.denom != denom || order.status != DvpStatus::Open {
+            continue;
+        }
+        if order.buyer.eq(new) || order.seller.eq(new) {
+            return Err(ContractError::InvalidRecovery {
+                err: format!("new address is a party of DvP order `{}`", order_id),
+            });
+        }
+
+        if order.buyer.eq(lost) {
+            order.buyer = new.clone();
+        }
+        if order.seller.eq(lost) {
+            order.seller = new.clone();
+        }
+        DVP_ORDERS.save(storage, order_id.as_bytes(), &order)?;
+        DVP_ORDERS_BY_PARTY.remove(storage, (lost, &order_id));
+        DVP_ORDERS_BY_PARTY.save(storage, (new, &order_id), &true)?;
+    }
+
+    Ok(())
+}
+
+// Approvals an account recovery needs when the denom doesn't set a threshold
+pub const DEFAULT_RECOVERY_THRESHOLD: u8 = 2;
+
+/// Function to get the approvals an account recovery of the denom needs
+pub fn get_recovery_threshold(storage: &dyn Storage, denom: &str) -> StdResult<u8> {
+    Ok(RECOVERY_THRESHOLD
+        .may_load(storage, denom.as_bytes())?
+        .unwrap_or(DEFAULT_RECOVERY_THRESHOLD))
+}
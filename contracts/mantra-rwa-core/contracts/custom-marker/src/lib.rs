@@ -1,9 +1,11 @@
 #![warn(clippy::all)]
 pub mod contract;
+pub mod country;
 pub mod enums;
 pub mod error;
 pub mod helper;
 pub mod msg;
+pub mod rules;
 pub mod state;
 pub mod structs;
 pub mod types;
@@ -11,12 +13,15 @@ pub mod types;
 #[cfg(test)]
 mod tests;
 
-use crate::{enums::*, error::ContractError, helper::*, msg::*, state::*, structs::*, types::*};
+use crate::{
+    country::*, enums::*, error::ContractError, helper::*, msg::*, rules::*, state::*, structs::*,
+    types::*,
+};
 use bincode::{deserialize, serialize};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    ensure, entry_point, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Storage, Uint128, Uint64,
+    ensure, entry_point, Addr, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Storage, Uint128, Uint64,
 };
 use cw_storage_plus::{Item, Map};
 use provwasm_std::{
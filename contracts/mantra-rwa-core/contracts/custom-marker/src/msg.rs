@@ -1,4 +1,5 @@
 use super::*;
+use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
 pub struct InitMsg {}
@@ -50,9 +51,13 @@ pub enum ExecuteMsg {
         limit: Uint128,
     },
     UpdateCountryCode {
-        update_type: UpdateType<u8>,
+        update_type: UpdateType<Countries>,
         denom: String,
     },
+    UpdateRegion {
+        name: String,
+        codes: Vec<CountryCode>,
+    },
     Send {
         amount: Uint128,
         denom: String,
@@ -61,9 +66,74 @@ pub enum ExecuteMsg {
     MintTo {
         mint_to_params: Vec<MintBurnParams>,
     },
-    DeliveryVsPayment {
+    CreateDvpOrder {
+        params: DvpOrderParams,
+    },
+    DeliverDvp {
+        order_id: String,
+    },
+    PayDvp {
+        order_id: String,
+    },
+    CancelDvp {
+        order_id: String,
+    },
+    Receive(Cw20ReceiveMsg),
+    UpdateLockupPeriod {
         denom: String,
-        mint_data: Vec<MintBurnData>,
+        period: Uint64,
+    },
+    UpdateVesting {
+        denom: String,
+        params: Vec<VestingParams>,
+    },
+    UpdateTransferRules {
+        denom: String,
+        rules: Vec<TransferRule>,
+    },
+    UpdateInvestorCategory {
+        denom: String,
+        params: Vec<InvestorCategoryParams>,
+    },
+    UpdateTierPolicies {
+        denom: String,
+        policies: Vec<TierPolicy>,
+    },
+    UpdateTierVerifier {
+        denom: String,
+        verifier: UpdateKind<Addr>,
+    },
+    SyncHolders {
+        denom: String,
+        addresses: Vec<Addr>,
+    },
+    StartCorporateAction {
+        params: CorporateActionParams,
+    },
+    ProcessCorporateAction {
+        action_id: String,
+        limit: Option<u32>,
+    },
+    CancelCorporateAction {
+        action_id: String,
+    },
+    RecoverAccount {
+        denom: String,
+        lost: Addr,
+        new: Addr,
+        evidence_hash: String,
+    },
+    ApproveRecovery {
+        denom: String,
+        lost: Addr,
+    },
+    CancelRecovery {
+        denom: String,
+        lost: Addr,
+    },
+    UpdateRecoveryThreshold {
+        denom: String,
+        threshold: u8,
     },
     BurnFrom {
         burn_from_params: Vec<MintBurnParams>,
@@ -83,7 +153,7 @@ pub enum QueryMsg {
     #[returns(Marker)]
     GetByDenom { denom: String },
 
-    #[returns(Vec<u8>)]
+    #[returns(Vec<u16>)]
     GetAuthorizedCountries { denom: String },
 
     #[returns(Option<Vec<Addr>>)]
@@ -95,7 +165,7 @@ pub enum QueryMsg {
     #[returns(DenomConfig)]
     GetDenomConfig { denom: String },
 
-    #[returns(u8)]
+    #[returns(u16)]
     GetCountryCodeByAddress { denom: String, address: Addr },
 
     #[returns(Vec<Addr>)]
@@ -115,11 +185,116 @@ pub enum QueryMsg {
 
     #[returns(Uint64)]
     GetHoldPeriod { denom: String },
+
+    #[returns(DvpOrder)]
+    GetDvpOrder { order_id: String },
+
+    #[returns(Vec<DvpOrder>)]
+    GetDvpOrders {
+        address: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Uint64)]
+    GetLockupPeriod { denom: String },
+
+    #[returns(Vec<LockupLot>)]
+    GetLockupLots { denom: String, address: Addr },
+
+    #[returns(Option<VestingSchedule>)]
+    GetVesting { denom: String, address: Addr },
+
+    #[returns(LockedBalance)]
+    GetLockedBalance { denom: String, address: Addr },
+
+    #[returns(Vec<TransferRule>)]
+    GetTransferRules { denom: String },
+
+    #[returns(Option<InvestorCategory>)]
+    GetInvestorCategory { denom: String, address: Addr },
+
+    #[returns(Vec<TierPolicy>)]
+    GetTierPolicies { denom: String },
+
+    #[returns(Option<Addr>)]
+    GetTierVerifier { denom: String },
+
+    #[returns(Vec<Addr>)]
+    GetHolders {
+        denom: String,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    #[returns(u64)]
+    GetHolderCount { denom: String },
+
+    #[returns(Vec<FreezeLot>)]
+    GetFreezeLots { denom: String, address: Addr },
+
+    #[returns(SpendableBalance)]
+    GetSpendableBalance { denom: String, address: Addr },
+
+    #[returns(CorporateActionRecord)]
+    GetCorporateAction { action_id: String },
+
+    #[returns(Vec<CashInLieu>)]
+    GetCashInLieu {
+        action_id: String,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<AccountRecovery>)]
+    GetRecovery { denom: String, lost: Addr },
+
+    #[returns(Option<Addr>)]
+    GetRecoveredAccount { denom: String, address: Addr },
+
+    #[returns(u8)]
+    GetRecoveryThreshold { denom: String },
+
+    #[returns(CanTransferResponse)]
+    CanTransfer {
+        denom: String,
+        from: Option<Addr>,
+        to: Addr,
+        amount: Uint128,
+    },
+
+    #[returns(u8)]
+    DetectTransferRestriction {
+        denom: String,
+        from: Addr,
+        to: Addr,
+        amount: Uint128,
+    },
+
+    #[returns(String)]
+    MessageForRestriction { code: u8 },
+
+    #[returns(Vec<u16>)]
+    GetRegion { name: String },
+
+    #[returns(Vec<(String, Vec<u16>)>)]
+    GetRegions {},
+}
+
+/// Messages accepted from cw20 token contracts through `send`.
+#[cw_serde]
+pub enum ReceiveMsg {
+    PayDvp { order_id: String },
 }
 
 /// Migrate the contract.
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// ISO 3166-1 countries replacing the legacy `u8` codes of denom configs, whitelists and
+    /// transfer rules
+    #[serde(default)]
+    pub country_codes: Vec<LegacyCountryCode>,
+}
 
 // Cancel Params
 #[cw_serde]
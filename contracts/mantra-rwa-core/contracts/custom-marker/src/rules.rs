@@ -0,0 +1,533 @@
+use super::*;
+use cosmwasm_std::Order;
+use cw_storage_plus::Bound;
+use provwasm_std::ProvenanceQuery;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Marker tokens moving from `from` to `to`; `from` is `None` when they are minted.
+pub struct Transfer {
+    pub denom: String,
+    pub from: Option<Addr>,
+    pub to: Addr,
+    pub amount: Uint128,
+    /// Moved to and from `from` earlier in the same batch, not yet in its balance
+    pub from_pending: Pending,
+    /// Moved to and from `to` earlier in the same batch, not yet in its balance
+    pub to_pending: Pending,
+}
+
+/// Amounts debited from and credited to an address earlier in a batch. The balances queried
+/// while the batch runs don't hold them yet, as the messages moving them execute after it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Pending {
+    pub debited: Uint128,
+    pub credited: Uint128,
+}
+
+impl Pending {
+    /// The balance once the earlier transfers of the batch execute
+    pub fn apply(&self, balance: Uint128) -> Uint128 {
+        (balance + self.credited).saturating_sub(self.debited)
+    }
+}
+
+/// Pending amounts of the addresses of a batch, by denom.
+#[derive(Default)]
+pub struct PendingBalances(Vec<(String, Addr, Pending)>);
+
+impl PendingBalances {
+    pub fn get(&self, denom: &str, address: &Addr) -> Pending {
+        self.0
+            .iter()
+            .find(|(d, a, _)| d == denom && a == address)
+            .map(|(_, _, pending)| *pending)
+            .unwrap_or_default()
+    }
+
+    pub fn debit(&mut self, denom: &str, address: &Addr, amount: Uint128) {
+        self.entry(denom, address).debited += amount;
+    }
+
+    pub fn credit(&mut self, denom: &str, address: &Addr, amount: Uint128) {
+        self.entry(denom, address).credited += amount;
+    }
+
+    fn entry(&mut self, denom: &str, address: &Addr) -> &mut Pending {
+        let index = match self
+            .0
+            .iter()
+            .position(|(d, a, _)| d == denom && a == address)
+        {
+            Some(index) => index,
+            None => {
+                self.0
+                    .push((denom.to_string(), address.clone(), Pending::default()));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[index].2
+    }
+}
+
+/// A check of the transfer pipeline that failed, and the error the transfer fails with.
+pub struct Restriction {
+    pub code: RestrictionCode,
+    pub error: ContractError,
+}
+
+fn restrict<T>(result: Result<T, ContractError>, code: RestrictionCode) -> Result<T, Restriction> {
+    result.map_err(|error| Restriction { code, error })
+}
+
+/// Transfer pipeline of a denom. Runs the built-in checks, then the denom's configured rules
+/// in order, and stops at the first that fails. A side of the transfer that is the contract
+/// itself, i.e. an escrow, is not checked.
+///
+/// Built-in checks:-
+///     - no corporate action of the denom is in progress
+///     - neither party is freezed
+///     - recipient is whitelisted
+///     - investor tiers allow the recipient to be minted to or receive, and the sender to send
+///     - recipient stays within the token_limit of its investor tier, or of the denom
+///     - holding period is passed
+///     - sender only moves its balance not partially frozen nor held by lock-up lots or vesting
+pub fn evaluate_transfer(
+    deps: Deps<ProvenanceQuery>,
+    env: &Env,
+    transfer: &Transfer,
+) -> Result<(), Restriction> {
+    let now = env.block.time.seconds();
+    let denom = &transfer.denom;
+    let from = transfer
+        .from
+        .as_ref()
+        .filter(|from| **from != env.contract.address);
+    let to = Some(&transfer.to).filter(|to| **to != env.contract.address);
+
+    restrict(
+        ensure_no_corporate_action(deps.storage, denom.clone()),
+        RestrictionCode::CorporateAction,
+    )?;
+
+    let parties: Vec<Addr> = from.into_iter().chain(to).cloned().collect();
+    restrict(
+        ensure_not_freezed(deps.storage, parties, denom.as_bytes()),
+        RestrictionCode::AccountFrozen,
+    )?;
+
+    if let Some(to) = to {
+        restrict(
+            ensure_authorized_country(deps.storage, denom.clone(), to.clone()),
+            RestrictionCode::NotWhitelisted,
+        )?;
+        let action = match transfer.from {
+            Some(_) => TierAction::Receive,
+            None => TierAction::Mint,
+        };
+        restrict(
+            ensure_tier_action(deps.storage, denom.clone(), to.clone(), action),
+            RestrictionCode::TierAction,
+        )?;
+        restrict(
+            ensure_token_limit(
+                deps,
+                to.clone(),
+                denom.clone(),
+                transfer.amount,
+                transfer.to_pending,
+            ),
+            RestrictionCode::TokenLimitExceeded,
+        )?;
+    }
+
+    if let Some(from) = from {
+        restrict(
+            ensure_tier_action(deps.storage, denom.clone(), from.clone(), TierAction::Send),
+            RestrictionCode::TierAction,
+        )?;
+        restrict(
+            ensure_holding_period_passed(deps.storage, denom.as_bytes(), now),
+            RestrictionCode::HoldingPeriod,
+        )?;
+        ensure_transferable(
+            deps,
+            from.clone(),
+            denom.clone(),
+            transfer.amount,
+            transfer.from_pending,
+            now,
+        )
+        .map_err(|error| {
+            let code = match error {
+                ContractError::TokensLocked { .. } => RestrictionCode::TokensLocked,
+                _ => RestrictionCode::BalanceFrozen,
+            };
+            Restriction { code, error }
+        })?;
+    }
+
+    if let Ok(rules) = TRANSFER_RULES.load(deps.storage, denom.as_bytes()) {
+        for rule in rules {
+            restrict(
+                check_rule(deps, &rule, transfer, from, to, now),
+                rule.code(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks of `send`: transfer access of the sender, then the transfer pipeline of the denom.
+/// `detect_transfer_restriction` and `can_transfer` report the same result without sending.
+pub fn evaluate_send(
+    deps: Deps<ProvenanceQuery>,
+    env: &Env,
+    transfer: &Transfer,
+) -> Result<(), Restriction> {
+    if let Some(from) = &transfer.from {
+        restrict(
+            has_transfer_access(deps.storage, transfer.denom.clone(), from.clone()),
+            RestrictionCode::NoTransferAccess,
+        )?;
+    }
+
+    evaluate_transfer(deps, env, transfer)
+}
+
+/// Checks of `force_transfer`: the sender is whitelisted, then the transfer pipeline of the
+/// denom.
+pub fn evaluate_force_transfer(
+    deps: Deps<ProvenanceQuery>,
+    env: &Env,
+    transfer: &Transfer,
+) -> Result<(), Restriction> {
+    if let Some(from) = &transfer.from {
+        restrict(
+            ensure_authorized_country(deps.storage, transfer.denom.clone(), from.clone()),
+            RestrictionCode::NotWhitelisted,
+        )?;
+    }
+
+    evaluate_transfer(deps, env, transfer)
+}
+
+/// Function to ensure the transfer pipeline of the denom allows a transfer
+pub fn ensure_transfer_allowed(
+    deps: Deps<ProvenanceQuery>,
+    env: &Env,
+    transfer: &Transfer,
+) -> Result<(), ContractError> {
+    evaluate_transfer(deps, env, transfer).map_err(|restriction| restriction.error)
+}
+
+fn check_rule(
+    deps: Deps<ProvenanceQuery>,
+    rule: &TransferRule,
+    transfer: &Transfer,
+    from: Option<&Addr>,
+    to: Option<&Addr>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let denom = transfer.denom.clone();
+    let amount = transfer.amount;
+
+    match rule {
+        TransferRule::MaxHolders { limit } => {
+            let count = HOLDER_COUNT
+                .may_load(deps.storage, denom.as_bytes())?
+                .unwrap_or_default();
+            let joining = match to {
+                Some(to) => !is_holder(deps.storage, &denom, to),
+                None => false,
+            };
+            let leaving = match from {
+                Some(from) => {
+                    let bal = get_consolidated_balance(deps, from.clone(), denom.clone())?;
+                    transfer.from_pending.apply(bal) <= amount
+                }
+                None => false,
+            };
+
+            if joining && !leaving && count >= *limit {
+                return Err(ContractError::MaxHoldersReached {
+                    denom,
+                    limit: *limit,
+                });
+            }
+        }
+        TransferRule::MinHolding { amount: min } => {
+            if let Some(to) = to {
+                let bal = get_consolidated_balance(deps, to.clone(), denom.clone())?;
+                let bal = transfer.to_pending.apply(bal);
+                if bal + amount < *min {
+                    return Err(ContractError::BelowMinHolding {
+                        address: to.clone(),
+                        min: *min,
+                    });
+                }
+            }
+            if let Some(from) = from {
+                let bal = get_consolidated_balance(deps, from.clone(), denom.clone())?;
+                let bal = transfer.from_pending.apply(bal);
+                let remaining = bal.saturating_sub(amount);
+                if !remaining.is_zero() && remaining < *min {
+                    return Err(ContractError::BelowMinHolding {
+                        address: from.clone(),
+                        min: *min,
+                    });
+                }
+            }
+        }
+        TransferRule::MaxHolding { amount: max } => {
+            if let Some(to) = to {
+                let bal = get_consolidated_balance(deps, to.clone(), denom.clone())?;
+                let bal = transfer.to_pending.apply(bal);
+                if bal + amount > *max {
+                    return Err(ContractError::AboveMaxHolding {
+                        address: to.clone(),
+                        max: *max,
+                    });
+                }
+            }
+        }
+        TransferRule::InvestorCategories { allowed } => {
+            if let Some(to) = to {
+                let key = Key::new(denom.clone(), to.clone()).as_bytes()?;
+                let category = INVESTOR_CATEGORIES.may_load(deps.storage, &key)?;
+                if !category.is_some_and(|category| allowed.contains(&category)) {
+                    return Err(ContractError::InvestorCategoryNotAllowed {
+                        denom,
+                        address: to.clone(),
+                    });
+                }
+            }
+        }
+        TransferRule::BlockedCountryPairs { pairs } => {
+            if let (Some(from), Some(to)) = (from, to) {
+                let from_key = Key::new(denom.clone(), from.clone()).as_bytes()?;
+                let to_key = Key::new(denom, to.clone()).as_bytes()?;
+                let codes = (
+                    WHITELIST.may_load(deps.storage, &from_key)?,
+                    WHITELIST.may_load(deps.storage, &to_key)?,
+                );
+                if let (Some(from), Some(to)) = codes {
+                    if pairs.contains(&CountryPair { from, to }) {
+                        return Err(ContractError::CountryPairRestricted { from, to });
+                    }
+                }
+            }
+        }
+        TransferRule::DailyVolumeCap { amount: cap } => {
+            if let Some(from) = from {
+                let sent = daily_volume(deps.storage, &denom, from, now)?;
+                if sent + amount > *cap {
+                    return Err(ContractError::DailyVolumeExceeded {
+                        address: from.clone(),
+                        remaining: cap.saturating_sub(sent),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Records an allowed transfer in the holder registry, daily volumes and the sender's lock-up
+/// lots. Call before the transfer message executes, as it reads the sender's balance beforehand.
+pub fn record_transfer(
+    deps: &mut DepsMut<ProvenanceQuery>,
+    env: &Env,
+    transfer: &Transfer,
+) -> Result<(), ContractError> {
+    let now = env.block.time.seconds();
+    let denom = &transfer.denom;
+
+    if let Some(from) = transfer
+        .from
+        .as_ref()
+        .filter(|from| **from != env.contract.address)
+    {
+        let bal = get_consolidated_balance(deps.as_ref(), from.clone(), denom.clone())?;
+        let bal = transfer.from_pending.apply(bal);
+        if bal <= transfer.amount {
+            remove_holder(deps.storage, denom, from)?;
+        }
+        consume_lockup_lots(
+            deps.storage,
+            denom.clone(),
+            from.clone(),
+            bal,
+            transfer.amount,
+            now,
+        )?;
+
+        let key = Key::new(denom.clone(), from.clone()).as_bytes()?;
+        let sent = daily_volume(deps.storage, denom, from, now)?;
+        DAILY_VOLUME.save(
+            deps.storage,
+            &key,
+            &DailyVolume {
+                day: now / SECONDS_PER_DAY,
+                amount: sent + transfer.amount,
+            },
+        )?;
+    }
+
+    if transfer.to != env.contract.address {
+        add_holder(deps.storage, denom, &transfer.to)?;
+    }
+
+    Ok(())
+}
+
+fn daily_volume(
+    storage: &dyn Storage,
+    denom: &str,
+    address: &Addr,
+    now: u64,
+) -> Result<Uint128, ContractError> {
+    let key = Key::new(denom.to_string(), address.clone()).as_bytes()?;
+
+    Ok(DAILY_VOLUME
+        .may_load(storage, &key)?
+        .filter(|volume| volume.day == now / SECONDS_PER_DAY)
+        .map(|volume| volume.amount)
+        .unwrap_or_default())
+}
+
+/// Records a burn in the holder registry and the address's lock-up lots: the address stops
+/// being a holder when the burn empties its balance, and burned locked tokens take their lots
+/// with them. Call before the burn message executes; `pending` was moved to and from the
+/// address earlier in the same batch.
+pub fn record_burn(
+    deps: &mut DepsMut<ProvenanceQuery>,
+    env: &Env,
+    denom: &str,
+    address: &Addr,
+    amount: Uint128,
+    pending: Pending,
+) -> Result<(), ContractError> {
+    let bal = get_consolidated_balance(deps.as_ref(), address.clone(), denom.to_string())?;
+    let bal = pending.apply(bal);
+    if bal <= amount {
+        remove_holder(deps.storage, denom, address)?;
+    }
+    consume_lockup_lots(
+        deps.storage,
+        denom.to_string(),
+        address.clone(),
+        bal,
+        amount,
+        env.block.time.seconds(),
+    )?;
+
+    Ok(())
+}
+
+/// Adds or removes an address in the holder registry by its current balance, for balances
+/// moved outside the contract, e.g. before the registry existed. Returns whether it holds.
+pub fn sync_holder(
+    deps: &mut DepsMut<ProvenanceQuery>,
+    denom: &str,
+    address: &Addr,
+) -> Result<bool, ContractError> {
+    let bal = get_consolidated_balance(deps.as_ref(), address.clone(), denom.to_string())?;
+    if bal.is_zero() {
+        remove_holder(deps.storage, denom, address)?;
+        Ok(false)
+    } else {
+        add_holder(deps.storage, denom, address)?;
+        Ok(true)
+    }
+}
+
+/// Moves a holder in the registry by its balance after a corporate action, from `denom` to
+/// `target_denom` when they differ.
+pub fn record_corporate_action(
+    storage: &mut dyn Storage,
+    denom: &str,
+    target_denom: &str,
+    address: &Addr,
+    balance: Uint128,
+) -> Result<(), ContractError> {
+    if balance.is_zero() || target_denom != denom {
+        remove_holder(storage, denom, address)?;
+    }
+    if !balance.is_zero() {
+        add_holder(storage, target_denom, address)?;
+    }
+
+    Ok(())
+}
+
+/// Moves a recovered holder in the registry to its new address.
+pub fn record_recovery(
+    storage: &mut dyn Storage,
+    denom: &str,
+    lost: &Addr,
+    new: &Addr,
+    balance: Uint128,
+) -> Result<(), ContractError> {
+    remove_holder(storage, denom, lost)?;
+    if !balance.is_zero() {
+        add_holder(storage, denom, new)?;
+    }
+
+    Ok(())
+}
+
+/// Holders of a denom in address order after `start_after`, at most `limit`.
+pub fn holders_after(
+    storage: &dyn Storage,
+    denom: &str,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> StdResult<Vec<Addr>> {
+    HOLDERS
+        .prefix(denom)
+        .keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
+}
+
+fn is_holder(storage: &dyn Storage, denom: &str, address: &Addr) -> bool {
+    HOLDERS.has(storage, (denom, address))
+}
+
+fn add_holder(storage: &mut dyn Storage, denom: &str, address: &Addr) -> Result<(), ContractError> {
+    if is_holder(storage, denom, address) {
+        return Ok(());
+    }
+
+    HOLDERS.save(storage, (denom, address), &true)?;
+    HOLDER_COUNT.update(storage, denom.as_bytes(), |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default() + 1)
+    })?;
+
+    Ok(())
+}
+
+fn remove_holder(
+    storage: &mut dyn Storage,
+    denom: &str,
+    address: &Addr,
+) -> Result<(), ContractError> {
+    if !is_holder(storage, denom, address) {
+        return Ok(());
+    }
+
+    HOLDERS.remove(storage, (denom, address));
+    HOLDER_COUNT.update(storage, denom.as_bytes(), |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default().saturating_sub(1))
+    })?;
+
+    Ok(())
+}
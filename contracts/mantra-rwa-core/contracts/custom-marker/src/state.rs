@@ -5,12 +5,19 @@ pub type Bytes<'a> = &'a [u8];
 pub const FREEZE_LIST: Map<Bytes, Vec<Addr>> = Map::new("freeze_list");
 pub const MINTED_TOKENS: Map<Bytes, Uint128> = Map::new("minted_tokens");
 pub const FROZEN_TOKENS: Map<Bytes, Uint128> = Map::new("frozen_tokens");
-pub const WHITELIST: Map<Bytes, u8> = Map::new("whitelist");
+pub const WHITELIST: Map<Bytes, u16> = Map::new("whitelist");
 pub const DENOM_CONFIG: Map<Bytes, DenomConfig> = Map::new("denom_config");
+// Partial freezes before freeze lots, moved to FREEZE_LOTS on migrate
 pub const PARTIAL_FREEZE: Map<Bytes, Uint128> = Map::new("partial_freeze");
+pub const FREEZE_LOTS: Map<Bytes, Vec<FreezeLot>> = Map::new("freeze_lots");
 
 pub const HOLDING_PERIOD: Map<Bytes, Uint64> = Map::new("holding_period");
 
+// Lock-up applied to every mint, in seconds
+pub const LOCKUP_PERIOD: Map<Bytes, Uint64> = Map::new("lockup_period");
+pub const LOCKUP_LOTS: Map<Bytes, Vec<LockupLot>> = Map::new("lockup_lots");
+pub const VESTING: Map<Bytes, VestingSchedule> = Map::new("vesting");
+
 // Admin
 pub const ADMIN: Item<Addr> = Item::new("admin");
 
@@ -24,3 +31,34 @@ pub const AGENTS: Map<Bytes, Vec<Addr>> = Map::new("agents_access");
 pub const ISSUER: Map<Bytes, Vec<AccessControls>> = Map::new("issuer");
 pub const TRANSFER_AGENT: Map<Bytes, Vec<AccessControls>> = Map::new("transfer_agent");
 pub const TOKENIZATION_AGENT: Map<Bytes, Vec<AccessControls>> = Map::new("tokenization_agent");
+
+// DvP orders by order id, their ids by buyer and by seller, and the ids of open orders by denom
+pub const DVP_ORDERS: Map<Bytes, DvpOrder> = Map::new("dvp_orders");
+pub const DVP_ORDERS_BY_PARTY: Map<(&Addr, &str), bool> = Map::new("dvp_orders_by_party");
+pub const OPEN_DVP_ORDERS: Map<(&str, &str), bool> = Map::new("open_dvp_orders");
+
+// Transfer rules of a denom, run in order after the built-in checks
+pub const TRANSFER_RULES: Map<Bytes, Vec<TransferRule>> = Map::new("transfer_rules");
+pub const INVESTOR_CATEGORIES: Map<Bytes, InvestorCategory> = Map::new("investor_categories");
+pub const TIER_POLICIES: Map<Bytes, Vec<TierPolicy>> = Map::new("tier_policies");
+pub const TIER_VERIFIERS: Map<Bytes, Addr> = Map::new("tier_verifiers");
+pub const DAILY_VOLUME: Map<Bytes, DailyVolume> = Map::new("daily_volume");
+
+// Holders by denom and address, and their number per denom
+pub const HOLDERS: Map<(&str, &Addr), bool> = Map::new("holders");
+pub const HOLDER_COUNT: Map<Bytes, u64> = Map::new("holder_count");
+
+// Corporate actions by action id, the one in progress per denom, and cash in lieu owed by
+// action id and address
+pub const CORPORATE_ACTIONS: Map<Bytes, CorporateActionRecord> = Map::new("corporate_actions");
+pub const ACTIVE_CORPORATE_ACTION: Map<Bytes, String> = Map::new("active_corporate_action");
+pub const CASH_IN_LIEU: Map<(&str, &Addr), CashInLieu> = Map::new("cash_in_lieu");
+
+// Account recoveries pending approval by denom and lost address, the new address of recovered
+// addresses, and approvals a recovery of a denom needs
+pub const RECOVERIES: Map<Bytes, AccountRecovery> = Map::new("recoveries");
+pub const RECOVERED_ACCOUNTS: Map<Bytes, Addr> = Map::new("recovered_accounts");
+pub const RECOVERY_THRESHOLD: Map<Bytes, u8> = Map::new("recovery_threshold");
+
+// ISO 3166-1 numeric codes of region groups by name
+pub const REGIONS: Map<&str, Vec<u16>> = Map::new("regions");
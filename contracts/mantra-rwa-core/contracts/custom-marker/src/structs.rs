@@ -25,6 +25,26 @@ pub struct ForceTransferParams {
 pub struct PartialFreezeParams {
     pub address: Addr,
     pub update_type: UpdateType<Uint128>,
+    /// Code of why the amount is frozen; an unfreeze with a reason only releases its freezes
+    pub reason: Option<u16>,
+    /// Timestamp in seconds the freeze expires at, for a freeze
+    pub expires_at: Option<Uint64>,
+}
+
+/// Amount of an address's balance frozen by one partial freeze.
+#[cw_serde]
+pub struct FreezeLot {
+    pub amount: Uint128,
+    pub reason: Option<u16>,
+    /// Released once the block time passes it, when set
+    pub expires_at: Option<Uint64>,
+}
+
+impl FreezeLot {
+    pub fn is_active(&self, current_timestamp: u64) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at.u64() > current_timestamp)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
@@ -75,8 +95,8 @@ pub struct DenomConfig {
     /// token limit for each token holder (eg. token limit for each user = 1000,
     /// users can only hold up to 1000 tokens.
     pub token_limit: Uint128,
-    /// Country code
-    pub country_codes: Vec<u8>,
+    /// ISO 3166-1 numeric country codes
+    pub country_codes: Vec<u16>,
 }
 
 impl DenomConfig {
@@ -95,7 +115,7 @@ impl DenomConfig {
         self.token_limit -= token_limit;
     }
 
-    pub fn add_country_codes(&mut self, country_codes: Vec<u8>) -> Result<(), ContractError> {
+    pub fn add_country_codes(&mut self, country_codes: Vec<u16>) -> Result<(), ContractError> {
         for code in country_codes {
             add_country_codes(&mut self.country_codes, code)?;
         }
@@ -103,7 +123,7 @@ impl DenomConfig {
         Ok(())
     }
 
-    pub fn remove_country_codes(&mut self, country_codes: Vec<u8>) -> Result<(), ContractError> {
+    pub fn remove_country_codes(&mut self, country_codes: Vec<u16>) -> Result<(), ContractError> {
         for code in country_codes {
             remove_country_codes(&mut self.country_codes, code)?;
         }
@@ -133,5 +153,257 @@ pub struct WhiteListParams {
 #[cw_serde]
 pub struct CountryCodeData {
     pub address: Addr,
-    pub country_code: UpdateKind<u8>,
+    pub country_code: UpdateKind<CountryCode>,
+}
+
+// DvP Order Params
+#[cw_serde]
+pub struct DvpOrderParams {
+    pub order_id: String,
+    pub denom: String,
+    pub amount: Uint128,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub payment_asset: PaymentAsset,
+    pub price: Uint128,
+    pub expiry: Uint64, // Timestamp in seconds
+}
+
+/// Delivery-versus-payment order. The seller escrows `amount` marker tokens and the buyer
+/// escrows `price` of `payment_asset`; whichever leg arrives second swaps both.
+#[cw_serde]
+pub struct DvpOrder {
+    pub order_id: String,
+    pub denom: String,
+    pub amount: Uint128,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub payment_asset: PaymentAsset,
+    pub price: Uint128,
+    pub expiry: Uint64,
+    /// Marker tokens are held by the contract
+    pub delivered: bool,
+    /// Payment is held by the contract
+    pub paid: bool,
+    pub status: DvpStatus,
+}
+
+impl DvpOrder {
+    pub fn new(params: DvpOrderParams) -> Self {
+        Self {
+            order_id: params.order_id,
+            denom: params.denom,
+            amount: params.amount,
+            seller: params.seller,
+            buyer: params.buyer,
+            payment_asset: params.payment_asset,
+            price: params.price,
+            expiry: params.expiry,
+            delivered: false,
+            paid: false,
+            status: DvpStatus::Open,
+        }
+    }
+
+    pub fn is_expired(&self, current_timestamp: u64) -> bool {
+        self.expiry.u64() <= current_timestamp
+    }
+}
+
+/// Tokens of one mint, locked until `unlock_at`.
+#[cw_serde]
+pub struct LockupLot {
+    pub amount: Uint128,
+    pub unlock_at: Uint64, // Timestamp in seconds
+}
+
+/// Vesting of `amount` tokens: nothing vests before `cliff`, then the vested amount grows
+/// linearly from `start` until everything has vested at `end`. A pure cliff schedule sets
+/// `cliff` equal to `end`.
+#[cw_serde]
+pub struct VestingSchedule {
+    pub amount: Uint128,
+    pub start: Uint64, // Timestamp in seconds
+    pub cliff: Uint64, // Timestamp in seconds
+    pub end: Uint64,   // Timestamp in seconds
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.amount.is_zero() {
+            return Err(ContractError::InvalidVestingSchedule {
+                err: "amount must be greater than zero".into(),
+            });
+        }
+        if self.start > self.cliff || self.cliff > self.end {
+            return Err(ContractError::InvalidVestingSchedule {
+                err: "expected start <= cliff <= end".into(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn vested(&self, current_timestamp: u64) -> Uint128 {
+        if current_timestamp < self.cliff.u64() {
+            Uint128::zero()
+        } else if current_timestamp >= self.end.u64() {
+            self.amount
+        } else {
+            self.amount.multiply_ratio(
+                current_timestamp - self.start.u64(),
+                self.end.u64() - self.start.u64(),
+            )
+        }
+    }
+
+    pub fn unvested(&self, current_timestamp: u64) -> Uint128 {
+        self.amount - self.vested(current_timestamp)
+    }
+}
+
+#[cw_serde]
+pub struct VestingParams {
+    pub address: Addr,
+    pub schedule: UpdateKind<VestingSchedule>,
+}
+
+/// Balance of a holder and the parts of it that are locked.
+#[cw_serde]
+pub struct LockedBalance {
+    pub balance: Uint128,
+    /// Held by lock-up lots that haven't unlocked
+    pub locked_lots: Uint128,
+    /// Held by the vesting schedule
+    pub unvested: Uint128,
+    pub transferable: Uint128,
+}
+
+/// Balance of an address split by its partial freezes.
+#[cw_serde]
+pub struct SpendableBalance {
+    pub balance: Uint128,
+    /// Held by partial freezes that haven't expired
+    pub frozen: Uint128,
+    pub spendable: Uint128,
+}
+
+#[cw_serde]
+pub struct CountryPair {
+    /// ISO 3166-1 numeric country code
+    pub from: u16,
+    /// ISO 3166-1 numeric country code
+    pub to: u16,
+}
+
+/// Holding cap and allowed actions of the holders of one investor tier of a denom.
+#[cw_serde]
+pub struct TierPolicy {
+    pub category: InvestorCategory,
+    /// Replaces the token_limit of the denom config for holders of the tier
+    pub token_limit: Option<Uint128>,
+    pub actions: Vec<TierAction>,
+}
+
+#[cw_serde]
+pub struct InvestorCategoryParams {
+    pub address: Addr,
+    pub category: UpdateKind<InvestorCategory>,
+}
+
+/// Amount an address has sent on one UTC day.
+#[cw_serde]
+pub struct DailyVolume {
+    pub day: u64,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct CanTransferResponse {
+    /// Numeric `restriction`, 0 when the transfer is allowed
+    pub code: u8,
+    pub restriction: RestrictionCode,
+    /// Error the transfer would fail with
+    pub reason: Option<String>,
+}
+
+/// Price the fractional tokens of a corporate action are accounted at as cash in lieu.
+#[cw_serde]
+pub struct CashInLieuPrice {
+    pub payment_asset: PaymentAsset,
+    /// Price of one whole token
+    pub price: Uint128,
+}
+
+// Corporate Action Params
+#[cw_serde]
+pub struct CorporateActionParams {
+    pub action_id: String,
+    pub denom: String,
+    pub action: CorporateAction,
+    pub cash_in_lieu: Option<CashInLieuPrice>,
+}
+
+/// Corporate action of a denom, applied to its holders a batch at a time.
+#[cw_serde]
+pub struct CorporateActionRecord {
+    pub action_id: String,
+    pub denom: String,
+    pub action: CorporateAction,
+    pub cash_in_lieu: Option<CashInLieuPrice>,
+    pub status: CorporateActionStatus,
+    pub holders_processed: u64,
+    /// Minted to holders, in the denom they hold after the action
+    pub minted: Uint128,
+    pub burned: Uint128,
+    /// Fractional tokens rounded off the balances
+    pub fractions: Decimal,
+    /// Cash in lieu owed for the fractional tokens
+    pub cash_in_lieu_total: Uint128,
+    pub started_at: Uint64, // Timestamp in seconds
+    /// Timestamp in seconds the action completed or was cancelled at
+    pub completed_at: Option<Uint64>,
+    /// Last holder processed; the next batch starts after it
+    pub last_holder: Option<Addr>,
+}
+
+impl CorporateActionRecord {
+    pub fn new(params: CorporateActionParams, started_at: Uint64) -> Self {
+        Self {
+            action_id: params.action_id,
+            denom: params.denom,
+            action: params.action,
+            cash_in_lieu: params.cash_in_lieu,
+            status: CorporateActionStatus::InProgress,
+            holders_processed: 0,
+            minted: Uint128::zero(),
+            burned: Uint128::zero(),
+            fractions: Decimal::zero(),
+            cash_in_lieu_total: Uint128::zero(),
+            started_at,
+            completed_at: None,
+            last_holder: None,
+        }
+    }
+}
+
+/// Recovery of the holdings of a lost address to a new address, pending approval by the
+/// agents of the denom.
+#[cw_serde]
+pub struct AccountRecovery {
+    pub denom: String,
+    pub lost: Addr,
+    pub new: Addr,
+    /// Hash of the off-chain evidence of the loss, e.g. a SHA-256 hex digest
+    pub evidence_hash: String,
+    pub approvals: Vec<Addr>,
+    pub created_at: Uint64, // Timestamp in seconds
+}
+
+/// Fractional token a corporate action rounded off a holder's balance, and the cash owed for it.
+#[cw_serde]
+pub struct CashInLieu {
+    pub address: Addr,
+    pub fraction: Decimal,
+    pub amount: Uint128,
 }
@@ -4,10 +4,14 @@ use crate::contract::*;
 use cosmwasm_std::{
     coin, from_binary,
     testing::{mock_env, mock_info, MockApi, MockStorage},
-    Binary, Coin, CosmosMsg, OwnedDeps,
+    to_json_binary, BankMsg, Coin, CosmosMsg, OwnedDeps, WasmMsg,
 };
-use provwasm_mocks::{mock_dependencies, ProvenanceMockQuerier};
-use provwasm_std::{ProvenanceMsg, ProvenanceMsgParams, ProvenanceQuery, ProvenanceRoute};
+use cw20::Cw20ReceiveMsg;
+use provwasm_mocks::{
+    mock_dependencies, mock_dependencies_with_balances, must_read_binary_file,
+    ProvenanceMockQuerier,
+};
+use provwasm_std::{Marker, ProvenanceMsg, ProvenanceMsgParams, ProvenanceQuery, ProvenanceRoute};
 
 fn do_init(
     deps: &mut OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
@@ -41,7 +45,7 @@ fn do_create_marker(
 ) -> Result<Response<ProvenanceMsg>, ContractError> {
     let _ = do_init(deps, env.clone(), info.clone());
 
-    let country_codes = vec![91, 1];
+    let country_codes = vec![356, 840];
 
     let mut msg = ExecuteMsg::ManageRoles {
         denom: String::default(),
@@ -82,7 +86,7 @@ fn do_whitelist(
             denom: "budz".into(),
             data: vec![CountryCodeData {
                 address,
-                country_code: UpdateKind::Set(91),
+                country_code: UpdateKind::Set(CountryCode::Numeric(356)),
             }],
         }],
     };
@@ -108,6 +112,15 @@ fn do_add_issuer(
     execute(deps.as_mut(), env, info, msg).unwrap();
 }
 
+// Registers the `budz` marker of testdata with the mock querier, for burns and transfers
+fn do_mock_marker(
+    deps: &mut OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+) {
+    let bin = must_read_binary_file("testdata/marker.json");
+    let marker: Marker = from_binary(&bin).unwrap();
+    deps.querier.with_markers(vec![marker]);
+}
+
 #[test]
 fn create_marker() {
     // Create default provenance mocks.
@@ -137,9 +150,9 @@ fn create_marker() {
         denom: denom.clone(),
     };
     let res = query(deps.as_ref(), env.clone(), msg).unwrap();
-    let country_codes: Vec<u8> = from_binary(&res).unwrap();
-    assert!(country_codes.contains(&91));
-    assert!(country_codes.contains(&1));
+    let country_codes: Vec<u16> = from_binary(&res).unwrap();
+    assert!(country_codes.contains(&356));
+    assert!(country_codes.contains(&840));
 
     // Check denom config
     msg = QueryMsg::GetDenomConfig {
@@ -147,7 +160,7 @@ fn create_marker() {
     };
     let res = query(deps.as_ref(), env, msg).unwrap();
     let denom_config: DenomConfig = from_binary(&res).unwrap();
-    assert!(denom_config.country_codes.contains(&91));
+    assert!(denom_config.country_codes.contains(&356));
     assert_eq!(denom_config.token_limit.u128(), 1000);
 
     // Verifying accesses
@@ -194,8 +207,8 @@ fn whitelist() {
         address: to_address.clone(),
     };
     let res = query(deps.as_ref(), env.clone(), msg).unwrap();
-    let country_code: u8 = from_binary(&res).unwrap();
-    assert_eq!(country_code, 91);
+    let country_code: u16 = from_binary(&res).unwrap();
+    assert_eq!(country_code, 356);
 
     // Unsetting the whitelisting
     let msg = ExecuteMsg::Whitelist {
@@ -216,7 +229,7 @@ fn whitelist() {
         address: to_address,
     };
     let err = query(deps.as_ref(), env, msg).unwrap_err();
-    assert_eq!(err, StdError::NotFound { kind: "u8".into() });
+    assert_eq!(err, StdError::NotFound { kind: "u16".into() });
 }
 
 #[test]
@@ -551,6 +564,8 @@ fn update_frozen_balance() {
         params: vec![PartialFreezeParams {
             update_type: UpdateType::Add(frozen_balance),
             address: address.clone(),
+            reason: None,
+            expires_at: None,
         }],
     };
 
@@ -575,6 +590,8 @@ fn update_frozen_balance() {
         params: vec![PartialFreezeParams {
             update_type: UpdateType::Remove(frozen_balance),
             address: address.clone(),
+            reason: None,
+            expires_at: None,
         }],
     };
 
@@ -594,6 +611,283 @@ fn update_frozen_balance() {
         .eq(&Uint128::zero()));
 }
 
+#[test]
+fn test_spendable_balance() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies_with_balances(&[("holder", &[coin(1000, "budz")])]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let denom = String::from("budz");
+    let holder = Addr::unchecked("holder");
+    let receiver = Addr::unchecked("receiver");
+    let now = env.block.time.seconds();
+
+    do_mock_marker(&mut deps);
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), holder.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+
+    // Tracking the minted supply the mocked balance of the holder stands for; as balances of
+    // the mocks don't change, it is minted to the receiver to stay within the token limit
+    let msg = ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: receiver.clone(),
+                amount: Uint128::new(1000),
+            }],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let freeze = |update_type: UpdateType<Uint128>, reason: Option<u16>, expires_at: u64| {
+        ExecuteMsg::PartialFreeze {
+            denom: denom.clone(),
+            params: vec![PartialFreezeParams {
+                address: holder.clone(),
+                update_type,
+                reason,
+                expires_at: Some(Uint64::new(expires_at)),
+            }],
+        }
+    };
+
+    // Fail due to an expiry in the past
+    let msg = freeze(UpdateType::Add(Uint128::new(600)), Some(7), now);
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidFreezeExpiry {
+            expires_at: Uint64::new(now)
+        }
+    );
+
+    let msg = freeze(UpdateType::Add(Uint128::new(600)), Some(7), now + 100);
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let balance_msg = QueryMsg::GetSpendableBalance {
+        denom: denom.clone(),
+        address: holder.clone(),
+    };
+    let res: SpendableBalance =
+        from_binary(&query(deps.as_ref(), env.clone(), balance_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        SpendableBalance {
+            balance: Uint128::new(1000),
+            frozen: Uint128::new(600),
+            spendable: Uint128::new(400),
+        }
+    );
+
+    let frozen_err = |spendable: u128| ContractError::BalanceFrozen {
+        denom: denom.clone(),
+        address: holder.clone(),
+        spendable: Uint128::new(spendable),
+    };
+
+    // Giving transfer access to the holder
+    let msg = ExecuteMsg::ManageRoles {
+        denom: denom.clone(),
+        roles: vec![Role::Agent {
+            update_type: UpdateType::Add(vec![holder.clone()]),
+            marker_access: vec![AccessControls::Transfer],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Only the spendable balance is sent
+    let send = ExecuteMsg::Send {
+        amount: Uint128::new(500),
+        denom: denom.clone(),
+        to: receiver.clone(),
+    };
+    let holder_info = mock_info(holder.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), holder_info.clone(), send).unwrap_err();
+    assert_eq!(err, frozen_err(400));
+
+    // Only the spendable balance is force transferred, net of earlier transfers of the batch
+    let force_transfer = |amounts: &[u128]| ExecuteMsg::ForceTransfer {
+        denom: denom.clone(),
+        params: amounts
+            .iter()
+            .map(|amount| ForceTransferParams {
+                amount: Uint128::new(*amount),
+                from: holder.clone(),
+                to: receiver.clone(),
+            })
+            .collect(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        force_transfer(&[500]),
+    )
+    .unwrap_err();
+    assert_eq!(err, frozen_err(400));
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        force_transfer(&[300, 300]),
+    )
+    .unwrap_err();
+    assert_eq!(err, frozen_err(100));
+
+    // Only the spendable balance is burned, net of earlier burns of the batch
+    let burn = |amounts: &[u128]| ExecuteMsg::BurnFrom {
+        burn_from_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: amounts
+                .iter()
+                .map(|amount| MintBurnData {
+                    address: holder.clone(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        }],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), burn(&[500])).unwrap_err();
+    assert_eq!(err, frozen_err(400));
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), burn(&[300, 300])).unwrap_err();
+    assert_eq!(err, frozen_err(100));
+
+    // Frozen and unvested tokens add up
+    let msg = ExecuteMsg::UpdateVesting {
+        denom: denom.clone(),
+        params: vec![VestingParams {
+            address: holder.clone(),
+            schedule: UpdateKind::Set(VestingSchedule {
+                amount: Uint128::new(300),
+                start: Uint64::new(now),
+                cliff: Uint64::new(now + 100),
+                end: Uint64::new(now + 1000),
+            }),
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        force_transfer(&[200]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TokensLocked {
+            denom: denom.clone(),
+            address: holder.clone(),
+            transferable: Uint128::new(100)
+        }
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        force_transfer(&[100]),
+    )
+    .unwrap();
+    execute(deps.as_mut(), env.clone(), info.clone(), burn(&[400])).unwrap();
+
+    // Unfreezes release only what their reason froze
+    let msg = freeze(UpdateType::Remove(Uint128::new(100)), Some(3), now + 100);
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnfreezeExceedsFrozen {
+            address: holder.clone(),
+            frozen: Uint128::zero()
+        }
+    );
+    let msg = freeze(UpdateType::Remove(Uint128::new(100)), Some(7), now + 100);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let lots_msg = QueryMsg::GetFreezeLots {
+        denom: denom.clone(),
+        address: holder.clone(),
+    };
+    let lots: Vec<FreezeLot> =
+        from_binary(&query(deps.as_ref(), env.clone(), lots_msg).unwrap()).unwrap();
+    assert_eq!(
+        lots,
+        vec![FreezeLot {
+            amount: Uint128::new(500),
+            reason: Some(7),
+            expires_at: Some(Uint64::new(now + 100)),
+        }]
+    );
+
+    // The freeze is released once it expires
+    let mut later = env;
+    later.block.time = later.block.time.plus_seconds(200);
+    let res: SpendableBalance =
+        from_binary(&query(deps.as_ref(), later, balance_msg).unwrap()).unwrap();
+    assert_eq!(res.frozen, Uint128::zero());
+    assert_eq!(res.spendable, Uint128::new(1000));
+}
+
+#[test]
+fn test_batched_credits() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies_with_balances(&[("holder", &[coin(1200, "budz")])]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let denom = String::from("budz");
+    let holder = Addr::unchecked("holder");
+    let receiver = Addr::unchecked("receiver");
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), holder.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+
+    let limit_err = ContractError::TokenLimitExceeded {
+        address: receiver.clone(),
+    };
+
+    // Mints of the batch to the same address add up against its token limit of 1000
+    let mint = |amounts: &[u128]| ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: amounts
+                .iter()
+                .map(|amount| MintBurnData {
+                    address: receiver.clone(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        }],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), mint(&[600, 600])).unwrap_err();
+    assert_eq!(err, limit_err);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), mint(&[600, 400])).unwrap();
+    assert_eq!(res.messages.len(), 4);
+
+    // So do force transfers of the batch to the same address
+    let force_transfer = |amounts: &[u128]| ExecuteMsg::ForceTransfer {
+        denom: denom.clone(),
+        params: amounts
+            .iter()
+            .map(|amount| ForceTransferParams {
+                amount: Uint128::new(*amount),
+                from: holder.clone(),
+                to: receiver.clone(),
+            })
+            .collect(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        force_transfer(&[600, 600]),
+    )
+    .unwrap_err();
+    assert_eq!(err, limit_err);
+    let res = execute(deps.as_mut(), env, info, force_transfer(&[600, 400])).unwrap();
+    assert_eq!(res.messages.len(), 2);
+}
+
 #[test]
 fn update_token_limit() {
     // Create default provenance mocks.
@@ -630,7 +924,7 @@ fn update_country_code() {
     let env = mock_env();
     let info = mock_info("sender", &[]);
     let denom = String::from("budz");
-    let mut country_code = 91;
+    let mut country_code = 356;
 
     // Create marker
     do_create_marker(&mut deps, env.clone(), info.clone(), denom.to_string()).unwrap();
@@ -638,7 +932,7 @@ fn update_country_code() {
     // Try to add existing country code again
     let msg = ExecuteMsg::UpdateCountryCode {
         denom: denom.clone(),
-        update_type: UpdateType::Add(country_code),
+        update_type: UpdateType::Add(Countries::Code(CountryCode::Numeric(country_code))),
     };
 
     // Call execute and ensure a cosmos message was dispatched
@@ -649,10 +943,10 @@ fn update_country_code() {
     );
 
     // Try to add new country code
-    country_code = 61;
+    country_code = 36;
     let msg = ExecuteMsg::UpdateCountryCode {
         denom: denom.clone(),
-        update_type: UpdateType::Add(country_code),
+        update_type: UpdateType::Add(Countries::Code(CountryCode::Numeric(country_code))),
     };
 
     // Call execute and ensure a cosmos message was dispatched
@@ -668,10 +962,10 @@ fn update_country_code() {
     assert!(denom_config.country_codes.contains(&country_code));
 
     // Try to remove recent country code added
-    country_code = 61;
+    country_code = 36;
     let msg = ExecuteMsg::UpdateCountryCode {
         denom: denom.clone(),
-        update_type: UpdateType::Remove(country_code),
+        update_type: UpdateType::Remove(Countries::Code(CountryCode::Numeric(country_code))),
     };
 
     // Call execute and ensure a cosmos message was dispatched
@@ -689,7 +983,7 @@ fn update_country_code() {
     // Try to remove non-existing country code
     let msg = ExecuteMsg::UpdateCountryCode {
         denom: denom.clone(),
-        update_type: UpdateType::Remove(country_code),
+        update_type: UpdateType::Remove(Countries::Code(CountryCode::Numeric(country_code))),
     };
 
     // Call execute and ensure a cosmos message was dispatched
@@ -701,136 +995,1547 @@ fn update_country_code() {
 }
 
 #[test]
-fn test_mint_to() {
+fn test_country_regions() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let denom = String::from("budz");
+    let holder = Addr::unchecked("holder");
+
+    // Create marker
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+
+    // Default region groups are stored on instantiate
+    let msg = QueryMsg::GetRegion { name: "GCC".into() };
+    let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+    let gcc: Vec<u16> = from_binary(&res).unwrap();
+    assert_eq!(gcc, vec![784, 48, 414, 512, 634, 682]);
+
+    // Whitelisting by alpha-2 code stores the numeric code
+    let msg = ExecuteMsg::Whitelist {
+        lists: vec![WhiteListParams {
+            denom: denom.clone(),
+            data: vec![CountryCodeData {
+                address: holder.clone(),
+                country_code: UpdateKind::Set(CountryCode::Alpha2("ae".into())),
+            }],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = QueryMsg::GetCountryCodeByAddress {
+        denom: denom.clone(),
+        address: holder.clone(),
+    };
+    let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+    let country_code: u16 = from_binary(&res).unwrap();
+    assert_eq!(country_code, 784);
+
+    // Codes not assigned by ISO 3166-1 are rejected
+    let msg = ExecuteMsg::Whitelist {
+        lists: vec![WhiteListParams {
+            denom: denom.clone(),
+            data: vec![CountryCodeData {
+                address: holder.clone(),
+                country_code: UpdateKind::Set(CountryCode::Numeric(91)),
+            }],
+        }],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCountryCode { code: "91".into() });
+
+    // Add the GCC as a unit
+    let msg = ExecuteMsg::UpdateCountryCode {
+        denom: denom.clone(),
+        update_type: UpdateType::Add(Countries::Region("gcc".into())),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = QueryMsg::GetAuthorizedCountries {
+        denom: denom.clone(),
+    };
+    let res = query(deps.as_ref(), env.clone(), msg.clone()).unwrap();
+    let country_codes: Vec<u16> = from_binary(&res).unwrap();
+    assert_eq!(country_codes, vec![356, 840, 784, 48, 414, 512, 634, 682]);
+
+    // Update the region group and remove it as a unit
+    let msg = ExecuteMsg::UpdateRegion {
+        name: "gcc".into(),
+        codes: vec![
+            CountryCode::Alpha2("AE".into()),
+            CountryCode::Alpha2("SA".into()),
+        ],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateCountryCode {
+        denom: denom.clone(),
+        update_type: UpdateType::Remove(Countries::Region("GCC".into())),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let msg = QueryMsg::GetAuthorizedCountries {
+        denom: denom.clone(),
+    };
+    let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+    let country_codes: Vec<u16> = from_binary(&res).unwrap();
+    assert_eq!(country_codes, vec![356, 840, 48, 414, 512, 634]);
+
+    // Unknown region groups fail
+    let msg = ExecuteMsg::UpdateCountryCode {
+        denom: denom.clone(),
+        update_type: UpdateType::Add(Countries::Region("asean".into())),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RegionNotFound {
+            name: "asean".into()
+        }
+    );
+}
+
+#[test]
+fn test_migrate_country_codes() {
+    let mut deps = cosmwasm_std::testing::mock_dependencies();
+    let key = Key::new(String::from("budz"), Addr::unchecked("holder"))
+        .as_bytes()
+        .unwrap();
+
+    // Legacy code without a country fails
+    WHITELIST.save(&mut deps.storage, &key, &91).unwrap();
+    let msg = MigrateMsg {
+        country_codes: vec![],
+    };
+    let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidMigration {
+            err: "legacy country code `91` has no country given".into()
+        }
+    );
+
+    // Legacy code is replaced by its country
+    let msg = MigrateMsg {
+        country_codes: vec![LegacyCountryCode {
+            legacy: 91,
+            code: CountryCode::Alpha2("IN".into()),
+        }],
+    };
+    migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+    assert_eq!(WHITELIST.load(&deps.storage, &key).unwrap(), 356);
+    assert!(load_region(&deps.storage, "EU").is_ok());
+
+    // Fail due to migrating legacy codes again
+    let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidMigration {
+            err: "legacy country codes are already migrated".into()
+        }
+    );
+
+    // Later migrations keep the stored codes
+    let msg = MigrateMsg {
+        country_codes: vec![],
+    };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+    assert_eq!(WHITELIST.load(&deps.storage, &key).unwrap(), 356);
+}
+
+#[test]
+fn test_mint_to() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let sender = Addr::unchecked("sender");
+    let mut info = mock_info(sender.as_str(), &[]);
+    let denom = String::from("budz");
+    let amount = Uint128::new(500);
+
+    // Create marker
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.to_string()).unwrap();
+
+    // Whitelist address
+    do_whitelist(&mut deps, env.clone(), info.clone(), sender.clone()).unwrap();
+
+    // Try mintTo
+    let msg = ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: sender.clone(),
+                amount,
+            }],
+        }],
+    };
+
+    // Fail due to no access rights
+    info = mock_info("random", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            err: "Address `random`: Don't have Issuer, Tokenization, Sub Admin or Mint rights!"
+                .into()
+        }
+    );
+
+    // Providing Issuer access
+    info = mock_info(sender.as_str(), &[]);
+    do_add_issuer(&mut deps, env, info.clone(), sender.clone());
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(1, res.messages.len());
+}
+
+fn dvp_order_msg(env: &Env, buyer: Addr, payment_asset: PaymentAsset) -> ExecuteMsg {
+    ExecuteMsg::CreateDvpOrder {
+        params: DvpOrderParams {
+            order_id: "order-1".into(),
+            denom: "budz".into(),
+            amount: Uint128::new(500),
+            seller: Addr::unchecked("issuer"),
+            buyer,
+            payment_asset,
+            price: Uint128::new(1000),
+            expiry: Uint64::new(env.block.time.seconds() + 3600),
+        },
+    }
+}
+
+fn do_create_dvp_order(
+    deps: &mut OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+    env: Env,
+    buyer: Addr,
+    payment_asset: PaymentAsset,
+) -> Result<Response<ProvenanceMsg>, ContractError> {
+    // Create marker, with `issuer` as the seller
+    let info = mock_info("sender", &[]);
+    do_create_marker(deps, env.clone(), info.clone(), "budz".into()).unwrap();
+
+    // Whitelist buyer
+    do_whitelist(deps, env.clone(), info, buyer.clone()).unwrap();
+
+    let msg = dvp_order_msg(&env, buyer.clone(), payment_asset);
+    execute(deps.as_mut(), env, mock_info(buyer.as_str(), &[]), msg)
+}
+
+fn query_dvp_order(
+    deps: &OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+) -> DvpOrder {
+    let msg = QueryMsg::GetDvpOrder {
+        order_id: "order-1".into(),
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_dvp_native_payment() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let buyer = Addr::unchecked("buyer");
+    let payment_asset = PaymentAsset::Native {
+        denom: "uusdc".into(),
+    };
+
+    let res =
+        do_create_dvp_order(&mut deps, env.clone(), buyer.clone(), payment_asset.clone()).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    // Fail due to duplicate order id
+    let msg = dvp_order_msg(&env, buyer.clone(), payment_asset);
+    let info = mock_info(buyer.as_str(), &[]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DvpOrderExists {
+            order_id: "order-1".into()
+        }
+    );
+
+    // Fail due to wrong payment
+    let msg = ExecuteMsg::PayDvp {
+        order_id: "order-1".into(),
+    };
+    let info = mock_info(buyer.as_str(), &[coin(999, "uusdc")]);
+    let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidDvpPayment {
+            expected: "1000uusdc".into()
+        }
+    );
+
+    // Escrow payment, nothing is settled yet
+    let info = mock_info(buyer.as_str(), &[coin(1000, "uusdc")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(0, res.messages.len());
+    let order = query_dvp_order(&deps);
+    assert!(order.paid && !order.delivered);
+    assert_eq!(order.status, DvpStatus::Open);
+
+    // Fail due to caller not being the seller
+    let msg = ExecuteMsg::DeliverDvp {
+        order_id: "order-1".into(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            err: "Address `random`: Not the seller of the DvP order!".into()
+        }
+    );
+
+    // Delivery swaps both legs
+    let res = execute(deps.as_mut(), env.clone(), mock_info("issuer", &[]), msg).unwrap();
+    assert_eq!(3, res.messages.len());
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "issuer".into(),
+            amount: vec![coin(1000, "uusdc")],
+        })
+    );
+    assert_eq!(query_dvp_order(&deps).status, DvpStatus::Settled);
+
+    // Settled orders can't be cancelled
+    let msg = ExecuteMsg::CancelDvp {
+        order_id: "order-1".into(),
+    };
+    let err = execute(deps.as_mut(), env, mock_info(buyer.as_str(), &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DvpOrderClosed {
+            order_id: "order-1".into(),
+            status: DvpStatus::Settled
+        }
+    );
+}
+
+#[test]
+fn test_dvp_cw20_payment() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let buyer = Addr::unchecked("buyer");
+    let token = Addr::unchecked("usdc_token");
+
+    do_create_dvp_order(
+        &mut deps,
+        env.clone(),
+        buyer.clone(),
+        PaymentAsset::Cw20 {
+            address: token.clone(),
+        },
+    )
+    .unwrap();
+
+    // Deliver first
+    let msg = ExecuteMsg::DeliverDvp {
+        order_id: "order-1".into(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("issuer", &[]), msg).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert!(query_dvp_order(&deps).delivered);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: buyer.to_string(),
+        amount: Uint128::new(1000),
+        msg: to_json_binary(&ReceiveMsg::PayDvp {
+            order_id: "order-1".into(),
+        })
+        .unwrap(),
+    });
+
+    // Fail due to payment in a different token
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("other_token", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidDvpPayment {
+            expected: "1000usdc_token".into()
+        }
+    );
+
+    // Payment swaps both legs
+    let res = execute(deps.as_mut(), env, mock_info(token.as_str(), &[]), msg).unwrap();
+    assert_eq!(2, res.messages.len());
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "issuer".into(),
+                amount: Uint128::new(1000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let msg = QueryMsg::GetDvpOrders {
+        address: buyer,
+        start_after: None,
+        limit: None,
+    };
+    let orders: Vec<DvpOrder> =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(1, orders.len());
+    assert_eq!(orders[0].status, DvpStatus::Settled);
+}
+
+#[test]
+fn test_cancel_dvp() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    let buyer = Addr::unchecked("buyer");
+
+    do_create_dvp_order(
+        &mut deps,
+        env.clone(),
+        buyer.clone(),
+        PaymentAsset::Native {
+            denom: "uusdc".into(),
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::DeliverDvp {
+        order_id: "order-1".into(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("issuer", &[]), msg).unwrap();
+
+    // Fail due to caller not being a party before expiry
+    let msg = ExecuteMsg::CancelDvp {
+        order_id: "order-1".into(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            err: "Address `random`: Not a party of the DvP order!".into()
+        }
+    );
+
+    // Payment is refused after expiry
+    env.block.time = env.block.time.plus_seconds(3600);
+    let pay = ExecuteMsg::PayDvp {
+        order_id: "order-1".into(),
+    };
+    let info = mock_info(buyer.as_str(), &[coin(1000, "uusdc")]);
+    let err = execute(deps.as_mut(), env.clone(), info, pay).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DvpOrderExpired {
+            order_id: "order-1".into(),
+            expiry: env.block.time.seconds()
+        }
+    );
+
+    // Anyone can expire it, the delivered tokens go back to the seller
+    let res = execute(deps.as_mut(), env, mock_info("random", &[]), msg).unwrap();
+    assert_eq!(1, res.messages.len());
+    match &res.messages[0].msg {
+        CosmosMsg::Custom(ProvenanceMsg { route, params, .. }) => {
+            assert!(matches!(route, ProvenanceRoute::Marker));
+            assert!(matches!(params, ProvenanceMsgParams::Marker(_)));
+        }
+        _ => panic!("unexpected cosmos message"),
+    }
+    assert_eq!(query_dvp_order(&deps).status, DvpStatus::Expired);
+}
+
+#[test]
+fn test_lockup_lots() {
+    // Create default provenance mocks, the holder holds 500 tokens
+    let mut deps = mock_dependencies_with_balances(&[("holder", &[coin(500, "budz")])]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let holder = Addr::unchecked("holder");
+    let receiver = Addr::unchecked("receiver");
+    let denom = String::from("budz");
+    let period = Uint64::new(40 * 24 * 60 * 60);
+
+    do_mock_marker(&mut deps);
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), holder.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+
+    // Fail due to no access rights
+    let msg = ExecuteMsg::UpdateLockupPeriod {
+        denom: denom.clone(),
+        period,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotSubAdmin {
+            address: Addr::unchecked("random")
+        }
+    );
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Mint is locked as a lot
+    let msg = ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: holder.clone(),
+                amount: Uint128::new(400),
+            }],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let lots_msg = QueryMsg::GetLockupLots {
+        denom: denom.clone(),
+        address: holder.clone(),
+    };
+    let lots: Vec<LockupLot> =
+        from_binary(&query(deps.as_ref(), env.clone(), lots_msg.clone()).unwrap()).unwrap();
+    let unlock_at = Uint64::new(env.block.time.seconds() + period.u64());
+    assert_eq!(
+        lots,
+        vec![LockupLot {
+            amount: Uint128::new(400),
+            unlock_at,
+        }]
+    );
+
+    let msg = QueryMsg::GetLockedBalance {
+        denom: denom.clone(),
+        address: holder.clone(),
+    };
+    let balance: LockedBalance =
+        from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(balance.locked_lots, Uint128::new(400));
+    assert_eq!(balance.transferable, Uint128::new(100));
+
+    // Giving transfer access to the holder
+    let msg = ExecuteMsg::ManageRoles {
+        denom: denom.clone(),
+        roles: vec![Role::Agent {
+            update_type: UpdateType::Add(vec![holder.clone()]),
+            marker_access: vec![AccessControls::Transfer],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let locked_err = ContractError::TokensLocked {
+        denom: denom.clone(),
+        address: holder.clone(),
+        transferable: Uint128::new(100),
+    };
+
+    // Fail due to sending locked tokens
+    let send = ExecuteMsg::Send {
+        amount: Uint128::new(200),
+        denom: denom.clone(),
+        to: receiver.clone(),
+    };
+    let holder_info = mock_info(holder.as_str(), &[]);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        holder_info.clone(),
+        send.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, locked_err);
+
+    // Fail due to force transferring locked tokens
+    let msg = ExecuteMsg::ForceTransfer {
+        denom: denom.clone(),
+        params: vec![ForceTransferParams {
+            amount: Uint128::new(200),
+            from: holder.clone(),
+            to: receiver,
+        }],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, locked_err);
+
+    // Burning past the unlocked tokens consumes the lot
+    let msg = ExecuteMsg::BurnFrom {
+        burn_from_params: vec![MintBurnParams {
+            denom,
+            mint_burn_data: vec![MintBurnData {
+                address: holder,
+                amount: Uint128::new(300),
+            }],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let lots: Vec<LockupLot> =
+        from_binary(&query(deps.as_ref(), env.clone(), lots_msg).unwrap()).unwrap();
+    assert_eq!(
+        lots,
+        vec![LockupLot {
+            amount: Uint128::new(200),
+            unlock_at,
+        }]
+    );
+
+    // The lot unlocks after the lock-up period
+    let mut later = env;
+    later.block.time = later.block.time.plus_seconds(period.u64());
+    let res = execute(deps.as_mut(), later, holder_info, send).unwrap();
+    assert_eq!(1, res.messages.len());
+}
+
+#[test]
+fn test_vesting() {
+    let schedule = VestingSchedule {
+        amount: Uint128::new(1000),
+        start: Uint64::new(100),
+        cliff: Uint64::new(200),
+        end: Uint64::new(1100),
+    };
+    assert_eq!(schedule.vested(150), Uint128::zero());
+    assert_eq!(schedule.vested(600), Uint128::new(500));
+    assert_eq!(schedule.unvested(2000), Uint128::zero());
+
+    // Create default provenance mocks, the contract holds 500 tokens
+    let mut deps = mock_dependencies(&[coin(500, "budz")]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let holder = env.contract.address.clone();
+    let denom = String::from("budz");
+    let now = env.block.time.seconds();
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+
+    let mut msg = ExecuteMsg::UpdateVesting {
+        denom: denom.clone(),
+        params: vec![VestingParams {
+            address: holder.clone(),
+            schedule: UpdateKind::Set(VestingSchedule {
+                amount: Uint128::new(400),
+                start: Uint64::new(now),
+                cliff: Uint64::new(now + 100),
+                end: Uint64::new(now + 1000),
+            }),
+        }],
+    };
+
+    // Fail due to no access rights
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            err: "Address `random`: Don't have Issuer or Sub Admin rights!".into()
+        }
+    );
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Nothing is transferable from the vesting before the cliff
+    let query_msg = QueryMsg::GetLockedBalance {
+        denom: denom.clone(),
+        address: holder.clone(),
+    };
+    let balance: LockedBalance =
+        from_binary(&query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(balance.unvested, Uint128::new(400));
+    assert_eq!(balance.transferable, Uint128::new(100));
+
+    let mut later = env.clone();
+    later.block.time = later.block.time.plus_seconds(550);
+    let balance: LockedBalance =
+        from_binary(&query(deps.as_ref(), later, query_msg).unwrap()).unwrap();
+    assert_eq!(balance.unvested, Uint128::new(180));
+    assert_eq!(balance.transferable, Uint128::new(320));
+
+    // Fail due to cliff before start
+    msg = ExecuteMsg::UpdateVesting {
+        denom,
+        params: vec![VestingParams {
+            address: holder,
+            schedule: UpdateKind::Set(VestingSchedule {
+                cliff: Uint64::new(0),
+                ..schedule
+            }),
+        }],
+    };
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidVestingSchedule {
+            err: "expected start <= cliff <= end".into()
+        }
+    );
+}
+
+fn query_can_transfer(
+    deps: &OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+    from: &Addr,
+    to: &Addr,
+    amount: u128,
+) -> CanTransferResponse {
+    let msg = QueryMsg::CanTransfer {
+        denom: "budz".into(),
+        from: Some(from.clone()),
+        to: to.clone(),
+        amount: Uint128::new(amount),
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_transfer_rules() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let sender = Addr::unchecked("sender");
+    let info = mock_info(sender.as_str(), &[]);
+    let receiver = Addr::unchecked("receiver");
+    let other = Addr::unchecked("other");
+    let denom = String::from("budz");
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), other.clone()).unwrap();
+
+    // Giving transfer access to the sender
+    let msg = ExecuteMsg::ManageRoles {
+        denom: denom.clone(),
+        roles: vec![Role::Agent {
+            update_type: UpdateType::Add(vec![sender.clone()]),
+            marker_access: vec![AccessControls::Transfer],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Fail due to no access rights
+    let msg = ExecuteMsg::UpdateTransferRules {
+        denom: denom.clone(),
+        rules: vec![
+            TransferRule::MaxHolding {
+                amount: Uint128::new(400),
+            },
+            TransferRule::DailyVolumeCap {
+                amount: Uint128::new(500),
+            },
+        ],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotSubAdmin {
+            address: Addr::unchecked("random")
+        }
+    );
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Dry run reports the failing rule
+    let res = query_can_transfer(&deps, &sender, &receiver, 450);
+    assert_eq!(res.restriction, RestrictionCode::MaxHolding);
+    assert_eq!(res.code, 9);
+    assert_eq!(
+        query_can_transfer(&deps, &sender, &Addr::unchecked("unknown"), 10).restriction,
+        RestrictionCode::NotWhitelisted
+    );
+    let res = query_can_transfer(&deps, &sender, &receiver, 300);
+    assert_eq!(res.code, 0);
+    assert_eq!(res.reason, None);
+
+    // Fail due to the recipient's max holding
+    let send = |to: &Addr, amount: u128| ExecuteMsg::Send {
+        amount: Uint128::new(amount),
+        denom: denom.clone(),
+        to: to.clone(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        send(&receiver, 450),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AboveMaxHolding {
+            address: receiver.clone(),
+            max: Uint128::new(400)
+        }
+    );
+
+    // Fail due to the sender's daily volume
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        send(&receiver, 300),
+    )
+    .unwrap();
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), send(&other, 300)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DailyVolumeExceeded {
+            address: sender.clone(),
+            remaining: Uint128::new(200)
+        }
+    );
+    assert_eq!(
+        query_can_transfer(&deps, &sender, &other, 300).restriction,
+        RestrictionCode::DailyVolume
+    );
+
+    // The volume resets on the next day
+    let mut tomorrow = env.clone();
+    tomorrow.block.time = tomorrow.block.time.plus_seconds(24 * 60 * 60);
+    execute(deps.as_mut(), tomorrow, info.clone(), send(&other, 300)).unwrap();
+
+    // Holding rules count the earlier mints of the batch to the same address
+    let msg = ExecuteMsg::UpdateTransferRules {
+        denom: denom.clone(),
+        rules: vec![
+            TransferRule::MinHolding {
+                amount: Uint128::new(100),
+            },
+            TransferRule::MaxHolding {
+                amount: Uint128::new(400),
+            },
+        ],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let mint = |amounts: &[u128]| ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: amounts
+                .iter()
+                .map(|amount| MintBurnData {
+                    address: other.clone(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), mint(&[100, 50])).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), mint(&[300, 300])).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AboveMaxHolding {
+            address: other.clone(),
+            max: Uint128::new(400)
+        }
+    );
+
+    // Mints are limited to allowed investor categories
+    let msg = ExecuteMsg::UpdateTransferRules {
+        denom: denom.clone(),
+        rules: vec![TransferRule::InvestorCategories {
+            allowed: vec![InvestorCategory::Professional],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let mint = ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: receiver.clone(),
+                amount: Uint128::new(100),
+            }],
+        }],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), mint.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvestorCategoryNotAllowed {
+            denom: denom.clone(),
+            address: receiver.clone()
+        }
+    );
+
+    let msg = ExecuteMsg::UpdateInvestorCategory {
+        denom: denom.clone(),
+        params: vec![InvestorCategoryParams {
+            address: receiver.clone(),
+            category: UpdateKind::Set(InvestorCategory::Professional),
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info, mint).unwrap();
+
+    // The supply is minted to the marker and withdrawn to the receiver
+    let expected = mint_to(
+        denom.clone(),
+        MintBurnData {
+            address: receiver.clone(),
+            amount: Uint128::new(100),
+        },
+        env.contract.address.clone(),
+    )
+    .unwrap();
+    let msgs: Vec<CosmosMsg<ProvenanceMsg>> = res.messages.into_iter().map(|msg| msg.msg).collect();
+    assert_eq!(msgs, expected);
+
+    let msg = QueryMsg::GetInvestorCategory {
+        denom,
+        address: receiver,
+    };
+    let category: Option<InvestorCategory> =
+        from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(category, Some(InvestorCategory::Professional));
+}
+
+#[test]
+fn test_investor_tiers() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let sender = Addr::unchecked("sender");
+    let info = mock_info(sender.as_str(), &[]);
+    let receiver = Addr::unchecked("receiver");
+    let verifier = Addr::unchecked("verifier");
+    let denom = String::from("budz");
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+
+    // Fail due to two policies for one tier
+    let retail = TierPolicy {
+        category: InvestorCategory::Retail,
+        token_limit: Some(Uint128::new(100)),
+        actions: vec![TierAction::Receive, TierAction::Send],
+    };
+    let institutional = TierPolicy {
+        category: InvestorCategory::Institutional,
+        token_limit: Some(Uint128::new(5000)),
+        actions: vec![TierAction::Mint, TierAction::Receive, TierAction::Send],
+    };
+    let msg = ExecuteMsg::UpdateTierPolicies {
+        denom: denom.clone(),
+        policies: vec![retail.clone(), retail.clone()],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicateTierPolicy {
+            category: InvestorCategory::Retail
+        }
+    );
+
+    let msg = ExecuteMsg::UpdateTierPolicies {
+        denom: denom.clone(),
+        policies: vec![retail, institutional],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Giving transfer access to the sender
+    let msg = ExecuteMsg::ManageRoles {
+        denom: denom.clone(),
+        roles: vec![Role::Agent {
+            update_type: UpdateType::Add(vec![sender.clone()]),
+            marker_access: vec![AccessControls::Transfer],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Holders without a tier keep the token_limit of the denom
+    assert_eq!(query_can_transfer(&deps, &sender, &receiver, 500).code, 0);
+
+    // Only the verifier of the denom attests tiers besides the agents
+    let msg = ExecuteMsg::UpdateTierVerifier {
+        denom: denom.clone(),
+        verifier: UpdateKind::Set(verifier.clone()),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let attest = |category: InvestorCategory| ExecuteMsg::UpdateInvestorCategory {
+        denom: denom.clone(),
+        params: vec![InvestorCategoryParams {
+            address: receiver.clone(),
+            category: UpdateKind::Set(category),
+        }],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        attest(InvestorCategory::Retail),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            err: "Address `random`: Don't have Issuer, Transfer, Sub Admin or Verifier rights!"
+                .into()
+        }
+    );
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(verifier.as_str(), &[]),
+        attest(InvestorCategory::Retail),
+    )
+    .unwrap();
+
+    // Retail holders are capped by their tier and can't be minted to
+    let res = query_can_transfer(&deps, &sender, &receiver, 500);
+    assert_eq!(res.restriction, RestrictionCode::TokenLimitExceeded);
+    assert_eq!(query_can_transfer(&deps, &sender, &receiver, 100).code, 0);
+
+    let mint = |amount: u128| ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: receiver.clone(),
+                amount: Uint128::new(amount),
+            }],
+        }],
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), mint(50)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TierActionNotAllowed {
+            denom: denom.clone(),
+            address: receiver.clone(),
+            action: TierAction::Mint
+        }
+    );
+
+    // Institutional holders may be minted beyond the token_limit of the denom
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(verifier.as_str(), &[]),
+        attest(InvestorCategory::Institutional),
+    )
+    .unwrap();
+    execute(deps.as_mut(), env.clone(), info, mint(2000)).unwrap();
+
+    let msg = QueryMsg::GetTierPolicies {
+        denom: denom.clone(),
+    };
+    let policies: Vec<TierPolicy> =
+        from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(policies.len(), 2);
+
+    let msg = QueryMsg::GetTierVerifier { denom };
+    let res: Option<Addr> = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(res, Some(verifier));
+}
+
+fn query_transfer_restriction(
+    deps: &OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+    from: &Addr,
+    to: &Addr,
+) -> u8 {
+    let msg = QueryMsg::DetectTransferRestriction {
+        denom: "budz".into(),
+        from: from.clone(),
+        to: to.clone(),
+        amount: Uint128::new(500),
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_detect_transfer_restriction() {
+    for restriction in RestrictionCode::ALL {
+        assert_eq!(
+            RestrictionCode::from_code(restriction.code()),
+            Some(restriction)
+        );
+    }
+    assert_eq!(RestrictionCode::from_code(200), None);
+
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let sender = Addr::unchecked("sender");
+    let info = mock_info(sender.as_str(), &[]);
+    let receiver = Addr::unchecked("receiver");
+    let denom = String::from("budz");
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+
+    // Sender doesn't have transfer access
+    assert_eq!(
+        query_transfer_restriction(&deps, &sender, &receiver),
+        RestrictionCode::NoTransferAccess.code()
+    );
+    let res = query_can_transfer(&deps, &sender, &receiver, 500);
+    assert_eq!(res.restriction, RestrictionCode::NoTransferAccess);
+    let msg = ExecuteMsg::ManageRoles {
+        denom: denom.clone(),
+        roles: vec![Role::Agent {
+            update_type: UpdateType::Add(vec![sender.clone()]),
+            marker_access: vec![AccessControls::Transfer],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    // Recipient is not whitelisted
+    assert_eq!(query_transfer_restriction(&deps, &sender, &receiver), 2);
+    let msg = QueryMsg::MessageForRestriction { code: 2 };
+    let message: String = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(message, "Recipient is not whitelisted for the denom");
+
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+    assert_eq!(query_transfer_restriction(&deps, &sender, &receiver), 0);
+
+    // Recipient is freezed
+    let msg = ExecuteMsg::Freeze {
+        denom: denom.clone(),
+        update_type: UpdateType::Add(vec![receiver.clone()]),
+    };
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        query_transfer_restriction(&deps, &sender, &receiver),
+        RestrictionCode::AccountFrozen.code()
+    );
+
+    let msg = QueryMsg::MessageForRestriction { code: 200 };
+    assert!(query(deps.as_ref(), env, msg).is_err());
+}
+
+#[test]
+fn burn_from() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let sender = Addr::unchecked("sender");
+    let mut info = mock_info(sender.as_str(), &[]);
+    let denom = String::from("budz");
+    let amount = Uint128::new(500);
+
+    // Create marker
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.to_string()).unwrap();
+
+    // Whitelist address
+    do_whitelist(&mut deps, env.clone(), info.clone(), sender.clone()).unwrap();
+
+    // Try burnFrom
+    let msg = ExecuteMsg::BurnFrom {
+        burn_from_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: sender.clone(),
+                amount,
+            }],
+        }],
+    };
+
+    // Fail due to no access rights
+    info = mock_info("random", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Unauthorized {
+            err: "Address `random`: Don't have Issuer, Tokenization, Sub Admin or Burn rights!"
+                .into()
+        }
+    );
+
+    // Providing Issuer access
+    info = mock_info(sender.as_str(), &[]);
+    do_add_issuer(&mut deps, env, info.clone(), sender.clone());
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(2, res.messages.len());
+}
+
+fn query_holders(
+    deps: &OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Vec<Addr> {
+    let msg = QueryMsg::GetHolders {
+        denom: "budz".into(),
+        start_after,
+        limit,
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn query_holder_count(
+    deps: &OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+) -> u64 {
+    let msg = QueryMsg::GetHolderCount {
+        denom: "budz".into(),
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_holder_registry() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let sender = Addr::unchecked("sender");
+    let info = mock_info(sender.as_str(), &[]);
+    let denom = String::from("budz");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let carol = Addr::unchecked("carol");
+
+    do_mock_marker(&mut deps);
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    for holder in [&alice, &bob, &carol] {
+        do_whitelist(&mut deps, env.clone(), info.clone(), holder.clone()).unwrap();
+    }
+
+    // Cap the denom at two holders
+    let msg = ExecuteMsg::UpdateTransferRules {
+        denom: denom.clone(),
+        rules: vec![TransferRule::MaxHolders { limit: 2 }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let mint = |address: &Addr| ExecuteMsg::MintTo {
+        mint_to_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: address.clone(),
+                amount: Uint128::new(100),
+            }],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), mint(&alice)).unwrap();
+    execute(deps.as_mut(), env.clone(), info.clone(), mint(&bob)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), mint(&carol)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MaxHoldersReached {
+            denom: denom.clone(),
+            limit: 2
+        }
+    );
+
+    // Holders are listed a page at a time
+    assert_eq!(query_holder_count(&deps), 2);
+    assert_eq!(query_holders(&deps, None, Some(1)), vec![alice.clone()]);
+    assert_eq!(
+        query_holders(&deps, Some(alice.clone()), None),
+        vec![bob.clone()]
+    );
+
+    // Burning the whole balance removes the holder
+    do_add_issuer(&mut deps, env.clone(), info.clone(), sender.clone());
+    let msg = ExecuteMsg::BurnFrom {
+        burn_from_params: vec![MintBurnParams {
+            denom: denom.clone(),
+            mint_burn_data: vec![MintBurnData {
+                address: alice.clone(),
+                amount: Uint128::new(100),
+            }],
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(query_holder_count(&deps), 1);
+    execute(deps.as_mut(), env.clone(), info.clone(), mint(&carol)).unwrap();
+
+    // Syncing drops holders without a balance
+    let msg = ExecuteMsg::SyncHolders {
+        denom: denom.clone(),
+        addresses: vec![bob],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotSubAdmin {
+            address: Addr::unchecked("random")
+        }
+    );
+    execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(query_holders(&deps, None, None), vec![carol]);
+    assert_eq!(query_holder_count(&deps), 1);
+}
+
+fn query_corporate_action(
+    deps: &OwnedDeps<MockStorage, MockApi, ProvenanceMockQuerier, ProvenanceQuery>,
+    action_id: &str,
+) -> CorporateActionRecord {
+    let msg = QueryMsg::GetCorporateAction {
+        action_id: action_id.into(),
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn test_corporate_actions() {
     // Create default provenance mocks.
-    let mut deps = mock_dependencies(&[]);
+    let mut deps = mock_dependencies_with_balances(&[("holder", &[coin(1000, "budz")])]);
     let env = mock_env();
-    let sender = Addr::unchecked("sender");
-    let mut info = mock_info(sender.as_str(), &[]);
+    let info = mock_info("sender", &[]);
     let denom = String::from("budz");
-    let amount = Uint128::new(500);
-
-    // Create marker
-    do_create_marker(&mut deps, env.clone(), info.clone(), denom.to_string()).unwrap();
+    let holder = Addr::unchecked("holder");
+    let investor = Addr::unchecked("investor");
 
-    // Whitelist address
-    do_whitelist(&mut deps, env.clone(), info.clone(), sender.clone()).unwrap();
+    do_mock_marker(&mut deps);
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), holder.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), investor.clone()).unwrap();
 
-    // Try mintTo
-    let msg = ExecuteMsg::MintTo {
+    let mint = ExecuteMsg::MintTo {
         mint_to_params: vec![MintBurnParams {
             denom: denom.clone(),
             mint_burn_data: vec![MintBurnData {
-                address: sender.clone(),
-                amount,
+                address: investor.clone(),
+                amount: Uint128::new(1000),
             }],
         }],
     };
+    execute(deps.as_mut(), env.clone(), info.clone(), mint.clone()).unwrap();
 
-    // Fail due to no access rights
-    info = mock_info("random", &[]);
-    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    // Balances of the mocks don't change, so `holder` keeps its 1000 tokens and `investor` none
+    let msg = ExecuteMsg::SyncHolders {
+        denom: denom.clone(),
+        addresses: vec![holder.clone()],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+    let start = |action_id: &str, action: CorporateAction| ExecuteMsg::StartCorporateAction {
+        params: CorporateActionParams {
+            action_id: action_id.into(),
+            denom: denom.clone(),
+            action,
+            cash_in_lieu: Some(CashInLieuPrice {
+                payment_asset: PaymentAsset::Native {
+                    denom: "usd".into(),
+                },
+                price: Uint128::new(30),
+            }),
+        },
+    };
+
+    // Fail due to a zero ratio term
+    let msg = start(
+        "split",
+        CorporateAction::Split {
+            numerator: 0,
+            denominator: 1,
+        },
+    );
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
     assert_eq!(
         err,
-        ContractError::Unauthorized {
-            err: "Address `random`: Don't have Issuer, Tokenization, Sub Admin or Mint rights!"
-                .into()
+        ContractError::InvalidCorporateAction {
+            err: "ratio terms must be greater than zero".into()
         }
     );
 
-    // Providing Issuer access
-    info = mock_info(sender.as_str(), &[]);
-    do_add_issuer(&mut deps, env, info.clone(), sender.clone());
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-    assert_eq!(1, res.messages.len());
-}
-
-#[test]
-fn test_dvp() {
-    // Create default provenance mocks.
-    let mut deps = mock_dependencies(&[]);
-    let env = mock_env();
-    let sender = Addr::unchecked("sender");
-    let mut info = mock_info(sender.as_str(), &[]);
-    let denom = String::from("budz");
-    let amount = Uint128::new(500);
-
-    // Create marker
-    do_create_marker(&mut deps, env.clone(), info.clone(), denom.to_string()).unwrap();
+    // Fail due to a new denom without denom config
+    let msg = start(
+        "redenominate",
+        CorporateAction::Redenominate {
+            new_denom: "nugz".into(),
+            numerator: 1,
+            denominator: 100,
+        },
+    );
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MissingDenomConfig {
+            denom: "nugz".into()
+        }
+    );
 
-    // Whitelist address
-    do_whitelist(&mut deps, env.clone(), info.clone(), sender.clone()).unwrap();
+    let msg = start(
+        "split",
+        CorporateAction::Split {
+            numerator: 2,
+            denominator: 1,
+        },
+    );
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-    // Try DVP
-    let msg = ExecuteMsg::DeliveryVsPayment {
+    // Actions, transfers and DvP orders of the denom wait for the action in progress
+    let in_progress = ContractError::CorporateActionInProgress {
         denom: denom.clone(),
-        mint_data: vec![MintBurnData {
-            address: sender.clone(),
-            amount,
-        }],
+        action_id: "split".into(),
     };
+    let msg = start("reverse-split", CorporateAction::ReverseSplit { ratio: 3 });
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, in_progress);
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), mint.clone()).unwrap_err();
+    assert_eq!(err, in_progress);
+    let native = PaymentAsset::Native {
+        denom: "usd".into(),
+    };
+    let order = dvp_order_msg(&env, investor.clone(), native);
+    let buyer_info = mock_info(investor.as_str(), &[]);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        buyer_info.clone(),
+        order.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, in_progress);
 
-    // Fail due to no access rights
-    info = mock_info("random", &[]);
-    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    // Fail due to caller not being issuer or sub_admin
+    let cancel = ExecuteMsg::CancelCorporateAction {
+        action_id: "split".into(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        cancel.clone(),
+    )
+    .unwrap_err();
     assert_eq!(
         err,
         ContractError::Unauthorized {
-            err: "Address `random`: Don't have Issuer, Tokenization, Sub Admin or Mint rights!"
-                .into()
+            err: "Address `random`: Don't have Issuer or Sub Admin rights!".into()
         }
     );
 
-    // Providing Issuer access
-    info = mock_info(sender.as_str(), &[]);
-    do_add_issuer(&mut deps, env, info.clone(), sender.clone());
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-    assert_eq!(1, res.messages.len());
-}
+    // A cancelled action can't be processed or cancelled again
+    execute(deps.as_mut(), env.clone(), info.clone(), cancel.clone()).unwrap();
+    let record = query_corporate_action(&deps, "split");
+    assert_eq!(record.status, CorporateActionStatus::Cancelled);
+    assert_eq!(record.holders_processed, 0);
+    let cancelled = ContractError::CorporateActionCancelled {
+        action_id: "split".into(),
+    };
+    let msg = ExecuteMsg::ProcessCorporateAction {
+        action_id: "split".into(),
+        limit: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, cancelled);
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), cancel).unwrap_err();
+    assert_eq!(err, cancelled);
+
+    // Fail due to an open DvP order, as its escrow isn't scaled
+    execute(deps.as_mut(), env.clone(), buyer_info.clone(), order).unwrap();
+    let msg = start("reverse-split", CorporateAction::ReverseSplit { ratio: 3 });
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DvpOrdersOpen {
+            denom: denom.clone(),
+            order_id: "order-1".into()
+        }
+    );
+    let cancel_dvp = ExecuteMsg::CancelDvp {
+        order_id: "order-1".into(),
+    };
+    execute(deps.as_mut(), env.clone(), buyer_info, cancel_dvp).unwrap();
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-#[test]
-fn burn_from() {
-    // Create default provenance mocks.
-    let mut deps = mock_dependencies(&[]);
-    let env = mock_env();
-    let sender = Addr::unchecked("sender");
-    let mut info = mock_info(sender.as_str(), &[]);
-    let denom = String::from("budz");
-    let amount = Uint128::new(500);
+    // Holders are processed a batch at a time
+    let process = ExecuteMsg::ProcessCorporateAction {
+        action_id: "reverse-split".into(),
+        limit: Some(1),
+    };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), process.clone()).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    let record = query_corporate_action(&deps, "reverse-split");
+    assert_eq!(record.status, CorporateActionStatus::InProgress);
+    assert_eq!(record.holders_processed, 1);
+    assert_eq!(record.burned, Uint128::new(667));
+    assert_eq!(record.last_holder, Some(holder.clone()));
+
+    execute(deps.as_mut(), env.clone(), info.clone(), process.clone()).unwrap();
+    let record = query_corporate_action(&deps, "reverse-split");
+    assert_eq!(record.status, CorporateActionStatus::Completed);
+    assert_eq!(record.holders_processed, 2);
+    assert_eq!(record.fractions, Decimal::from_ratio(1u128, 3u128));
+    assert_eq!(record.cash_in_lieu_total, Uint128::new(10));
+
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), process).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CorporateActionCompleted {
+            action_id: "reverse-split".into()
+        }
+    );
 
-    // Create marker
-    do_create_marker(&mut deps, env.clone(), info.clone(), denom.to_string()).unwrap();
+    // The fractional token of 1000 / 3 is owed as cash in lieu
+    let msg = QueryMsg::GetCashInLieu {
+        action_id: "reverse-split".into(),
+        start_after: None,
+        limit: None,
+    };
+    let cash: Vec<CashInLieu> =
+        from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        cash,
+        vec![CashInLieu {
+            address: holder.clone(),
+            fraction: Decimal::from_ratio(1u128, 3u128),
+            amount: Uint128::new(10),
+        }]
+    );
 
-    // Whitelist address
-    do_whitelist(&mut deps, env.clone(), info.clone(), sender.clone()).unwrap();
+    // Holders left without a balance leave the registry
+    assert_eq!(query_holders(&deps, None, None), vec![holder.clone()]);
 
-    // Try burnFrom
-    let msg = ExecuteMsg::BurnFrom {
-        burn_from_params: vec![MintBurnParams {
-            denom: denom.clone(),
-            mint_burn_data: vec![MintBurnData {
-                address: sender.clone(),
-                amount,
-            }],
-        }],
+    // Fail due to burning more than the 333 tokens left minted, as the mocked balance stays 1000
+    let msg = start(
+        "reverse-split-2",
+        CorporateAction::ReverseSplit { ratio: 3 },
+    );
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::ProcessCorporateAction {
+        action_id: "reverse-split-2".into(),
+        limit: None,
     };
-
-    // Fail due to no access rights
-    info = mock_info("random", &[]);
-    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
     assert_eq!(
         err,
-        ContractError::Unauthorized {
-            err: "Address `random`: Don't have Issuer, Tokenization, Sub Admin or Burn rights!"
-                .into()
+        ContractError::BurnExceedsMinted {
+            denom: denom.clone(),
+            minted: Uint128::new(333),
+            amount: Uint128::new(667),
         }
     );
 
-    // Providing Issuer access
-    info = mock_info(sender.as_str(), &[]);
-    do_add_issuer(&mut deps, env, info.clone(), sender.clone());
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-    assert_eq!(2, res.messages.len());
+    // The stuck action is cancelled, allowing mints again
+    let msg = ExecuteMsg::CancelCorporateAction {
+        action_id: "reverse-split-2".into(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    execute(deps.as_mut(), env, info, mint).unwrap();
 }
 
 #[test]
@@ -917,6 +2622,43 @@ fn send() {
     assert_eq!(1, res.messages.len());
 }
 
+#[test]
+fn force_transfer() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies_with_balances(&[("holder", &[coin(500, "budz")])]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let denom = String::from("budz");
+    let holder = Addr::unchecked("holder");
+    let receiver = Addr::unchecked("receiver");
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), receiver.clone()).unwrap();
+
+    let msg = ExecuteMsg::ForceTransfer {
+        denom: denom.clone(),
+        params: vec![ForceTransferParams {
+            amount: Uint128::new(200),
+            from: holder.clone(),
+            to: receiver,
+        }],
+    };
+
+    // Fail due to the sender not being whitelisted
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CountryCodeAuthorizationFailed {
+            denom,
+            address: holder.clone()
+        }
+    );
+
+    do_whitelist(&mut deps, env.clone(), info.clone(), holder).unwrap();
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(1, res.messages.len());
+}
+
 #[test]
 fn query_marker() {
     let mut deps = mock_dependencies(&[]);
@@ -1819,3 +3561,248 @@ fn test_update_admin() {
     let admin: Addr = from_binary(&res).unwrap();
     assert_eq!(new_admin, admin);
 }
+
+#[test]
+fn test_account_recovery() {
+    // Create default provenance mocks.
+    let mut deps = mock_dependencies_with_balances(&[
+        ("lost", &[coin(1000, "budz")]),
+        ("funded", &[coin(10, "budz")]),
+    ]);
+    let env = mock_env();
+    let info = mock_info("sender", &[]);
+    let denom = String::from("budz");
+    let lost = Addr::unchecked("lost");
+    let new = Addr::unchecked("new_wallet");
+    let funded = Addr::unchecked("funded");
+    let buyer = Addr::unchecked("buyer");
+    let now = env.block.time.seconds();
+
+    do_create_marker(&mut deps, env.clone(), info.clone(), denom.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), lost.clone()).unwrap();
+    do_whitelist(&mut deps, env.clone(), info.clone(), buyer.clone()).unwrap();
+
+    let msgs = vec![
+        ExecuteMsg::PartialFreeze {
+            denom: denom.clone(),
+            params: vec![PartialFreezeParams {
+                address: lost.clone(),
+                update_type: UpdateType::Add(Uint128::new(100)),
+                reason: Some(1),
+                expires_at: None,
+            }],
+        },
+        ExecuteMsg::UpdateVesting {
+            denom: denom.clone(),
+            params: vec![VestingParams {
+                address: lost.clone(),
+                schedule: UpdateKind::Set(VestingSchedule {
+                    amount: Uint128::new(500),
+                    start: Uint64::new(now),
+                    cliff: Uint64::new(now + 100),
+                    end: Uint64::new(now + 1000),
+                }),
+            }],
+        },
+        ExecuteMsg::SyncHolders {
+            denom: denom.clone(),
+            addresses: vec![lost.clone()],
+        },
+    ];
+    for msg in msgs {
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    }
+
+    // An open DvP order the lost address sells in
+    let msg = ExecuteMsg::CreateDvpOrder {
+        params: DvpOrderParams {
+            order_id: "order-1".into(),
+            denom: denom.clone(),
+            amount: Uint128::new(500),
+            seller: lost.clone(),
+            buyer: buyer.clone(),
+            payment_asset: PaymentAsset::Native {
+                denom: "usd".into(),
+            },
+            price: Uint128::new(1000),
+            expiry: Uint64::new(now + 3600),
+        },
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(buyer.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+
+    let recover = |new: &Addr| ExecuteMsg::RecoverAccount {
+        denom: denom.clone(),
+        lost: lost.clone(),
+        new: new.clone(),
+        evidence_hash: "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".into(),
+    };
+
+    // Fail due to an unauthorized caller
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        recover(&new),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+    // Fail due to recovering to the lost address
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), recover(&lost)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidRecovery {
+            err: "lost and new addresses must differ".into()
+        }
+    );
+
+    // Fail due to the state of a whitelisted address being overwritten
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), recover(&buyer)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidRecovery {
+            err: "new address already has a whitelisted country code of the denom".into()
+        }
+    );
+
+    // Fail due to a balance of an address the holder registry doesn't know
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), recover(&funded)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidRecovery {
+            err: "new address already has a balance of the denom".into()
+        }
+    );
+
+    let agent = mock_info("transfer_agent", &[]);
+    execute(deps.as_mut(), env.clone(), agent.clone(), recover(&new)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), recover(&new)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RecoveryPending {
+            denom: denom.clone(),
+            address: lost.clone()
+        }
+    );
+
+    // The proposer's approval counts once
+    let approve = ExecuteMsg::ApproveRecovery {
+        denom: denom.clone(),
+        lost: lost.clone(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), agent.clone(), approve.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AlreadyApproved {
+            address: agent.sender.clone()
+        }
+    );
+
+    // The approval of a proposer who lost the role no longer counts
+    let msg = ExecuteMsg::ManageRoles {
+        denom: denom.clone(),
+        roles: vec![Role::TransferAgent {
+            update_type: UpdateType::Remove(agent.sender.clone()),
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("issuer", &[]),
+        approve.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 0);
+    let msg = QueryMsg::GetRecovery {
+        denom: denom.clone(),
+        lost: lost.clone(),
+    };
+    let recovery: Option<AccountRecovery> =
+        from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        recovery.map(|recovery| recovery.approvals),
+        Some(vec![Addr::unchecked("issuer")])
+    );
+
+    // A second agent carries out the recovery
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), approve).unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let query_msg = |msg: QueryMsg| query(deps.as_ref(), env.clone(), msg).unwrap();
+    let recovery: Option<AccountRecovery> = from_binary(&query_msg(QueryMsg::GetRecovery {
+        denom: denom.clone(),
+        lost: lost.clone(),
+    }))
+    .unwrap();
+    assert_eq!(recovery, None);
+    let recovered: Option<Addr> = from_binary(&query_msg(QueryMsg::GetRecoveredAccount {
+        denom: denom.clone(),
+        address: lost.clone(),
+    }))
+    .unwrap();
+    assert_eq!(recovered, Some(new.clone()));
+
+    // Country code, partial freezes and vesting move to the new address
+    let code: u16 = from_binary(&query_msg(QueryMsg::GetCountryCodeByAddress {
+        denom: denom.clone(),
+        address: new.clone(),
+    }))
+    .unwrap();
+    assert_eq!(code, 356);
+    let lots: Vec<FreezeLot> = from_binary(&query_msg(QueryMsg::GetFreezeLots {
+        denom: denom.clone(),
+        address: new.clone(),
+    }))
+    .unwrap();
+    assert_eq!(
+        lots,
+        vec![FreezeLot {
+            amount: Uint128::new(100),
+            reason: Some(1),
+            expires_at: None,
+        }]
+    );
+    let schedule: Option<VestingSchedule> = from_binary(&query_msg(QueryMsg::GetVesting {
+        denom: denom.clone(),
+        address: lost.clone(),
+    }))
+    .unwrap();
+    assert_eq!(schedule, None);
+
+    // The open DvP order now refunds and pays the new address
+    let order = query_dvp_order(&deps);
+    assert_eq!(order.seller, new);
+    assert_eq!(order.status, DvpStatus::Open);
+
+    // The lost address is freezed for good and the new one holds
+    let freezed: Option<Vec<Addr>> = from_binary(&query_msg(QueryMsg::GetFreezedAccounts {
+        denom: denom.clone(),
+    }))
+    .unwrap();
+    assert_eq!(freezed, Some(vec![lost.clone()]));
+    assert_eq!(query_holders(&deps, None, None), vec![new]);
+
+    let msg = ExecuteMsg::Freeze {
+        denom: denom.clone(),
+        update_type: UpdateType::Remove(vec![lost.clone()]),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::AccountRecovered { address: lost });
+
+    let msg = ExecuteMsg::UpdateRecoveryThreshold {
+        denom,
+        threshold: 1,
+    };
+    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidRecoveryThreshold { threshold: 1 }
+    );
+}
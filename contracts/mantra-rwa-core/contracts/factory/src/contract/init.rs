@@ -1,5 +1,7 @@
 #[cfg(not(feature = "library"))]
 use super::*;
+#[cfg(not(feature = "library"))]
+use cw2::set_contract_version;
 
 /// Initialize the smart contract config state, then bind a name to the contract address.
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -13,6 +15,7 @@ pub fn instantiate(
     let admin = info.sender;
     ADMIN.save(deps.storage, &admin)?;
     SUB_ADMIN.save(deps.storage, &vec![admin])?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     CODE_ID.save(deps.storage, &msg.code_id)?;
 
@@ -1,8 +1,135 @@
 #[cfg(not(feature = "library"))]
 use super::*;
+#[cfg(not(feature = "library"))]
+use cw2::{set_contract_version, CONTRACT};
 
 /// Called when migrating a contract instance to a new code ID.
+/// An instance without a contract version predates it: its country codes stored as `u8` before
+/// ISO 3166-1 codes were required are replaced by the countries given for them in
+/// `country_codes`, in denom configs and whitelists. Then the contract version is stored.
+///
+/// Fails when:-
+///     - the instance is of another contract
+///     - `country_codes` is given for an instance with a contract version
+///     - a given country is not an ISO 3166-1 alpha-2 or numeric code
+///     - a stored code has no given country
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match CONTRACT.may_load(deps.storage)? {
+        None => migrate_country_codes(deps.storage, &msg)?,
+        Some(version) => {
+            if version.contract != CONTRACT_NAME {
+                return Err(ContractError::InvalidMigration {
+                    err: format!("can't migrate contract `{}`", version.contract),
+                });
+            }
+            if !msg.country_codes.is_empty() {
+                return Err(ContractError::InvalidMigration {
+                    err: "legacy country codes are already migrated".into(),
+                });
+            }
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
+
+#[cfg(not(feature = "library"))]
+fn migrate_country_codes(storage: &mut dyn Storage, msg: &MigrateMsg) -> Result<(), ContractError> {
+    let legacy = LegacyCountryCodes::new(&msg.country_codes)?;
+
+    let denom_configs = DENOM_CONFIG
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (denom, mut denom_config) in denom_configs {
+        denom_config.country_codes = legacy.migrate_all(&denom_config.country_codes)?;
+        DENOM_CONFIG.save(storage, &denom, &denom_config)?;
+    }
+
+    let whitelist = WHITELIST
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, code) in whitelist {
+        WHITELIST.save(storage, &key, &legacy.migrate(code)?)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use country_codes::CountryCode;
+
+    fn legacy(legacy: u8, code: &str) -> LegacyCountryCode {
+        LegacyCountryCode {
+            legacy,
+            code: CountryCode::Alpha2(code.into()),
+        }
+    }
+
+    #[test]
+    fn test_migrate_country_codes() {
+        let mut deps = mock_dependencies();
+        let denom_config = DenomConfig {
+            token_limit: Uint128::new(1000),
+            country_codes: vec![91, 1, 44],
+        };
+        DENOM_CONFIG
+            .save(&mut deps.storage, b"budz", &denom_config)
+            .unwrap();
+        WHITELIST.save(&mut deps.storage, b"holder", &91).unwrap();
+
+        // Fail due to a legacy code without a country, though 44 is the numeric code of the
+        // Bahamas
+        let msg = MigrateMsg {
+            country_codes: vec![legacy(91, "IN"), legacy(1, "US")],
+        };
+        let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMigration {
+                err: "legacy country code `44` has no country given".into()
+            }
+        );
+
+        // Legacy codes are replaced by their countries
+        let msg = MigrateMsg {
+            country_codes: vec![legacy(91, "IN"), legacy(1, "US"), legacy(44, "GB")],
+        };
+        migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+        let denom_config = DENOM_CONFIG.load(&deps.storage, b"budz").unwrap();
+        assert_eq!(denom_config.country_codes, vec![356, 840, 826]);
+        assert_eq!(WHITELIST.load(&deps.storage, b"holder").unwrap(), 356);
+
+        // Fail due to migrating legacy codes again
+        let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMigration {
+                err: "legacy country codes are already migrated".into()
+            }
+        );
+
+        // Later migrations keep the stored codes
+        let msg = MigrateMsg {
+            country_codes: vec![],
+        };
+        migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+        assert_eq!(WHITELIST.load(&deps.storage, b"holder").unwrap(), 356);
+
+        // Fail due to migrating another contract
+        let mut deps = mock_dependencies();
+        set_contract_version(&mut deps.storage, "crates.io:custom-marker", "1.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMigration {
+                err: "can't migrate contract `crates.io:custom-marker`".into()
+            }
+        );
+    }
+}
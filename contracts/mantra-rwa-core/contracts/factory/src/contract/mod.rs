@@ -1,5 +1,9 @@
 use super::*;
 
+// Contract version stored on instantiate and migrate, with `cw2`
+const CONTRACT_NAME: &str = "crates.io:factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 mod execute;
 mod init;
 mod migrate;
@@ -54,10 +54,13 @@ pub enum ContractError {
     CountryCodeAuthorizationFailed { denom: String, address: Addr },
 
     #[error("Country code: `{code}` already exists!")]
-    CountryCodeAlreadyExists { code: u8 },
+    CountryCodeAlreadyExists { code: u16 },
 
     #[error("Country code: `{code}` does not exists!")]
-    CountryCodeNotExists { code: u8 },
+    CountryCodeNotExists { code: u16 },
+
+    #[error("Country code: `{code}` is not an ISO 3166-1 alpha-2 or numeric code!")]
+    InvalidCountryCode { code: String },
 
     #[error("Address:`{address}` is not an Issuer!")]
     NotAnIssuer { address: Addr },
@@ -112,4 +115,21 @@ pub enum ContractError {
 
     #[error("Address: `{addr}` doesn't exist!")]
     NotFound { addr: Addr },
+
+    #[error("Invalid migration! `{err}`")]
+    InvalidMigration { err: String },
+}
+
+impl From<InvalidCountryCode> for ContractError {
+    fn from(err: InvalidCountryCode) -> Self {
+        ContractError::InvalidCountryCode { code: err.code }
+    }
+}
+
+impl From<UnmappedCountryCode> for ContractError {
+    fn from(err: UnmappedCountryCode) -> Self {
+        ContractError::InvalidMigration {
+            err: err.to_string(),
+        }
+    }
 }
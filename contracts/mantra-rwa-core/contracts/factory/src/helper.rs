@@ -105,7 +105,7 @@ pub fn ensure_not_freezed(
     Ok(())
 }
 
-pub fn add_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(), ContractError> {
+pub fn add_country_codes(country_codes: &mut Vec<u16>, code: u16) -> Result<(), ContractError> {
     if country_codes.contains(&code) {
         return Err(ContractError::CountryCodeAlreadyExists { code });
     } else {
@@ -115,7 +115,7 @@ pub fn add_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(), Co
     Ok(())
 }
 
-pub fn remove_country_codes(country_codes: &mut Vec<u8>, code: u8) -> Result<(), ContractError> {
+pub fn remove_country_codes(country_codes: &mut Vec<u16>, code: u16) -> Result<(), ContractError> {
     if !country_codes.contains(&code) {
         return Err(ContractError::CountryCodeNotExists { code });
     } else {
@@ -129,7 +129,7 @@ pub fn get_country_code_from_whitelist(
     storage: &mut dyn Storage,
     denom: String,
     address: Addr,
-) -> Result<u8, ContractError> {
+) -> Result<u16, ContractError> {
     let key = Key::new(denom.to_string(), address.clone()).as_bytes()?;
 
     match WHITELIST.load(storage, &key) {
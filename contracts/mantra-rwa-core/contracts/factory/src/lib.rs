@@ -18,6 +18,9 @@ use cosmwasm_std::{
     DepsMut, Env, MessageInfo, Order, QueryResponse, Response, StdError, StdResult, Storage,
     Uint128,
 };
+use country_codes::{
+    InvalidCountryCode, LegacyCountryCode, LegacyCountryCodes, UnmappedCountryCode,
+};
 use cw20::{Cw20Coin, MinterResponse};
 use cw_storage_plus::{Item, Map};
 use provwasm_std::{
@@ -56,7 +56,7 @@ pub enum QueryMsg {
     #[returns(provwasm_std::Marker)]
     GetByDenom { denom: String },
 
-    #[returns(Vec<u8>)]
+    #[returns(Vec<u16>)]
     GetAuthorizedCountries { denom: String },
 
     #[returns(Option<Vec<Addr>>)]
@@ -68,7 +68,7 @@ pub enum QueryMsg {
     #[returns(DenomConfig)]
     GetDenomConfig { denom: String },
 
-    #[returns(u8)]
+    #[returns(u16)]
     GetCountryCodeByAddress { denom: String, address: Addr },
 
     #[returns(Vec<Addr>)]
@@ -101,4 +101,8 @@ pub enum QueryMsg {
 
 /// Migrate the contract.
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// ISO 3166-1 countries replacing the legacy `u8` codes of denom configs and whitelists
+    #[serde(default)]
+    pub country_codes: Vec<LegacyCountryCode>,
+}
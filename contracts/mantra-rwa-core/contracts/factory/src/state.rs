@@ -5,7 +5,7 @@ pub type Bytes<'a> = &'a [u8];
 pub const FREEZE_LIST: Map<Bytes, Vec<Addr>> = Map::new("freeze_list");
 pub const MINTED_TOKENS: Map<Bytes, Uint128> = Map::new("minted_tokens");
 pub const FROZEN_TOKENS: Map<Bytes, Uint128> = Map::new("frozen_tokens");
-pub const WHITELIST: Map<Bytes, u8> = Map::new("whitelist");
+pub const WHITELIST: Map<Bytes, u16> = Map::new("whitelist");
 pub const DENOM_CONFIG: Map<Bytes, DenomConfig> = Map::new("denom_config");
 pub const PARTIAL_FREEZE: Map<Bytes, Uint128> = Map::new("partial_freeze");
 
@@ -76,7 +76,7 @@ pub struct DenomConfig {
     /// users can only hold up to 1000 tokens.
     pub token_limit: Uint128,
     /// Country code
-    pub country_codes: Vec<u8>,
+    pub country_codes: Vec<u16>,
 }
 
 impl DenomConfig {
@@ -95,7 +95,7 @@ impl DenomConfig {
         self.token_limit -= token_limit;
     }
 
-    pub fn add_country_codes(&mut self, country_codes: Vec<u8>) -> Result<(), ContractError> {
+    pub fn add_country_codes(&mut self, country_codes: Vec<u16>) -> Result<(), ContractError> {
         for code in country_codes {
             add_country_codes(&mut self.country_codes, code)?;
         }
@@ -103,7 +103,7 @@ impl DenomConfig {
         Ok(())
     }
 
-    pub fn remove_country_codes(&mut self, country_codes: Vec<u8>) -> Result<(), ContractError> {
+    pub fn remove_country_codes(&mut self, country_codes: Vec<u16>) -> Result<(), ContractError> {
         for code in country_codes {
             remove_country_codes(&mut self.country_codes, code)?;
         }
@@ -0,0 +1,206 @@
+//! ISO 3166-1 country codes of the marker contracts, with the migration of the `u8` country
+//! codes they stored before.
+use cosmwasm_schema::cw_serde;
+use std::{
+    fmt,
+    fmt::{Display, Formatter},
+};
+use thiserror::Error;
+
+/// ISO 3166-1 countries as alpha-2 code and numeric code, sorted by alpha-2 code.
+#[rustfmt::skip]
+pub const ISO_3166: [(&str, u16); 249] = [
+    ("AD", 20), ("AE", 784), ("AF", 4), ("AG", 28), ("AI", 660), ("AL", 8),
+    ("AM", 51), ("AO", 24), ("AQ", 10), ("AR", 32), ("AS", 16), ("AT", 40),
+    ("AU", 36), ("AW", 533), ("AX", 248), ("AZ", 31), ("BA", 70), ("BB", 52),
+    ("BD", 50), ("BE", 56), ("BF", 854), ("BG", 100), ("BH", 48), ("BI", 108),
+    ("BJ", 204), ("BL", 652), ("BM", 60), ("BN", 96), ("BO", 68), ("BQ", 535),
+    ("BR", 76), ("BS", 44), ("BT", 64), ("BV", 74), ("BW", 72), ("BY", 112),
+    ("BZ", 84), ("CA", 124), ("CC", 166), ("CD", 180), ("CF", 140), ("CG", 178),
+    ("CH", 756), ("CI", 384), ("CK", 184), ("CL", 152), ("CM", 120), ("CN", 156),
+    ("CO", 170), ("CR", 188), ("CU", 192), ("CV", 132), ("CW", 531), ("CX", 162),
+    ("CY", 196), ("CZ", 203), ("DE", 276), ("DJ", 262), ("DK", 208), ("DM", 212),
+    ("DO", 214), ("DZ", 12), ("EC", 218), ("EE", 233), ("EG", 818), ("EH", 732),
+    ("ER", 232), ("ES", 724), ("ET", 231), ("FI", 246), ("FJ", 242), ("FK", 238),
+    ("FM", 583), ("FO", 234), ("FR", 250), ("GA", 266), ("GB", 826), ("GD", 308),
+    ("GE", 268), ("GF", 254), ("GG", 831), ("GH", 288), ("GI", 292), ("GL", 304),
+    ("GM", 270), ("GN", 324), ("GP", 312), ("GQ", 226), ("GR", 300), ("GS", 239),
+    ("GT", 320), ("GU", 316), ("GW", 624), ("GY", 328), ("HK", 344), ("HM", 334),
+    ("HN", 340), ("HR", 191), ("HT", 332), ("HU", 348), ("ID", 360), ("IE", 372),
+    ("IL", 376), ("IM", 833), ("IN", 356), ("IO", 86), ("IQ", 368), ("IR", 364),
+    ("IS", 352), ("IT", 380), ("JE", 832), ("JM", 388), ("JO", 400), ("JP", 392),
+    ("KE", 404), ("KG", 417), ("KH", 116), ("KI", 296), ("KM", 174), ("KN", 659),
+    ("KP", 408), ("KR", 410), ("KW", 414), ("KY", 136), ("KZ", 398), ("LA", 418),
+    ("LB", 422), ("LC", 662), ("LI", 438), ("LK", 144), ("LR", 430), ("LS", 426),
+    ("LT", 440), ("LU", 442), ("LV", 428), ("LY", 434), ("MA", 504), ("MC", 492),
+    ("MD", 498), ("ME", 499), ("MF", 663), ("MG", 450), ("MH", 584), ("MK", 807),
+    ("ML", 466), ("MM", 104), ("MN", 496), ("MO", 446), ("MP", 580), ("MQ", 474),
+    ("MR", 478), ("MS", 500), ("MT", 470), ("MU", 480), ("MV", 462), ("MW", 454),
+    ("MX", 484), ("MY", 458), ("MZ", 508), ("NA", 516), ("NC", 540), ("NE", 562),
+    ("NF", 574), ("NG", 566), ("NI", 558), ("NL", 528), ("NO", 578), ("NP", 524),
+    ("NR", 520), ("NU", 570), ("NZ", 554), ("OM", 512), ("PA", 591), ("PE", 604),
+    ("PF", 258), ("PG", 598), ("PH", 608), ("PK", 586), ("PL", 616), ("PM", 666),
+    ("PN", 612), ("PR", 630), ("PS", 275), ("PT", 620), ("PW", 585), ("PY", 600),
+    ("QA", 634), ("RE", 638), ("RO", 642), ("RS", 688), ("RU", 643), ("RW", 646),
+    ("SA", 682), ("SB", 90), ("SC", 690), ("SD", 729), ("SE", 752), ("SG", 702),
+    ("SH", 654), ("SI", 705), ("SJ", 744), ("SK", 703), ("SL", 694), ("SM", 674),
+    ("SN", 686), ("SO", 706), ("SR", 740), ("SS", 728), ("ST", 678), ("SV", 222),
+    ("SX", 534), ("SY", 760), ("SZ", 748), ("TC", 796), ("TD", 148), ("TF", 260),
+    ("TG", 768), ("TH", 764), ("TJ", 762), ("TK", 772), ("TL", 626), ("TM", 795),
+    ("TN", 788), ("TO", 776), ("TR", 792), ("TT", 780), ("TV", 798), ("TW", 158),
+    ("TZ", 834), ("UA", 804), ("UG", 800), ("UM", 581), ("US", 840), ("UY", 858),
+    ("UZ", 860), ("VA", 336), ("VC", 670), ("VE", 862), ("VG", 92), ("VI", 850),
+    ("VN", 704), ("VU", 548), ("WF", 876), ("WS", 882), ("YE", 887), ("YT", 175),
+    ("ZA", 710), ("ZM", 894), ("ZW", 716),
+];
+
+/// Function to get the numeric code of an ISO 3166-1 alpha-2 code, in any case
+pub fn numeric_country_code(alpha_2: &str) -> Option<u16> {
+    let alpha_2 = alpha_2.to_ascii_uppercase();
+    ISO_3166
+        .binary_search_by(|(code, _)| code.cmp(&alpha_2.as_str()))
+        .ok()
+        .map(|index| ISO_3166[index].1)
+}
+
+/// Function to check a numeric code is assigned by ISO 3166-1
+pub fn is_country_code(code: u16) -> bool {
+    ISO_3166.iter().any(|(_, numeric)| *numeric == code)
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Country code: `{code}` is not an ISO 3166-1 alpha-2 or numeric code!")]
+pub struct InvalidCountryCode {
+    pub code: String,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("legacy country code `{code}` has no country given")]
+pub struct UnmappedCountryCode {
+    pub code: u16,
+}
+
+/// ISO 3166-1 country, as alpha-2 code (`"US"`) or numeric code (`840`). Stored numeric.
+#[cw_serde]
+#[serde(untagged)]
+pub enum CountryCode {
+    Numeric(u16),
+    Alpha2(String),
+}
+
+impl CountryCode {
+    /// Numeric code, when the country is assigned by ISO 3166-1
+    pub fn numeric(&self) -> Result<u16, InvalidCountryCode> {
+        let code = match self {
+            Self::Numeric(code) => Some(*code).filter(|code| is_country_code(*code)),
+            Self::Alpha2(code) => numeric_country_code(code),
+        };
+
+        code.ok_or_else(|| InvalidCountryCode {
+            code: self.to_string(),
+        })
+    }
+}
+
+impl Display for CountryCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Numeric(code) => write!(f, "{}", code),
+            Self::Alpha2(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+/// Country of a code stored before ISO 3166-1 codes were required.
+#[cw_serde]
+pub struct LegacyCountryCode {
+    pub legacy: u8,
+    pub code: CountryCode,
+}
+
+/// Numeric codes replacing the legacy codes of a migrate message.
+pub struct LegacyCountryCodes(Vec<(u16, u16)>);
+
+impl LegacyCountryCodes {
+    /// Fails when a given country is not an ISO 3166-1 alpha-2 or numeric code
+    pub fn new(countries: &[LegacyCountryCode]) -> Result<Self, InvalidCountryCode> {
+        let codes = countries
+            .iter()
+            .map(|country| Ok((u16::from(country.legacy), country.code.numeric()?)))
+            .collect::<Result<Vec<(u16, u16)>, InvalidCountryCode>>()?;
+
+        Ok(Self(codes))
+    }
+
+    /// Code replacing a stored code: the country given for it. Fails when no country is given,
+    /// as legacy dialing codes that are also ISO 3166-1 numeric codes, e.g. 44, name another
+    /// country.
+    pub fn migrate(&self, code: u16) -> Result<u16, UnmappedCountryCode> {
+        self.0
+            .iter()
+            .find(|(from, _)| *from == code)
+            .map(|(_, to)| *to)
+            .ok_or(UnmappedCountryCode { code })
+    }
+
+    /// Codes replacing a list of stored codes, without the duplicates they become
+    pub fn migrate_all(&self, codes: &[u16]) -> Result<Vec<u16>, UnmappedCountryCode> {
+        let mut migrated: Vec<u16> = Vec::new();
+        for code in codes {
+            let code = self.migrate(*code)?;
+            if !migrated.contains(&code) {
+                migrated.push(code);
+            }
+        }
+
+        Ok(migrated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_code() {
+        assert!(ISO_3166.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(CountryCode::Alpha2("in".into()).numeric(), Ok(356));
+        assert_eq!(CountryCode::Numeric(840).numeric(), Ok(840));
+        assert_eq!(
+            CountryCode::Numeric(91).numeric(),
+            Err(InvalidCountryCode { code: "91".into() })
+        );
+        assert_eq!(
+            CountryCode::Alpha2("XX".into()).numeric(),
+            Err(InvalidCountryCode { code: "XX".into() })
+        );
+    }
+
+    #[test]
+    fn test_legacy_country_codes() {
+        let legacy = LegacyCountryCodes::new(&[
+            LegacyCountryCode {
+                legacy: 91,
+                code: CountryCode::Alpha2("IN".into()),
+            },
+            LegacyCountryCode {
+                legacy: 1,
+                code: CountryCode::Numeric(840),
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(legacy.migrate(91), Ok(356));
+        assert_eq!(legacy.migrate(7), Err(UnmappedCountryCode { code: 7 }));
+        // 44 is the dialing code of the UK, but the numeric code of the Bahamas
+        assert_eq!(legacy.migrate(44), Err(UnmappedCountryCode { code: 44 }));
+        assert_eq!(legacy.migrate_all(&[91, 1, 91]), Ok(vec![356, 840]));
+
+        let err = LegacyCountryCodes::new(&[LegacyCountryCode {
+            legacy: 2,
+            code: CountryCode::Alpha2("XX".into()),
+        }])
+        .err();
+        assert_eq!(err, Some(InvalidCountryCode { code: "XX".into() }));
+    }
+}
@@ -39,14 +39,9 @@ if [ "$GENERATE_PROOF" = true ]; then
     
     cd circuits/compliance-proofs/crates/mantra-script
     
-    RUST_LOG=info cargo run --release --bin mantra
-    
-    # Copy generated proof to fixtures for future use
-    if [ -f "../../../../proof_output.json" ]; then
-        mkdir -p ../../fixtures
-        cp ../../../../proof_output.json ../../fixtures/
-        echo "✅ Proof generated and cached to fixtures/"
-    fi
+    RUST_LOG=info cargo run --release --bin mantra -- prove ../../fixtures/proof_request.json \
+        --prover local --output ../../fixtures/proof_output.json
+    echo "✅ Proof generated and cached to fixtures/"
     
     cd ../../../..
 else
@@ -82,6 +77,8 @@ echo "To deploy to MANTRA testnet:"
 echo "   wasmd tx wasm store artifacts/verifier.wasm --from <key> --gas auto"
 echo ""
 echo "To verify the proof on-chain:"
-echo "   wasmd tx wasm execute <CONTRACT> '{\"mint_rwa_asset\": {...}}' --from <key>"
+echo "   cd circuits/compliance-proofs/crates/mantra-script"
+echo "   MANTRA_SIGNER_KEY=<hex key> cargo run --bin mantra -- submit ../../fixtures/proof_request.json \\"
+echo "       --output ../../fixtures/proof_output.json --contract <CONTRACT> --rest <LCD URL> --broadcast"
 echo ""
 echo "🎉 DEMO COMPLETE: Z-RWA System Ready for Grant Review"